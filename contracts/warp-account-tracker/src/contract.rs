@@ -1,12 +1,16 @@
-use crate::execute::config::update_config;
 use crate::state::CONFIG;
 use crate::{execute, query, ContractError};
 use account_tracker::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use common::migrate::ensure_migratable;
 use cosmwasm_std::{
     entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
 };
+use cw2::set_contract_version;
 use cw_utils::nonpayable;
 
+pub const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -23,6 +27,7 @@ pub fn instantiate(
             warp_addr: deps.api.addr_validate(&msg.warp_addr)?,
         },
     )?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
@@ -40,7 +45,16 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin && info.sender != config.warp_addr {
+
+    // Pending admin is not authorized yet, the ownership flow checks the sender itself
+    let is_ownership_msg = matches!(
+        msg,
+        ExecuteMsg::ProposeOwner(_)
+            | ExecuteMsg::AcceptOwnership(_)
+            | ExecuteMsg::CancelOwnershipProposal(_)
+    );
+
+    if !is_ownership_msg && info.sender != config.admin && info.sender != config.warp_addr {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -61,7 +75,22 @@ pub fn execute(
             nonpayable(&info).unwrap();
             execute::account::free_funding_account(deps, data)
         }
-        ExecuteMsg::UpdateConfig(data) => update_config(deps, env, info, data),
+        ExecuteMsg::UpdateConfig(data) => {
+            nonpayable(&info).unwrap();
+            execute::config::update_config(deps, env, info, data, config)
+        }
+        ExecuteMsg::ProposeOwner(data) => {
+            nonpayable(&info).unwrap();
+            execute::config::propose_owner(deps, env, info, data, config)
+        }
+        ExecuteMsg::AcceptOwnership(data) => {
+            nonpayable(&info).unwrap();
+            execute::config::accept_ownership(deps, env, info, data, config)
+        }
+        ExecuteMsg::CancelOwnershipProposal(data) => {
+            nonpayable(&info).unwrap();
+            execute::config::cancel_ownership_proposal(deps, env, info, data, config)
+        }
     }
 }
 
@@ -69,6 +98,7 @@ pub fn execute(
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::QueryConfig(_) => to_binary(&query::account::query_config(deps)?),
+        QueryMsg::QueryPendingOwner(_) => to_binary(&query::account::query_pending_owner(deps)?),
         QueryMsg::QueryAccounts(data) => to_binary(&query::account::query_accounts(deps, data)?),
         QueryMsg::QueryFundingAccounts(data) => {
             to_binary(&query::account::query_funding_accounts(deps, data)?)
@@ -92,6 +122,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::new())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_migratable(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &msg.version)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("version", CONTRACT_VERSION))
 }
@@ -1,4 +1,6 @@
 use crate::ContractError::{DecodeError, DeserializationError, SerializationError};
+use common::migrate::MigrationError;
+use common::ownership::OwnershipError;
 use cosmwasm_std::StdError;
 use thiserror::Error;
 
@@ -7,6 +9,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Migration(#[from] MigrationError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -1,22 +1,40 @@
-use account_tracker::UpdateConfigMsg;
+use account_tracker::{
+    AcceptOwnershipMsg, CancelOwnershipProposalMsg, Config, ProposeOwnerMsg, UpdateConfigMsg,
+};
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 
-use crate::{state::CONFIG, ContractError};
+use crate::{
+    state::{CONFIG, OWNERSHIP},
+    ContractError,
+};
 
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     data: UpdateConfigMsg,
+    mut config: Config,
 ) -> Result<Response, ContractError> {
-    let mut config = CONFIG.load(deps.storage)?;
-
     if info.sender != config.admin {
         return Err(ContractError::Unauthorized {});
     }
 
-    config.admin = match data.admin {
-        None => config.admin,
+    if let Some(admin) = &data.admin {
+        OWNERSHIP.propose(
+            deps.storage,
+            deps.api,
+            &env,
+            &info.sender,
+            &config.admin,
+            ProposeOwnerMsg {
+                owner: admin.clone(),
+                expires_at_time: None,
+            },
+        )?;
+    }
+
+    config.warp_addr = match data.warp_addr {
+        None => config.warp_addr,
         Some(data) => deps.api.addr_validate(data.as_str())?,
     };
 
@@ -24,5 +42,49 @@ pub fn update_config(
 
     Ok(Response::new()
         .add_attribute("action", "update_config")
-        .add_attribute("config_admin", config.admin))
+        .add_attribute("config_admin", config.admin)
+        .add_attribute("config_warp_addr", config.warp_addr)
+        .add_attributes(data.admin.map(|admin| ("pending_admin", admin))))
+}
+
+pub fn propose_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: ProposeOwnerMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    Ok(OWNERSHIP.propose(
+        deps.storage,
+        deps.api,
+        &env,
+        &info.sender,
+        &config.admin,
+        data,
+    )?)
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    _data: AcceptOwnershipMsg,
+    mut config: Config,
+) -> Result<Response, ContractError> {
+    let (owner, response) = OWNERSHIP.accept(deps.storage, &env, &info.sender, &config.admin)?;
+
+    config.admin = owner;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+pub fn cancel_ownership_proposal(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _data: CancelOwnershipProposalMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    Ok(OWNERSHIP.cancel(deps.storage, &info.sender, &config.admin)?)
 }
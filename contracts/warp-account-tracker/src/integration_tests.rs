@@ -1,11 +1,13 @@
 #[cfg(test)]
 mod tests {
     use account_tracker::{
-        AccountStatus, Config, ConfigResponse, ExecuteMsg, FreeJobAccountMsg, InstantiateMsg,
-        JobAccount, JobAccountResponse, JobAccountsResponse, QueryConfigMsg,
-        QueryFirstFreeJobAccountMsg, QueryJobAccountsMsg, QueryMsg, TakeJobAccountMsg,
+        AcceptOwnershipMsg, AccountStatus, Config, ConfigResponse, ExecuteMsg, FreeJobAccountMsg,
+        InstantiateMsg, JobAccount, JobAccountResponse, JobAccountsResponse, PendingOwner,
+        PendingOwnerResponse, ProposeOwnerMsg, QueryConfigMsg, QueryFirstFreeJobAccountMsg,
+        QueryJobAccountsMsg, QueryMsg, QueryPendingOwnerMsg, TakeJobAccountMsg, UpdateConfigMsg,
     };
     use anyhow::Result as AnyResult;
+    use common::ownership::OwnershipError;
     use cosmwasm_std::{Addr, Coin, Empty, Uint128, Uint64};
     use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
 
@@ -21,6 +23,7 @@ mod tests {
     const DUMMY_WARP_ACCOUNT_3_ADDR: &str = "terra5";
     const DUMMY_JOB_1_ID: Uint64 = Uint64::zero();
     const DUMMY_JOB_2_ID: Uint64 = Uint64::one();
+    const NEW_ADMIN: &str = "terra6";
 
     fn mock_app() -> App {
         AppBuilder::new().build(|router, _, storage| {
@@ -385,4 +388,147 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn warp_account_tracker_contract_multi_test_ownership_transfer() {
+        let mut app = mock_app();
+        let warp_account_tracker_contract_code_id = app.store_code(contract_warp_account_tracker());
+        let warp_account_tracker_contract_addr =
+            init_warp_account_tracker(&mut app, warp_account_tracker_contract_code_id);
+
+        // Only admin can propose, warp controller is not allowed to
+        assert_err(
+            app.execute_contract(
+                Addr::unchecked(DUMMY_WARP_CONTROLLER_ADDR),
+                warp_account_tracker_contract_addr.clone(),
+                &ExecuteMsg::ProposeOwner(ProposeOwnerMsg {
+                    owner: NEW_ADMIN.to_string(),
+                    expires_at_time: None,
+                }),
+                &[],
+            ),
+            ContractError::Ownership(OwnershipError::Unauthorized {}),
+        );
+
+        let _ = app.execute_contract(
+            Addr::unchecked(USER_1),
+            warp_account_tracker_contract_addr.clone(),
+            &ExecuteMsg::ProposeOwner(ProposeOwnerMsg {
+                owner: NEW_ADMIN.to_string(),
+                expires_at_time: None,
+            }),
+            &[],
+        );
+
+        assert_eq!(
+            app.wrap().query_wasm_smart(
+                warp_account_tracker_contract_addr.clone(),
+                &QueryMsg::QueryPendingOwner(QueryPendingOwnerMsg {})
+            ),
+            Ok(PendingOwnerResponse {
+                pending_owner: Some(PendingOwner {
+                    owner: Addr::unchecked(NEW_ADMIN),
+                    expires_at_time: None,
+                })
+            })
+        );
+
+        // Admin is unchanged until the proposal is accepted
+        assert_err(
+            app.execute_contract(
+                Addr::unchecked(USER_1),
+                warp_account_tracker_contract_addr.clone(),
+                &ExecuteMsg::AcceptOwnership(AcceptOwnershipMsg {}),
+                &[],
+            ),
+            ContractError::Ownership(OwnershipError::Unauthorized {}),
+        );
+
+        let _ = app.execute_contract(
+            Addr::unchecked(NEW_ADMIN),
+            warp_account_tracker_contract_addr.clone(),
+            &ExecuteMsg::AcceptOwnership(AcceptOwnershipMsg {}),
+            &[],
+        );
+
+        assert_eq!(
+            app.wrap().query_wasm_smart(
+                warp_account_tracker_contract_addr.clone(),
+                &QueryMsg::QueryConfig(QueryConfigMsg {})
+            ),
+            Ok(ConfigResponse {
+                config: Config {
+                    admin: Addr::unchecked(NEW_ADMIN),
+                    warp_addr: Addr::unchecked(DUMMY_WARP_CONTROLLER_ADDR),
+                }
+            })
+        );
+        assert_eq!(
+            app.wrap().query_wasm_smart(
+                warp_account_tracker_contract_addr,
+                &QueryMsg::QueryPendingOwner(QueryPendingOwnerMsg {})
+            ),
+            Ok(PendingOwnerResponse {
+                pending_owner: None
+            })
+        );
+    }
+
+    #[test]
+    fn warp_account_tracker_contract_multi_test_update_config() {
+        let mut app = mock_app();
+        let warp_account_tracker_contract_code_id = app.store_code(contract_warp_account_tracker());
+        let warp_account_tracker_contract_addr =
+            init_warp_account_tracker(&mut app, warp_account_tracker_contract_code_id);
+
+        // Only admin can update config
+        assert_err(
+            app.execute_contract(
+                Addr::unchecked(DUMMY_WARP_CONTROLLER_ADDR),
+                warp_account_tracker_contract_addr.clone(),
+                &ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+                    admin: None,
+                    warp_addr: Some(DUMMY_WARP_ACCOUNT_1_ADDR.to_string()),
+                }),
+                &[],
+            ),
+            ContractError::Unauthorized {},
+        );
+
+        let _ = app.execute_contract(
+            Addr::unchecked(USER_1),
+            warp_account_tracker_contract_addr.clone(),
+            &ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+                admin: Some(NEW_ADMIN.to_string()),
+                warp_addr: Some(DUMMY_WARP_ACCOUNT_1_ADDR.to_string()),
+            }),
+            &[],
+        );
+
+        // Warp addr is updated directly, admin is only proposed
+        assert_eq!(
+            app.wrap().query_wasm_smart(
+                warp_account_tracker_contract_addr.clone(),
+                &QueryMsg::QueryConfig(QueryConfigMsg {})
+            ),
+            Ok(ConfigResponse {
+                config: Config {
+                    admin: Addr::unchecked(USER_1),
+                    warp_addr: Addr::unchecked(DUMMY_WARP_ACCOUNT_1_ADDR),
+                }
+            })
+        );
+        assert_eq!(
+            app.wrap().query_wasm_smart(
+                warp_account_tracker_contract_addr,
+                &QueryMsg::QueryPendingOwner(QueryPendingOwnerMsg {})
+            ),
+            Ok(PendingOwnerResponse {
+                pending_owner: Some(PendingOwner {
+                    owner: Addr::unchecked(NEW_ADMIN),
+                    expires_at_time: None,
+                })
+            })
+        );
+    }
 }
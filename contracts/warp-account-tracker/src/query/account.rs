@@ -2,14 +2,14 @@ use cosmwasm_std::{Deps, Order, StdResult};
 use cw_storage_plus::{Bound, PrefixBound};
 
 use crate::state::{
-    ACCOUNTS, CONFIG, FREE_FUNDING_ACCOUNTS, FREE_JOB_ACCOUNTS, TAKEN_FUNDING_ACCOUNTS,
+    ACCOUNTS, CONFIG, FREE_FUNDING_ACCOUNTS, FREE_JOB_ACCOUNTS, OWNERSHIP, TAKEN_FUNDING_ACCOUNTS,
     TAKEN_JOB_ACCOUNTS,
 };
 
 use account_tracker::{
     Account, AccountStatus, AccountsResponse, ConfigResponse, FundingAccount,
     FundingAccountResponse, FundingAccountsResponse, JobAccount, JobAccountResponse,
-    JobAccountsResponse, QueryAccountsMsg, QueryFirstFreeFundingAccountMsg,
+    JobAccountsResponse, PendingOwnerResponse, QueryAccountsMsg, QueryFirstFreeFundingAccountMsg,
     QueryFirstFreeJobAccountMsg, QueryFundingAccountMsg, QueryFundingAccountsMsg,
     QueryJobAccountMsg, QueryJobAccountsMsg,
 };
@@ -21,6 +21,10 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     Ok(ConfigResponse { config })
 }
 
+pub fn query_pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+    OWNERSHIP.query(deps.storage)
+}
+
 pub fn query_accounts(deps: Deps, data: QueryAccountsMsg) -> StdResult<AccountsResponse> {
     let account_owner_ref = &deps.api.addr_validate(data.account_owner_addr.as_str())?;
     let start_after = data
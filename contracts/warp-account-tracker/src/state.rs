@@ -1,8 +1,10 @@
 use account_tracker::{Account, Config};
+use common::ownership::Ownership;
 use cosmwasm_std::{Addr, Uint64};
 use cw_storage_plus::{Item, Map};
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const OWNERSHIP: Ownership = Ownership::new("pending_owner");
 
 // Key is the (account owner address, account address), value is the account struct
 pub const ACCOUNTS: Map<(&Addr, &Addr), Account> = Map::new("accounts");
@@ -1,10 +1,15 @@
 use crate::state::CONFIG;
 use crate::{query, ContractError};
 use account::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use common::migrate::ensure_migratable;
 use controller::account::{execute_warp_msgs, warp_msgs_to_cosmos_msgs};
 use cosmwasm_std::{
     entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
 };
+use cw2::set_contract_version;
+
+pub const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -20,6 +25,7 @@ pub fn instantiate(
     };
 
     CONFIG.save(deps.storage, &config)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let msgs = warp_msgs_to_cosmos_msgs(deps.as_ref(), env, msg.msgs, &config.owner).unwrap();
 
@@ -63,6 +69,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::new())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_migratable(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &msg.version)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("version", CONTRACT_VERSION))
 }
@@ -1,4 +1,5 @@
 use crate::ContractError::{DecodeError, DeserializationError, SerializationError};
+use common::migrate::MigrationError;
 use cosmwasm_std::StdError;
 use thiserror::Error;
 
@@ -7,6 +8,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Migration(#[from] MigrationError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -7,11 +7,19 @@ use cw_utils::nonpayable;
 use crate::{
     execute, migrate, query, reply,
     state::{CONFIG, STATE},
-    util::msg::build_instantiate_account_tracker_msg,
+    util::{config::ValidateConfig, msg::build_instantiate_account_tracker_msg},
     ContractError,
 };
 
-use controller::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, State};
+use common::migrate::ensure_migratable;
+use controller::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, PausableAction, QueryMsg, State};
+use cw2::set_contract_version;
+
+pub const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Config update timelock set on migration for deployments that predate it
+pub const DEFAULT_CONFIG_UPDATE_DELAY_SECONDS: u64 = 3 * 24 * 60 * 60;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -51,40 +59,23 @@ pub fn instantiate(
         queue_size_right: msg.queue_size_right,
         burn_fee_rate: msg.burn_fee_rate,
         cancellation_fee_rate: msg.cancellation_fee_rate,
+        fee_tier_stake_contract: msg
+            .fee_tier_stake_contract
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        guardian: msg
+            .guardian
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        paused: vec![],
+        config_update_delay_seconds: msg.config_update_delay_seconds,
     };
 
-    if config.creation_fee_max < config.creation_fee_min {
-        return Err(ContractError::CreationMaxFeeUnderMinFee {});
-    }
-
-    if config.maintenance_fee_max < config.maintenance_fee_min {
-        return Err(ContractError::MaintenanceMaxFeeUnderMinFee {});
-    }
-
-    if config.duration_days_max <= config.duration_days_min {
-        return Err(ContractError::DurationMaxDaysUnderMinDays {});
-    }
-
-    if config.cancellation_fee_rate.u64() > 100 {
-        return Err(ContractError::CancellationFeeTooHigh {});
-    }
-
-    if config.burn_fee_rate.u128() > 100 {
-        return Err(ContractError::BurnFeeTooHigh {});
-    }
-
-    if config.queue_size_right <= config.queue_size_left {
-        return Err(ContractError::QueueSizeRightUnderQueueSizeLeft {});
-    }
-
-    if config.duration_days_max > config.duration_days_limit
-        || config.duration_days_min > config.duration_days_limit
-    {
-        return Err(ContractError::DurationDaysLimit {});
-    }
+    config.validate()?;
 
     STATE.save(deps.storage, &state)?;
     CONFIG.save(deps.storage, &config)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let submsgs = vec![SubMsg {
         id: REPLY_ID_INSTANTIATE_SUB_CONTRACTS,
@@ -109,14 +100,20 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     match msg {
-        ExecuteMsg::CreateJob(data) => execute::job::create_job(deps, env, info, data, config),
+        ExecuteMsg::CreateJob(data) => {
+            ensure_not_paused(&config, PausableAction::CreateJob)?;
+            execute::job::create_job(deps, env, info, data, config)
+        }
+        // Deleting jobs is never paused so users can always exit
         ExecuteMsg::DeleteJob(data) => execute::job::delete_job(deps, env, info, data, config),
         ExecuteMsg::UpdateJob(data) => execute::job::update_job(deps, env, info, data),
         ExecuteMsg::ExecuteJob(data) => {
+            ensure_not_paused(&config, PausableAction::ExecuteJob)?;
             nonpayable(&info).unwrap();
             execute::job::execute_job(deps, env, info, data, config)
         }
         ExecuteMsg::EvictJob(data) => {
+            ensure_not_paused(&config, PausableAction::EvictJob)?;
             nonpayable(&info).unwrap();
             execute::job::evict_job(deps, env, info, data, config)
         }
@@ -124,26 +121,77 @@ pub fn execute(
             nonpayable(&info).unwrap();
             execute::controller::update_config(deps, env, info, data, config)
         }
+        ExecuteMsg::ScheduleConfigUpdate(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::schedule_config_update(deps, env, info, data, config)
+        }
+        ExecuteMsg::ExecuteConfigUpdate(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::execute_config_update(deps, env, info, data, config)
+        }
+        ExecuteMsg::CancelConfigUpdate(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::cancel_config_update(deps, env, info, data, config)
+        }
         ExecuteMsg::MigrateAccounts(data) => {
             nonpayable(&info).unwrap();
             migrate::account::migrate_accounts(deps.as_ref(), env, info, data, config)
         }
 
-        ExecuteMsg::MigratePendingJobs(data) => {
-            nonpayable(&info).unwrap();
-            migrate::job::migrate_pending_jobs(deps, env, info, data)
-        }
-        ExecuteMsg::MigrateFinishedJobs(data) => {
+        ExecuteMsg::MigrateJobs(data) => {
             nonpayable(&info).unwrap();
-            migrate::job::migrate_finished_jobs(deps, env, info, data)
+            migrate::job::migrate_jobs(deps, env, info, data, config)
         }
 
         ExecuteMsg::CreateFundingAccount(data) => {
             execute::account::create_funding_account(deps, env, info, data)
         }
+
+        ExecuteMsg::SetFeeTier(data) => {
+            nonpayable(&info).unwrap();
+            execute::fee::set_fee_tier(deps, env, info, data, config)
+        }
+        ExecuteMsg::RemoveFeeTier(data) => {
+            nonpayable(&info).unwrap();
+            execute::fee::remove_fee_tier(deps, env, info, data, config)
+        }
+        ExecuteMsg::AssignFeeTier(data) => {
+            nonpayable(&info).unwrap();
+            execute::fee::assign_fee_tier(deps, env, info, data, config)
+        }
+
+        ExecuteMsg::SetPaused(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::set_paused(deps, env, info, data, config)
+        }
+        ExecuteMsg::RemoveGuardian(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::remove_guardian(deps, env, info, data, config)
+        }
+
+        ExecuteMsg::ProposeOwner(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::propose_owner(deps, env, info, data, config)
+        }
+        ExecuteMsg::AcceptOwnership(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::accept_ownership(deps, env, info, data, config)
+        }
+        ExecuteMsg::CancelOwnershipProposal(data) => {
+            nonpayable(&info).unwrap();
+            execute::controller::cancel_ownership_proposal(deps, env, info, data, config)
+        }
     }
 }
 
+fn ensure_not_paused(config: &Config, action: PausableAction) -> Result<(), ContractError> {
+    if config.is_paused(&action) {
+        return Err(ContractError::ActionPaused { action });
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -153,12 +201,36 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&query::controller::query_config(deps, env, data)?)
         }
         QueryMsg::QueryState(data) => to_binary(&query::controller::query_state(deps, env, data)?),
+        QueryMsg::QueryPendingOwner(data) => {
+            to_binary(&query::controller::query_pending_owner(deps, env, data)?)
+        }
+        QueryMsg::QueryPendingConfigUpdates(data) => to_binary(
+            &query::controller::query_pending_config_updates(deps, env, data)?,
+        ),
+        QueryMsg::QueryFeeTier(data) => to_binary(&query::fee::query_fee_tier(deps, env, data)?),
+        QueryMsg::QueryFeeTiers(data) => to_binary(&query::fee::query_fee_tiers(deps, env, data)?),
+        QueryMsg::QueryAddressFeeTier(data) => {
+            to_binary(&query::fee::query_address_fee_tier(deps, env, data)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::new())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_migratable(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &msg.version)?;
+
+    let config_update_delay_backfilled =
+        migrate::config::backfill_config_update_delay(deps.storage)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("version", CONTRACT_VERSION)
+        .add_attribute(
+            "config_update_delay_backfilled",
+            config_update_delay_backfilled.to_string(),
+        ))
 }
 
 // first 10 slots (0-9) are reserved for custom reply calls
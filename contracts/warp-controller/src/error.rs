@@ -1,4 +1,7 @@
 use crate::ContractError::{CustomError, DecodeError, DeserializationError, SerializationError};
+use common::migrate::MigrationError;
+use common::ownership::OwnershipError;
+use controller::PausableAction;
 use cosmwasm_std::{DivideByZeroError, OverflowError, StdError};
 use std::num::ParseIntError;
 use std::str::ParseBoolError;
@@ -9,6 +12,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Migration(#[from] MigrationError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -100,11 +109,32 @@ pub enum ContractError {
     #[error("Queue size right smaller than queue size left.")]
     QueueSizeRightUnderQueueSizeLeft {},
 
+    #[error("Fee denom cannot be empty.")]
+    FeeDenomEmpty {},
+
     #[error("Eviction period not elapsed.")]
     EvictionPeriodNotElapsed {},
 
     #[error("Unknown reply ID.")]
     UnknownReplyId {},
+
+    #[error("Fee tier discount cannot exceed 100 percent.")]
+    FeeTierDiscountTooHigh {},
+
+    #[error("Fee tier does not exist.")]
+    FeeTierDoesNotExist {},
+
+    #[error("Action paused: {action:?}")]
+    ActionPaused { action: PausableAction },
+
+    #[error("Config update does not exist.")]
+    ConfigUpdateDoesNotExist {},
+
+    #[error("Config update timelock not elapsed.")]
+    ConfigUpdateNotReady {},
+
+    #[error("Legacy job {id} already exists in the current storage.")]
+    JobMigrationConflict { id: u64 },
 }
 
 impl From<serde_json_wasm::de::Error> for ContractError {
@@ -1,12 +1,20 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint64};
 
-use crate::{state::CONFIG, ContractError};
+use crate::{
+    state::{CONFIG, OWNERSHIP, PENDING_CONFIG_UPDATES, PENDING_CONFIG_UPDATE_ID},
+    util::config::ValidateConfig,
+    ContractError,
+};
 
-use controller::{Config, UpdateConfigMsg};
+use controller::{
+    AcceptOwnershipMsg, CancelConfigUpdateMsg, CancelOwnershipProposalMsg, Config,
+    ExecuteConfigUpdateMsg, PendingConfigUpdate, ProposeOwnerMsg, RemoveGuardianMsg,
+    ScheduleConfigUpdateMsg, SetPausedMsg, UpdateConfigMsg,
+};
 
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     data: UpdateConfigMsg,
     mut config: Config,
@@ -15,20 +23,153 @@ pub fn update_config(
         return Err(ContractError::Unauthorized {});
     }
 
-    config.owner = match data.owner {
-        None => config.owner,
-        Some(data) => deps.api.addr_validate(data.as_str())?,
-    };
+    if let Some(owner) = &data.owner {
+        OWNERSHIP.propose(
+            deps.storage,
+            deps.api,
+            &env,
+            &info.sender,
+            &config.owner,
+            ProposeOwnerMsg {
+                owner: owner.clone(),
+                expires_at_time: None,
+            },
+        )?;
+    }
 
     config.fee_collector = match data.fee_collector {
         None => config.fee_collector,
         Some(data) => deps.api.addr_validate(data.as_str())?,
     };
     config.minimum_reward = data.minimum_reward.unwrap_or(config.minimum_reward);
+    config.guardian = match data.guardian {
+        None => config.guardian,
+        Some(data) => Some(deps.api.addr_validate(data.as_str())?),
+    };
+
+    config.validate()?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("config_owner", config.owner)
+        .add_attribute("config_fee_collector", config.fee_collector)
+        .add_attribute("config_minimum_reward", config.minimum_reward)
+        .add_attribute("config_cancellation_fee_rate", config.cancellation_fee_rate)
+        .add_attributes(data.owner.map(|owner| ("pending_owner", owner))))
+}
+
+pub fn schedule_config_update(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: ScheduleConfigUpdateMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Fail early, the update is validated again against the config at execution time
+    apply_config_update(deps.as_ref(), config.clone(), &data)?.validate()?;
+
+    let id = PENDING_CONFIG_UPDATE_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    PENDING_CONFIG_UPDATE_ID.save(deps.storage, &(id + 1))?;
+
+    let update = PendingConfigUpdate {
+        id: Uint64::new(id),
+        update: data,
+        scheduled_at_time: Uint64::new(env.block.time.seconds()),
+        executable_at_time: Uint64::new(env.block.time.seconds())
+            .checked_add(config.config_update_delay_seconds)?,
+    };
+
+    PENDING_CONFIG_UPDATES.save(deps.storage, id, &update)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "schedule_config_update")
+        .add_attribute("config_update_id", update.id)
+        .add_attribute("config_update", serde_json_wasm::to_string(&update.update)?)
+        .add_attribute(
+            "config_update_executable_at_time",
+            update.executable_at_time,
+        ))
+}
+
+pub fn execute_config_update(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: ExecuteConfigUpdateMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let update = PENDING_CONFIG_UPDATES
+        .may_load(deps.storage, data.id.u64())?
+        .ok_or(ContractError::ConfigUpdateDoesNotExist {})?;
+
+    if env.block.time.seconds() < update.executable_at_time.u64() {
+        return Err(ContractError::ConfigUpdateNotReady {});
+    }
+
+    let config = apply_config_update(deps.as_ref(), config, &update.update)?;
+
+    config.validate()?;
+
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_CONFIG_UPDATES.remove(deps.storage, data.id.u64());
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_config_update")
+        .add_attribute("config_update_id", update.id)
+        .add_attribute("config_resolver_address", config.resolver_address)
+        .add_attribute("config_warp_account_code_id", config.warp_account_code_id)
+        .add_attribute("config_cancellation_fee_rate", config.cancellation_fee_rate))
+}
+
+pub fn cancel_config_update(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    data: CancelConfigUpdateMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if info.sender != config.owner && Some(&info.sender) != config.guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !PENDING_CONFIG_UPDATES.has(deps.storage, data.id.u64()) {
+        return Err(ContractError::ConfigUpdateDoesNotExist {});
+    }
+
+    PENDING_CONFIG_UPDATES.remove(deps.storage, data.id.u64());
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_config_update")
+        .add_attribute("config_update_id", data.id))
+}
+
+fn apply_config_update(
+    deps: Deps,
+    mut config: Config,
+    data: &ScheduleConfigUpdateMsg,
+) -> Result<Config, ContractError> {
+    config.resolver_address = match &data.resolver_address {
+        None => config.resolver_address,
+        Some(data) => deps.api.addr_validate(data.as_str())?,
+    };
+    config.warp_account_code_id = data
+        .warp_account_code_id
+        .unwrap_or(config.warp_account_code_id);
     config.cancellation_fee_rate = data
         .cancellation_fee_rate
         .unwrap_or(config.cancellation_fee_rate);
-
     config.creation_fee_min = data.creation_fee_min.unwrap_or(config.creation_fee_min);
     config.creation_fee_max = data.creation_fee_max.unwrap_or(config.creation_fee_max);
     config.burn_fee_min = data.burn_fee_min.unwrap_or(config.burn_fee_min);
@@ -40,46 +181,112 @@ pub fn update_config(
         .unwrap_or(config.maintenance_fee_max);
     config.duration_days_min = data.duration_days_min.unwrap_or(config.duration_days_min);
     config.duration_days_max = data.duration_days_max.unwrap_or(config.duration_days_max);
+    config.duration_days_limit = data
+        .duration_days_limit
+        .unwrap_or(config.duration_days_limit);
     config.queue_size_left = data.queue_size_left.unwrap_or(config.queue_size_left);
     config.queue_size_right = data.queue_size_right.unwrap_or(config.queue_size_right);
     config.burn_fee_rate = data.burn_fee_rate.unwrap_or(config.burn_fee_rate);
+    config.fee_tier_stake_contract = match &data.fee_tier_stake_contract {
+        None => config.fee_tier_stake_contract,
+        Some(data) => Some(deps.api.addr_validate(data.as_str())?),
+    };
+    config.config_update_delay_seconds = data
+        .config_update_delay_seconds
+        .unwrap_or(config.config_update_delay_seconds);
 
-    if config.burn_fee_rate.u128() > 100 {
-        return Err(ContractError::BurnFeeTooHigh {});
-    }
+    Ok(config)
+}
 
-    if config.creation_fee_max < config.creation_fee_min {
-        return Err(ContractError::CreationMaxFeeUnderMinFee {});
-    }
+pub fn set_paused(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    data: SetPausedMsg,
+    mut config: Config,
+) -> Result<Response, ContractError> {
+    let is_owner = info.sender == config.owner;
 
-    if config.maintenance_fee_max < config.maintenance_fee_min {
-        return Err(ContractError::MaintenanceMaxFeeUnderMinFee {});
+    if !is_owner && Some(&info.sender) != config.guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
     }
 
-    if config.duration_days_max <= config.duration_days_min {
-        return Err(ContractError::DurationMaxDaysUnderMinDays {});
+    // A compromised guardian must not be able to lift a pause imposed by the owner
+    if !is_owner
+        && config
+            .paused
+            .iter()
+            .any(|action| !data.paused.contains(action))
+    {
+        return Err(ContractError::Unauthorized {});
     }
 
-    if config.cancellation_fee_rate.u64() > 100 {
-        return Err(ContractError::CancellationFeeTooHigh {});
-    }
+    config.paused = data.paused;
 
-    if config.queue_size_right <= config.queue_size_left {
-        return Err(ContractError::QueueSizeRightUnderQueueSizeLeft {});
-    }
+    CONFIG.save(deps.storage, &config)?;
 
-    if config.duration_days_max > config.duration_days_limit
-        || config.duration_days_min > config.duration_days_limit
-    {
-        return Err(ContractError::DurationDaysLimit {});
+    Ok(Response::new()
+        .add_attribute("action", "set_paused")
+        .add_attribute("sender", info.sender)
+        .add_attribute("paused", serde_json_wasm::to_string(&config.paused)?))
+}
+
+pub fn remove_guardian(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _data: RemoveGuardianMsg,
+    mut config: Config,
+) -> Result<Response, ContractError> {
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
     }
 
+    config.guardian = None;
+
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "update_config")
-        .add_attribute("config_owner", config.owner)
-        .add_attribute("config_fee_collector", config.fee_collector)
-        .add_attribute("config_minimum_reward", config.minimum_reward)
-        .add_attribute("config_cancellation_fee_rate", config.cancellation_fee_rate))
+    Ok(Response::new().add_attribute("action", "remove_guardian"))
+}
+
+pub fn propose_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: ProposeOwnerMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    Ok(OWNERSHIP.propose(
+        deps.storage,
+        deps.api,
+        &env,
+        &info.sender,
+        &config.owner,
+        data,
+    )?)
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    _data: AcceptOwnershipMsg,
+    mut config: Config,
+) -> Result<Response, ContractError> {
+    let (owner, response) = OWNERSHIP.accept(deps.storage, &env, &info.sender, &config.owner)?;
+
+    config.owner = owner;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+pub fn cancel_ownership_proposal(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _data: CancelOwnershipProposalMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    Ok(OWNERSHIP.cancel(deps.storage, &info.sender, &config.owner)?)
 }
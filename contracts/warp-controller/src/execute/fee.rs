@@ -1,5 +1,15 @@
-use controller::Config;
-use cosmwasm_std::{Uint128, Uint64};
+use controller::{
+    fee_tier::{AssignFeeTierMsg, FeeTier, RemoveFeeTierMsg, SetFeeTierMsg},
+    Config,
+};
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128, Uint64,
+};
+
+use crate::{
+    state::{FEE_TIERS, FEE_TIER_ADDRESSES},
+    ContractError,
+};
 
 pub fn compute_creation_fee(queue_size: Uint64, config: &Config) -> Uint128 {
     let x1 = Uint128::from(config.queue_size_left);
@@ -47,3 +57,172 @@ pub fn compute_burn_fee(job_reward: Uint128, config: &Config) -> Uint128 {
         min_fee
     }
 }
+
+pub fn apply_fee_discount(fee: Uint128, discount: Uint64) -> Uint128 {
+    fee - fee * Uint128::from(discount) / Uint128::new(100)
+}
+
+// Explicit address assignments take precedence over stake based tiers,
+// among stake based tiers the one with the highest satisfied threshold wins.
+// A failing stake query is an error, rather than silently charging full fees.
+pub fn resolve_fee_tier(deps: Deps, config: &Config, address: &Addr) -> StdResult<Option<FeeTier>> {
+    if let Some(name) = FEE_TIER_ADDRESSES.may_load(deps.storage, address)? {
+        if let Some(tier) = FEE_TIERS.may_load(deps.storage, &name)? {
+            return Ok(Some(tier));
+        }
+    }
+
+    let stake_contract = match &config.fee_tier_stake_contract {
+        None => return Ok(None),
+        Some(stake_contract) => stake_contract,
+    };
+
+    let mut stake_tiers = vec![];
+
+    for item in FEE_TIERS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, tier) = item?;
+
+        if tier.stake_threshold.is_some() {
+            stake_tiers.push(tier);
+        }
+    }
+
+    // no tier can be reached through stake, skip the query
+    if stake_tiers.is_empty() {
+        return Ok(None);
+    }
+
+    let staked: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+        stake_contract,
+        &cw20::Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        },
+    )?;
+
+    let mut resolved: Option<FeeTier> = None;
+
+    for tier in stake_tiers {
+        if let Some(threshold) = tier.stake_threshold {
+            let is_better = match resolved.as_ref().and_then(|r| r.stake_threshold) {
+                None => true,
+                Some(resolved_threshold) => threshold > resolved_threshold,
+            };
+
+            if staked.balance >= threshold && is_better {
+                resolved = Some(tier);
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+pub fn set_fee_tier(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    data: SetFeeTierMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let tier = data.tier;
+
+    if tier.name.is_empty() {
+        return Err(ContractError::NameTooShort {});
+    }
+
+    if tier.creation_fee_discount.u64() > 100
+        || tier.maintenance_fee_discount.u64() > 100
+        || tier.burn_fee_discount.u64() > 100
+    {
+        return Err(ContractError::FeeTierDiscountTooHigh {});
+    }
+
+    FEE_TIERS.save(deps.storage, &tier.name, &tier)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_fee_tier")
+        .add_attribute("fee_tier_name", tier.name)
+        .add_attribute("fee_tier_creation_fee_discount", tier.creation_fee_discount)
+        .add_attribute(
+            "fee_tier_maintenance_fee_discount",
+            tier.maintenance_fee_discount,
+        )
+        .add_attribute("fee_tier_burn_fee_discount", tier.burn_fee_discount))
+}
+
+pub fn remove_fee_tier(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    data: RemoveFeeTierMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !FEE_TIERS.has(deps.storage, &data.name) {
+        return Err(ContractError::FeeTierDoesNotExist {});
+    }
+
+    FEE_TIERS.remove(deps.storage, &data.name);
+
+    // Addresses assigned to the removed tier fall back to stake based tiers
+    let mut assigned = vec![];
+
+    for item in FEE_TIER_ADDRESSES.range(deps.storage, None, None, Order::Ascending) {
+        let (addr, name) = item?;
+
+        if name == data.name {
+            assigned.push(addr);
+        }
+    }
+
+    for addr in &assigned {
+        FEE_TIER_ADDRESSES.remove(deps.storage, addr);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_fee_tier")
+        .add_attribute("fee_tier_name", data.name)
+        .add_attribute("unassigned_addresses", assigned.len().to_string()))
+}
+
+pub fn assign_fee_tier(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    data: AssignFeeTierMsg,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(name) = &data.tier {
+        if !FEE_TIERS.has(deps.storage, name) {
+            return Err(ContractError::FeeTierDoesNotExist {});
+        }
+    }
+
+    for address in &data.addresses {
+        let addr = deps.api.addr_validate(address)?;
+
+        match &data.tier {
+            Some(name) => FEE_TIER_ADDRESSES.save(deps.storage, &addr, name)?,
+            None => FEE_TIER_ADDRESSES.remove(deps.storage, &addr),
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "assign_fee_tier")
+        .add_attribute(
+            "fee_tier_name",
+            data.tier.unwrap_or_else(|| "none".to_string()),
+        )
+        .add_attribute("addresses", data.addresses.join(",")))
+}
@@ -2,7 +2,7 @@ use crate::contract::REPLY_ID_CREATE_JOB_ACCOUNT_AND_JOB;
 use crate::state::{JobQueue, STATE};
 use crate::util::msg::{
     build_account_execute_generic_msgs, build_account_execute_warp_msgs,
-    build_free_funding_account_msg, build_take_funding_account_msg,
+    build_free_funding_account_msg, build_job_env, build_take_funding_account_msg,
 };
 use crate::ContractError;
 use controller::account::WarpMsgs;
@@ -27,7 +27,10 @@ use account_tracker::{FundingAccount, FundingAccountResponse, JobAccountResponse
 use controller::{account::CwFund, Config};
 use resolver::QueryHydrateMsgsMsg;
 
-use super::fee::{compute_burn_fee, compute_creation_fee, compute_maintenance_fee};
+use super::fee::{
+    apply_fee_discount, compute_burn_fee, compute_creation_fee, compute_maintenance_fee,
+    resolve_fee_tier,
+};
 
 const MAX_TEXT_LENGTH: usize = 280;
 
@@ -68,9 +71,15 @@ pub fn create_job(
         }),
     )?;
 
-    let creation_fee = compute_creation_fee(state.q, &config);
-    let maintenance_fee = compute_maintenance_fee(data.duration_days, &config);
-    let burn_fee = compute_burn_fee(data.reward, &config);
+    let mut creation_fee = compute_creation_fee(state.q, &config);
+    let mut maintenance_fee = compute_maintenance_fee(data.duration_days, &config);
+    let mut burn_fee = compute_burn_fee(data.reward, &config);
+
+    if let Some(fee_tier) = resolve_fee_tier(deps.as_ref(), &config, &job_owner)? {
+        creation_fee = apply_fee_discount(creation_fee, fee_tier.creation_fee_discount);
+        maintenance_fee = apply_fee_discount(maintenance_fee, fee_tier.maintenance_fee_discount);
+        burn_fee = apply_fee_discount(burn_fee, fee_tier.burn_fee_discount);
+    }
 
     let total_fees = creation_fee + maintenance_fee + burn_fee;
 
@@ -138,6 +147,8 @@ pub fn create_job(
             created_at_time: Uint64::from(env.block.time.seconds()),
             // placeholder, will be updated later on
             funding_account: None,
+            execution_count: Uint64::zero(),
+            last_execution_time: None,
         },
     )?;
 
@@ -465,12 +476,15 @@ pub fn execute_job(
         return Err(ContractError::JobNotActive {});
     }
 
+    let job_env = build_job_env(&job, &env.contract.address);
+
     let vars: String = deps.querier.query_wasm_smart(
         config.resolver_address.clone(),
         &resolver::QueryMsg::QueryHydrateVars(resolver::QueryHydrateVarsMsg {
             vars: job.vars,
             external_inputs: data.external_inputs,
             warp_account_addr: Some(job.account.to_string()),
+            job_env: Some(job_env.clone()),
         }),
     )?;
 
@@ -487,6 +501,7 @@ pub fn execute_job(
                 condition,
                 vars: vars.clone(),
                 warp_account_addr: Some(job.account.to_string()),
+                job_env: Some(job_env.clone()),
             }),
         );
 
@@ -1,7 +1,8 @@
 use cosmwasm_std::{to_binary, Deps, Env, MessageInfo, Response, WasmMsg};
 
 use crate::ContractError;
-use account_tracker::{AccountsResponse, MigrateMsg, QueryAccountsMsg};
+use account::MigrateMsg;
+use account_tracker::{AccountsResponse, QueryAccountsMsg};
 use controller::{Config, MigrateAccountsMsg};
 
 pub fn migrate_accounts(
@@ -29,7 +30,9 @@ pub fn migrate_accounts(
         migration_msgs.push(WasmMsg::Migrate {
             contract_addr: account.account_addr.to_string(),
             new_code_id: msg.warp_account_code_id.u64(),
-            msg: to_binary(&MigrateMsg {})?,
+            msg: to_binary(&MigrateMsg {
+                version: msg.warp_account_version.clone(),
+            })?,
         });
     }
 
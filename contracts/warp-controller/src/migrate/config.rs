@@ -0,0 +1,31 @@
+use cosmwasm_std::{from_slice, StdResult, Storage, Uint64};
+
+use crate::contract::DEFAULT_CONFIG_UPDATE_DELAY_SECONDS;
+use crate::state::CONFIG;
+
+// Only the config fields the migration needs to tell legacy configs apart, unknown fields
+// are ignored
+#[derive(cosmwasm_schema::serde::Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct StoredConfigDelay {
+    config_update_delay_seconds: Option<Uint64>,
+}
+
+// Configs stored before the timelock existed lack the delay and get the default one.
+// A stored delay, including an explicit 0 (no timelock), is left as is.
+pub(crate) fn backfill_config_update_delay(storage: &mut dyn Storage) -> StdResult<bool> {
+    let stored = match storage.get(CONFIG.as_slice()) {
+        None => return Ok(false),
+        Some(stored) => from_slice::<StoredConfigDelay>(&stored)?,
+    };
+
+    if stored.config_update_delay_seconds.is_some() {
+        return Ok(false);
+    }
+
+    let mut config = CONFIG.load(storage)?;
+    config.config_update_delay_seconds = Uint64::new(DEFAULT_CONFIG_UPDATE_DELAY_SECONDS);
+    CONFIG.save(storage, &config)?;
+
+    Ok(true)
+}
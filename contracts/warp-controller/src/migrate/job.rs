@@ -1,182 +1,174 @@
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
-
-use crate::state::{FINISHED_JOBS, PENDING_JOBS};
-use crate::{state::CONFIG, ContractError};
-
-use controller::account::AssetInfo;
-use controller::job::{Execution, Job, JobStatus};
-use controller::MigrateJobsMsg;
-use cosmwasm_std::{Addr, Order, Uint128, Uint64};
-use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, MultiIndex, UniqueIndex};
-
-#[cw_serde]
-pub struct OldJob {
-    pub id: Uint64,
-    pub prev_id: Option<Uint64>,
-    pub owner: Addr,
-    pub account: Addr,
-    pub last_update_time: Uint64,
-    pub name: String,
-    pub description: String,
-    pub labels: Vec<String>,
-    pub status: JobStatus,
-    pub terminate_condition: Option<String>,
-    pub duration_days: Uint64,
-    pub executions: Vec<Execution>,
-    pub vars: String,
-    pub recurring: bool,
-    pub requeue_on_evict: bool,
-    pub reward: Uint128,
-    pub assets_to_withdraw: Vec<AssetInfo>,
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    from_slice, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult, Storage,
+};
+use cw_storage_plus::{IndexedMap, KeyDeserialize, Map};
+use json_codec_wasm::{Decoder, Encoder, Json};
+
+use crate::state::{JobIndexes, FINISHED_JOBS, PENDING_JOBS};
+use crate::ContractError;
+
+use controller::job::Job;
+use controller::{Config, MigrateJobsMsg};
+
+// A schema change of stored jobs. Jobs of the previous storage layout are decoded as
+// raw JSON objects and patched by `upgrade` until they deserialize as the current Job,
+// so old struct definitions never need to be kept around.
+pub struct JobMigration {
+    pub pending_jobs_namespace: &'static str,
+    pub finished_jobs_namespace: &'static str,
+    // Namespaces of the legacy job indexes, cleared once all legacy jobs are moved
+    pub index_namespaces: &'static [&'static str],
+    pub upgrade: fn(&mut HashMap<String, Json>),
 }
 
-pub struct OldJobIndexes<'a> {
-    pub reward: UniqueIndex<'a, (u128, u64), OldJob>,
-    pub publish_time: MultiIndex<'a, u64, OldJob, u64>,
-}
-
-impl IndexList<OldJob> for OldJobIndexes<'_> {
-    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<OldJob>> + '_> {
-        let v: Vec<&dyn Index<OldJob>> = vec![&self.reward, &self.publish_time];
-        Box::new(v.into_iter())
-    }
-}
+// Registered migrations, run in order by ExecuteMsg::MigrateJobs.
+// The v5 -> v6 layout change already ran on every deployment and its legacy entries were
+// never cleared, so it must not be registered again or stale jobs would be moved back.
+pub const JOB_MIGRATIONS: &[JobMigration] = &[];
 
-pub fn migrate_pending_jobs(
+pub fn migrate_jobs(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     msg: MigrateJobsMsg,
+    config: Config,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
         return Err(ContractError::Unauthorized {});
     }
 
-    let start_after = msg.start_after;
-    let start_after = start_after.map(Bound::exclusive);
-
-    #[allow(non_snake_case)]
-    pub fn OLD_PENDING_JOBS<'a>() -> IndexedMap<'a, u64, OldJob, OldJobIndexes<'a>> {
-        let indexes = OldJobIndexes {
-            reward: UniqueIndex::new(
-                |job| (job.reward.u128(), job.id.u64()),
-                "pending_jobs__reward_v5",
-            ),
-            publish_time: MultiIndex::new(
-                |_pk, job| job.last_update_time.u64(),
-                "pending_jobs_v5",
-                "pending_jobs__publish_timestamp_v5",
-            ),
-        };
-        IndexedMap::new("pending_jobs_v5", indexes)
-    }
+    let (migrated, done) = run_job_migrations(deps.storage, JOB_MIGRATIONS, msg.limit as usize)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_jobs")
+        .add_attribute("migrated_jobs", migrated.to_string())
+        .add_attribute("done", done.to_string()))
+}
 
-    let job_keys: Result<Vec<_>, _> = OLD_PENDING_JOBS()
-        .keys(deps.storage, start_after, None, Order::Ascending)
-        .take(msg.limit as usize)
-        .collect();
-    let job_keys = job_keys?;
-
-    for job_key in job_keys {
-        let old_job = OLD_PENDING_JOBS().load(deps.storage, job_key)?;
-
-        PENDING_JOBS().save(
-            deps.storage,
-            job_key,
-            &Job {
-                id: old_job.id,
-                prev_id: old_job.prev_id,
-                owner: old_job.owner,
-                account: old_job.account,
-                last_update_time: old_job.last_update_time,
-                name: old_job.name,
-                description: old_job.description,
-                labels: old_job.labels,
-                status: old_job.status,
-                terminate_condition: old_job.terminate_condition,
-                executions: old_job.executions,
-                vars: old_job.vars,
-                recurring: old_job.recurring,
-                reward: old_job.reward,
-                assets_to_withdraw: old_job.assets_to_withdraw,
-                duration_days: old_job.duration_days,
-                created_at_time: old_job.last_update_time,
-                // TODO: update to old_job.funding_account
-                funding_account: None,
-            },
+// Returns how many jobs were processed and whether all legacy namespaces are now empty.
+// Cleared legacy index entries count towards `limit` as well.
+pub(crate) fn run_job_migrations(
+    storage: &mut dyn Storage,
+    migrations: &[JobMigration],
+    limit: usize,
+) -> Result<(usize, bool), ContractError> {
+    let mut migrated = 0;
+    let mut cleared = 0;
+
+    for migration in migrations {
+        migrated += move_jobs(
+            storage,
+            migration.pending_jobs_namespace,
+            PENDING_JOBS(),
+            migration.upgrade,
+            limit - migrated - cleared,
+        )?;
+        migrated += move_jobs(
+            storage,
+            migration.finished_jobs_namespace,
+            FINISHED_JOBS(),
+            migration.upgrade,
+            limit - migrated - cleared,
         )?;
+
+        if is_empty(storage, migration.pending_jobs_namespace)
+            && is_empty(storage, migration.finished_jobs_namespace)
+        {
+            for namespace in migration.index_namespaces {
+                cleared += clear_namespace(storage, namespace, limit - migrated - cleared)?;
+            }
+        }
     }
 
-    Ok(Response::new())
-}
+    let done = migrations.iter().all(|migration| {
+        is_empty(storage, migration.pending_jobs_namespace)
+            && is_empty(storage, migration.finished_jobs_namespace)
+            && migration
+                .index_namespaces
+                .iter()
+                .all(|namespace| is_empty(storage, namespace))
+    });
 
-pub fn migrate_finished_jobs(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    msg: MigrateJobsMsg,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
-        return Err(ContractError::Unauthorized {});
-    }
+    Ok((migrated, done))
+}
 
-    let start_after = msg.start_after;
-    let start_after = start_after.map(Bound::exclusive);
-
-    #[allow(non_snake_case)]
-    pub fn OLD_FINISHED_JOBS<'a>() -> IndexedMap<'a, u64, OldJob, OldJobIndexes<'a>> {
-        let indexes = OldJobIndexes {
-            reward: UniqueIndex::new(
-                |job| (job.reward.u128(), job.id.u64()),
-                "finished_jobs__reward_v5",
-            ),
-            publish_time: MultiIndex::new(
-                |_pk, job| job.last_update_time.u64(),
-                "finished_jobs_v5",
-                "finished_jobs__publish_timestamp_v5",
-            ),
+// Processes up to `limit` jobs of a legacy namespace and returns how many were processed.
+// Moved jobs are removed from the legacy namespace, so every call resumes where the
+// previous one stopped. A legacy job whose id already exists in the target is an error,
+// rather than overwriting the current job or silently dropping the legacy one.
+fn move_jobs(
+    storage: &mut dyn Storage,
+    namespace: &'static str,
+    target: IndexedMap<u64, Job, JobIndexes>,
+    upgrade: fn(&mut HashMap<String, Json>),
+    limit: usize,
+) -> Result<usize, ContractError> {
+    let legacy: Map<u64, Job> = Map::new(namespace);
+
+    // raw keys, as typed access would deserialize the legacy values as Job
+    let ids = legacy
+        .keys_raw(storage, None, None, Order::Ascending)
+        .take(limit)
+        .map(|key| u64::from_slice(&key))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for id in &ids {
+        let key = legacy.key(*id);
+
+        if target.has(storage, *id) {
+            return Err(ContractError::JobMigrationConflict { id: *id });
+        }
+        let raw = storage
+            .get(&key)
+            .ok_or(ContractError::DeserializationError {})?;
+        let raw = String::from_utf8(raw).map_err(|_| ContractError::DeserializationError {})?;
+
+        let mut job = match Decoder::default(raw.chars()).decode()? {
+            Json::Object(job) => job,
+            _ => return Err(ContractError::DeserializationError {}),
         };
-        IndexedMap::new("finished_jobs_v5", indexes)
+        upgrade(&mut job);
+
+        let mut encoder = Encoder::new(vec![]);
+        encoder
+            .encode(&Json::Object(job))
+            .map_err(|_| ContractError::SerializationError {})?;
+        let job: Job = from_slice(&encoder.into_writer())?;
+
+        storage.remove(&key);
+        target.save(storage, *id, &job)?;
     }
 
-    let job_keys: Result<Vec<_>, _> = OLD_FINISHED_JOBS()
-        .keys(deps.storage, start_after, None, Order::Ascending)
-        .take(msg.limit as usize)
-        .collect();
-    let job_keys = job_keys?;
-
-    for job_key in job_keys {
-        let old_job = OLD_FINISHED_JOBS().load(deps.storage, job_key)?;
-
-        FINISHED_JOBS().save(
-            deps.storage,
-            job_key,
-            &Job {
-                id: old_job.id,
-                prev_id: old_job.prev_id,
-                owner: old_job.owner,
-                account: old_job.account,
-                last_update_time: old_job.last_update_time,
-                name: old_job.name,
-                description: old_job.description,
-                labels: old_job.labels,
-                status: old_job.status,
-                executions: old_job.executions,
-                terminate_condition: old_job.terminate_condition,
-                vars: old_job.vars,
-                recurring: old_job.recurring,
-                reward: old_job.reward,
-                assets_to_withdraw: old_job.assets_to_withdraw,
-                duration_days: old_job.duration_days,
-                created_at_time: old_job.last_update_time,
-                // TODO: update to old_job.funding_account
-                funding_account: None,
-            },
-        )?;
+    Ok(ids.len())
+}
+
+// Removes up to `limit` entries of a legacy namespace and returns how many were removed
+fn clear_namespace(
+    storage: &mut dyn Storage,
+    namespace: &'static str,
+    limit: usize,
+) -> StdResult<usize> {
+    let legacy: Map<&[u8], Empty> = Map::new(namespace);
+
+    let keys = legacy
+        .keys_raw(storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<Vec<_>>();
+
+    for key in &keys {
+        storage.remove(&legacy.key(key));
     }
 
-    Ok(Response::new())
+    Ok(keys.len())
+}
+
+fn is_empty(storage: &dyn Storage, namespace: &'static str) -> bool {
+    let legacy: Map<&[u8], Empty> = Map::new(namespace);
+
+    legacy
+        .keys_raw(storage, None, None, Order::Ascending)
+        .next()
+        .is_none()
 }
@@ -1,2 +1,3 @@
 pub(crate) mod account;
+pub(crate) mod config;
 pub(crate) mod job;
@@ -1,6 +1,11 @@
-use crate::state::{CONFIG, STATE};
-use controller::{ConfigResponse, QueryConfigMsg, QueryStateMsg, StateResponse};
-use cosmwasm_std::{Deps, Env, StdResult};
+use crate::state::{CONFIG, OWNERSHIP, PENDING_CONFIG_UPDATES, QUERY_PAGE_SIZE, STATE};
+use controller::{
+    ConfigResponse, PendingConfigUpdate, PendingConfigUpdatesResponse, PendingOwnerResponse,
+    QueryConfigMsg, QueryPendingConfigUpdatesMsg, QueryPendingOwnerMsg, QueryStateMsg,
+    StateResponse,
+};
+use cosmwasm_std::{Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
 
 pub fn query_config(deps: Deps, _env: Env, _data: QueryConfigMsg) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
@@ -11,3 +16,27 @@ pub fn query_state(deps: Deps, _env: Env, _data: QueryStateMsg) -> StdResult<Sta
     let state = STATE.load(deps.storage)?;
     Ok(StateResponse { state })
 }
+
+pub fn query_pending_owner(
+    deps: Deps,
+    _env: Env,
+    _data: QueryPendingOwnerMsg,
+) -> StdResult<PendingOwnerResponse> {
+    OWNERSHIP.query(deps.storage)
+}
+
+pub fn query_pending_config_updates(
+    deps: Deps,
+    _env: Env,
+    data: QueryPendingConfigUpdatesMsg,
+) -> StdResult<PendingConfigUpdatesResponse> {
+    let start_after = data.start_after.map(|id| Bound::exclusive(id.u64()));
+
+    let updates = PENDING_CONFIG_UPDATES
+        .range(deps.storage, start_after, None, Order::Ascending)
+        .take(data.limit.unwrap_or(QUERY_PAGE_SIZE).min(QUERY_PAGE_SIZE) as usize)
+        .map(|item| item.map(|(_, update)| update))
+        .collect::<StdResult<Vec<PendingConfigUpdate>>>()?;
+
+    Ok(PendingConfigUpdatesResponse { updates })
+}
@@ -0,0 +1,42 @@
+use crate::execute::fee::resolve_fee_tier;
+use crate::state::{CONFIG, FEE_TIERS, QUERY_PAGE_SIZE};
+use controller::fee_tier::{
+    AddressFeeTierResponse, FeeTier, FeeTierResponse, FeeTiersResponse, QueryAddressFeeTierMsg,
+    QueryFeeTierMsg, QueryFeeTiersMsg,
+};
+use cosmwasm_std::{Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
+
+pub fn query_fee_tier(deps: Deps, _env: Env, data: QueryFeeTierMsg) -> StdResult<FeeTierResponse> {
+    let tier = FEE_TIERS.load(deps.storage, &data.name)?;
+    Ok(FeeTierResponse { tier })
+}
+
+pub fn query_fee_tiers(
+    deps: Deps,
+    _env: Env,
+    data: QueryFeeTiersMsg,
+) -> StdResult<FeeTiersResponse> {
+    let start_after = data.start_after.as_deref().map(Bound::exclusive);
+
+    let tiers = FEE_TIERS
+        .range(deps.storage, start_after, None, Order::Ascending)
+        .take(data.limit.unwrap_or(QUERY_PAGE_SIZE).min(QUERY_PAGE_SIZE) as usize)
+        .map(|item| item.map(|(_, tier)| tier))
+        .collect::<StdResult<Vec<FeeTier>>>()?;
+
+    Ok(FeeTiersResponse { tiers })
+}
+
+pub fn query_address_fee_tier(
+    deps: Deps,
+    _env: Env,
+    data: QueryAddressFeeTierMsg,
+) -> StdResult<AddressFeeTierResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&data.address)?;
+
+    Ok(AddressFeeTierResponse {
+        tier: resolve_fee_tier(deps, &config, &address)?,
+    })
+}
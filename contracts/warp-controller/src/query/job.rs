@@ -49,7 +49,7 @@ pub fn query_jobs(deps: Deps, env: Env, data: QueryJobsMsg) -> StdResult<JobsRes
             name,
             owner,
             job_status,
-            start_after.map(|i| (i._1.u64())),
+            start_after.map(|i| i._1.u64()),
             page_size as usize,
         ),
         QueryJobsMsg {
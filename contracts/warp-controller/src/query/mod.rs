@@ -1,2 +1,3 @@
 pub(crate) mod controller;
+pub(crate) mod fee;
 pub(crate) mod job;
@@ -33,8 +33,8 @@ pub fn create_account_and_job(
     let job_id_str = account_event
         .attributes
         .iter()
+        .find(|&attr| attr.key == "job_id")
         .cloned()
-        .find(|attr| attr.key == "job_id")
         .ok_or_else(|| StdError::generic_err("cannot find `job_id` attribute"))?
         .value;
     let job_id = job_id_str.as_str().parse::<u64>()?;
@@ -42,8 +42,8 @@ pub fn create_account_and_job(
     let owner = account_event
         .attributes
         .iter()
+        .find(|&attr| attr.key == "owner")
         .cloned()
-        .find(|attr| attr.key == "owner")
         .ok_or_else(|| StdError::generic_err("cannot find `owner` attribute"))?
         .value;
 
@@ -51,8 +51,8 @@ pub fn create_account_and_job(
         &account_event
             .attributes
             .iter()
+            .find(|&attr| attr.key == "contract_addr")
             .cloned()
-            .find(|attr| attr.key == "contract_addr")
             .ok_or_else(|| StdError::generic_err("cannot find `contract_addr` attribute"))?
             .value,
     )?;
@@ -61,8 +61,8 @@ pub fn create_account_and_job(
         &account_event
             .attributes
             .iter()
+            .find(|&attr| attr.key == "native_funds")
             .cloned()
-            .find(|attr| attr.key == "native_funds")
             .ok_or_else(|| StdError::generic_err("cannot find `native_funds` attribute"))?
             .value,
     )?;
@@ -71,8 +71,8 @@ pub fn create_account_and_job(
         &account_event
             .attributes
             .iter()
+            .find(|&attr| attr.key == "cw_funds")
             .cloned()
-            .find(|attr| attr.key == "cw_funds")
             .ok_or_else(|| StdError::generic_err("cannot find `cw_funds` attribute"))?
             .value,
     )?;
@@ -149,8 +149,8 @@ pub fn create_funding_account(
     let owner = funding_account_event
         .attributes
         .iter()
+        .find(|&attr| attr.key == "owner")
         .cloned()
-        .find(|attr| attr.key == "owner")
         .ok_or_else(|| StdError::generic_err("cannot find `owner` attribute"))?
         .value;
 
@@ -158,8 +158,8 @@ pub fn create_funding_account(
         &funding_account_event
             .attributes
             .iter()
+            .find(|&attr| attr.key == "contract_addr")
             .cloned()
-            .find(|attr| attr.key == "contract_addr")
             .ok_or_else(|| StdError::generic_err("cannot find `contract_addr` attribute"))?
             .value,
     )?;
@@ -168,8 +168,8 @@ pub fn create_funding_account(
         &funding_account_event
             .attributes
             .iter()
+            .find(|&attr| attr.key == "native_funds")
             .cloned()
-            .find(|attr| attr.key == "native_funds")
             .ok_or_else(|| StdError::generic_err("cannot find `native_funds` attribute"))?
             .value,
     )?;
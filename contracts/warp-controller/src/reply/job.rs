@@ -5,10 +5,13 @@ use cosmwasm_std::{
 
 use crate::{
     error::map_contract_error,
-    execute::fee::{compute_burn_fee, compute_creation_fee, compute_maintenance_fee},
+    execute::fee::{
+        apply_fee_discount, compute_burn_fee, compute_creation_fee, compute_maintenance_fee,
+        resolve_fee_tier,
+    },
     state::{JobQueue, CONFIG, STATE},
     util::msg::{
-        build_account_execute_generic_msgs, build_account_withdraw_assets_msg,
+        build_account_execute_generic_msgs, build_account_withdraw_assets_msg, build_job_env,
         build_take_funding_account_msg, build_take_job_account_msg,
         build_transfer_native_funds_msg,
     },
@@ -48,9 +51,15 @@ pub fn execute_job(
     let mut new_job_attrs = vec![];
     let new_job_id = state.current_job_id;
 
-    let creation_fee = compute_creation_fee(state.q, &config);
-    let maintenance_fee = compute_maintenance_fee(finished_job.duration_days, &config);
-    let burn_fee = compute_burn_fee(finished_job.reward, &config);
+    let mut creation_fee = compute_creation_fee(state.q, &config);
+    let mut maintenance_fee = compute_maintenance_fee(finished_job.duration_days, &config);
+    let mut burn_fee = compute_burn_fee(finished_job.reward, &config);
+
+    if let Some(fee_tier) = resolve_fee_tier(deps.as_ref(), &config, &finished_job.owner)? {
+        creation_fee = apply_fee_discount(creation_fee, fee_tier.creation_fee_discount);
+        maintenance_fee = apply_fee_discount(maintenance_fee, fee_tier.maintenance_fee_discount);
+        burn_fee = apply_fee_discount(burn_fee, fee_tier.burn_fee_discount);
+    }
 
     let total_fees = creation_fee + maintenance_fee + burn_fee;
 
@@ -84,11 +93,14 @@ pub fn execute_job(
                 "failed_invalid_job_status",
             ));
         } else {
+            let job_env = build_job_env(&finished_job, &env.contract.address);
+
             let hydrated_vars: String = deps.querier.query_wasm_smart(
                 config.resolver_address.clone(),
                 &resolver::QueryMsg::QueryHydrateVars(resolver::QueryHydrateVarsMsg {
                     vars: finished_job.vars,
                     warp_account_addr: Some(finished_job.account.to_string()),
+                    job_env: Some(job_env.clone()),
                     external_inputs: None,
                 }),
             )?;
@@ -100,6 +112,7 @@ pub fn execute_job(
                     vars: hydrated_vars,
                     status: finished_job.status.clone(),
                     warp_account_addr: Some(finished_job.account.to_string()),
+                    job_env: Some(job_env.clone()),
                 }),
             )?;
 
@@ -115,6 +128,7 @@ pub fn execute_job(
                                 condition: terminate_condition,
                                 vars: new_vars.clone(),
                                 warp_account_addr: Some(finished_job.account.to_string()),
+                                job_env: Some(job_env),
                             },
                         ),
                     );
@@ -174,6 +188,8 @@ pub fn execute_job(
                         duration_days: finished_job.duration_days,
                         created_at_time: Uint64::from(env.block.time.seconds()),
                         funding_account: finished_job.funding_account.clone(),
+                        execution_count: finished_job.execution_count,
+                        last_execution_time: finished_job.last_execution_time,
                     },
                 )?;
 
@@ -281,8 +297,8 @@ pub fn instantiate_sub_contracts(
         &account_tracker_instantiate_event
             .attributes
             .iter()
+            .find(|&attr| attr.key == "account_tracker")
             .cloned()
-            .find(|attr| attr.key == "account_tracker")
             .ok_or_else(|| StdError::generic_err("cannot find `account_tracker` attribute"))?
             .value,
     )?;
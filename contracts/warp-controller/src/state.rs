@@ -1,9 +1,11 @@
-use cosmwasm_std::{Env, Storage, Uint64};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, UniqueIndex};
+use common::ownership::Ownership;
+use cosmwasm_std::{Addr, Env, Storage, Uint64};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 
 use controller::{
+    fee_tier::FeeTier,
     job::{Job, JobStatus, UpdateJobMsg},
-    Config, State,
+    Config, PendingConfigUpdate, State,
 };
 
 use crate::ContractError;
@@ -66,6 +68,18 @@ pub fn FINISHED_JOBS<'a>() -> IndexedMap<'a, u64, Job, JobIndexes<'a>> {
 pub const QUERY_PAGE_SIZE: u32 = 50;
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const STATE: Item<State> = Item::new("state");
+pub const OWNERSHIP: Ownership = Ownership::new("pending_owner");
+
+// Key is the config update id, value is the scheduled update waiting for its timelock
+pub const PENDING_CONFIG_UPDATES: Map<u64, PendingConfigUpdate> =
+    Map::new("pending_config_updates");
+pub const PENDING_CONFIG_UPDATE_ID: Item<u64> = Item::new("pending_config_update_id");
+
+// Key is the tier name, value is the fee tier
+pub const FEE_TIERS: Map<&str, FeeTier> = Map::new("fee_tiers");
+
+// Key is the address, value is the name of the fee tier explicitly assigned to it
+pub const FEE_TIER_ADDRESSES: Map<&Addr, String> = Map::new("fee_tier_addresses");
 
 pub struct JobQueue;
 
@@ -117,6 +131,8 @@ impl JobQueue {
                 duration_days: job.duration_days,
                 created_at_time: Uint64::from(env.block.time.seconds()),
                 funding_account: job.funding_account,
+                execution_count: job.execution_count,
+                last_execution_time: job.last_execution_time,
             }),
         })?;
 
@@ -149,6 +165,8 @@ impl JobQueue {
                 duration_days: job.duration_days,
                 created_at_time: job.created_at_time,
                 funding_account: job.funding_account,
+                execution_count: job.execution_count,
+                last_execution_time: job.last_execution_time,
             }),
         })?;
 
@@ -167,6 +185,8 @@ impl JobQueue {
 
         let job = PENDING_JOBS().load(storage, job_id)?;
 
+        let executed = status == JobStatus::Executed || status == JobStatus::Failed;
+
         let new_job = Job {
             id: job.id,
             prev_id: job.prev_id,
@@ -186,6 +206,16 @@ impl JobQueue {
             duration_days: job.duration_days,
             created_at_time: job.created_at_time,
             funding_account: job.funding_account,
+            execution_count: if executed {
+                job.execution_count.checked_add(Uint64::one())?
+            } else {
+                job.execution_count
+            },
+            last_execution_time: if executed {
+                Some(Uint64::new(env.block.time.seconds()))
+            } else {
+                job.last_execution_time
+            },
         };
 
         FINISHED_JOBS().update(storage, job_id, |j| match j {
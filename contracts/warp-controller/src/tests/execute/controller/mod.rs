@@ -1 +1,5 @@
-mod test_update_config;
+mod test_config_update;
+mod test_fee_tier;
+mod test_ownership;
+mod test_set_paused;
+mod test_validate_config;
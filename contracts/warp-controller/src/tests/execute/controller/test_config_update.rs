@@ -0,0 +1,239 @@
+use crate::contract::{execute, query};
+use crate::tests::helpers::{
+    default_instantiate_msg, instantiate_warp, CONFIG_UPDATE_DELAY_SECONDS, GUARDIAN, OWNER,
+};
+use crate::ContractError;
+use controller::{
+    CancelConfigUpdateMsg, ConfigResponse, ExecuteConfigUpdateMsg, ExecuteMsg,
+    PendingConfigUpdatesResponse, QueryConfigMsg, QueryMsg, QueryPendingConfigUpdatesMsg,
+    ScheduleConfigUpdateMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_binary, Addr, Deps, Env, Uint128, Uint64};
+
+fn empty_update() -> ScheduleConfigUpdateMsg {
+    ScheduleConfigUpdateMsg {
+        resolver_address: None,
+        warp_account_code_id: None,
+        cancellation_fee_rate: None,
+        creation_fee_min: None,
+        creation_fee_max: None,
+        burn_fee_min: None,
+        maintenance_fee_min: None,
+        maintenance_fee_max: None,
+        duration_days_min: None,
+        duration_days_max: None,
+        duration_days_limit: None,
+        queue_size_left: None,
+        queue_size_right: None,
+        burn_fee_rate: None,
+        fee_tier_stake_contract: None,
+        config_update_delay_seconds: None,
+    }
+}
+
+fn env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn query_pending_config_updates(deps: Deps) -> PendingConfigUpdatesResponse {
+    from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::QueryPendingConfigUpdates(QueryPendingConfigUpdatesMsg {
+                start_after: None,
+                limit: None,
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_config_update_timelock() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let update = ScheduleConfigUpdateMsg {
+        resolver_address: Some("new_resolver".to_string()),
+        cancellation_fee_rate: Some(Uint64::new(10)),
+        fee_tier_stake_contract: Some("stake_contract".to_string()),
+        ..empty_update()
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        ExecuteMsg::ScheduleConfigUpdate(update.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ScheduleConfigUpdate(update),
+    )
+    .unwrap();
+
+    let pending = query_pending_config_updates(deps.as_ref()).updates;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(
+        pending[0].executable_at_time,
+        Uint64::new(mock_env().block.time.seconds() + CONFIG_UPDATE_DELAY_SECONDS)
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_after(CONFIG_UPDATE_DELAY_SECONDS - 1),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ExecuteConfigUpdate(ExecuteConfigUpdateMsg { id: pending[0].id }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ConfigUpdateNotReady {});
+
+    execute(
+        deps.as_mut(),
+        env_after(CONFIG_UPDATE_DELAY_SECONDS),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ExecuteConfigUpdate(ExecuteConfigUpdateMsg { id: pending[0].id }),
+    )
+    .unwrap();
+
+    let res: ConfigResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryConfig(QueryConfigMsg {}),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.config.resolver_address, Addr::unchecked("new_resolver"));
+    assert_eq!(res.config.cancellation_fee_rate, Uint64::new(10));
+    assert_eq!(
+        res.config.fee_tier_stake_contract,
+        Some(Addr::unchecked("stake_contract"))
+    );
+    assert!(query_pending_config_updates(deps.as_ref())
+        .updates
+        .is_empty());
+}
+
+#[test]
+fn test_schedule_invalid_config_update() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ScheduleConfigUpdate(ScheduleConfigUpdateMsg {
+            creation_fee_max: Some(Uint128::new(1)),
+            ..empty_update()
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CreationMaxFeeUnderMinFee {});
+}
+
+#[test]
+fn test_execute_config_update_revalidates() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    // each update is valid against the config it was scheduled on
+    for update in [
+        ScheduleConfigUpdateMsg {
+            creation_fee_min: Some(Uint128::new(50_000_000)),
+            ..empty_update()
+        },
+        ScheduleConfigUpdateMsg {
+            creation_fee_max: Some(Uint128::new(1_000_000)),
+            ..empty_update()
+        },
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            ExecuteMsg::ScheduleConfigUpdate(update),
+        )
+        .unwrap();
+    }
+
+    let pending = query_pending_config_updates(deps.as_ref()).updates;
+
+    execute(
+        deps.as_mut(),
+        env_after(CONFIG_UPDATE_DELAY_SECONDS),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ExecuteConfigUpdate(ExecuteConfigUpdateMsg { id: pending[1].id }),
+    )
+    .unwrap();
+
+    // but no longer once the other one has been applied
+    let err = execute(
+        deps.as_mut(),
+        env_after(CONFIG_UPDATE_DELAY_SECONDS),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ExecuteConfigUpdate(ExecuteConfigUpdateMsg { id: pending[0].id }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CreationMaxFeeUnderMinFee {});
+
+    // the rejected update stays pending
+    assert_eq!(query_pending_config_updates(deps.as_ref()).updates.len(), 1);
+}
+
+#[test]
+fn test_cancel_config_update() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ScheduleConfigUpdate(ScheduleConfigUpdateMsg {
+            warp_account_code_id: Some(Uint64::new(42)),
+            ..empty_update()
+        }),
+    )
+    .unwrap();
+
+    let id = query_pending_config_updates(deps.as_ref()).updates[0].id;
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::CancelConfigUpdate(CancelConfigUpdateMsg { id }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        ExecuteMsg::CancelConfigUpdate(CancelConfigUpdateMsg { id }),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env_after(CONFIG_UPDATE_DELAY_SECONDS),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::ExecuteConfigUpdate(ExecuteConfigUpdateMsg { id }),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ConfigUpdateDoesNotExist {});
+}
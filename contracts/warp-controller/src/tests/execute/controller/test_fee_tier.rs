@@ -0,0 +1,463 @@
+use crate::contract::{execute, query, reply};
+use crate::execute::fee::apply_fee_discount;
+use crate::state::{JobQueue, STATE};
+use crate::tests::helpers::{default_instantiate_msg, instantiate_warp, OWNER};
+use crate::util::msg::{build_account_execute_generic_msgs, build_transfer_native_funds_msg};
+use crate::ContractError;
+use account_tracker::{AccountStatus, JobAccount, JobAccountResponse};
+use controller::fee_tier::{
+    AddressFeeTierResponse, AssignFeeTierMsg, FeeTier, QueryAddressFeeTierMsg, RemoveFeeTierMsg,
+    SetFeeTierMsg,
+};
+use controller::job::{CreateJobMsg, Job, JobStatus};
+use controller::{ExecuteMsg, QueryMsg};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balances, mock_env, mock_info,
+};
+use cosmwasm_std::{
+    coin, from_binary, from_slice, to_binary, Addr, Attribute, Binary, ContractResult, Reply,
+    Response, SubMsgResponse, SubMsgResult, SystemError, SystemResult, Uint128, Uint64, WasmQuery,
+};
+
+fn fee_tier(name: &str, discount: u64, stake_threshold: Option<u128>) -> FeeTier {
+    FeeTier {
+        name: name.to_string(),
+        creation_fee_discount: Uint64::new(discount),
+        maintenance_fee_discount: Uint64::new(discount),
+        burn_fee_discount: Uint64::new(discount),
+        stake_threshold: stake_threshold.map(Uint128::new),
+    }
+}
+
+fn set_fee_tier(tier: FeeTier) -> ExecuteMsg {
+    ExecuteMsg::SetFeeTier(SetFeeTierMsg { tier })
+}
+
+fn query_address_fee_tier(deps: cosmwasm_std::Deps, address: &str) -> AddressFeeTierResponse {
+    from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::QueryAddressFeeTier(QueryAddressFeeTierMsg {
+                address: address.to_string(),
+            }),
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_apply_fee_discount() {
+    assert_eq!(
+        apply_fee_discount(Uint128::new(1_000), Uint64::zero()),
+        Uint128::new(1_000)
+    );
+    assert_eq!(
+        apply_fee_discount(Uint128::new(1_000), Uint64::new(25)),
+        Uint128::new(750)
+    );
+    assert_eq!(
+        apply_fee_discount(Uint128::new(1_000), Uint64::new(100)),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_set_fee_tier_unauthorized() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        set_fee_tier(fee_tier("partner", 50, None)),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_set_fee_tier_discount_too_high() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_fee_tier(fee_tier("partner", 101, None)),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::FeeTierDiscountTooHigh {});
+}
+
+#[test]
+fn test_assign_fee_tier() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::AssignFeeTier(AssignFeeTierMsg {
+            addresses: vec!["partner".to_string()],
+            tier: Some("partner".to_string()),
+        }),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::FeeTierDoesNotExist {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_fee_tier(fee_tier("partner", 50, None)),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::AssignFeeTier(AssignFeeTierMsg {
+            addresses: vec!["partner".to_string()],
+            tier: Some("partner".to_string()),
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_address_fee_tier(deps.as_ref(), "partner").tier,
+        Some(fee_tier("partner", 50, None))
+    );
+    assert_eq!(query_address_fee_tier(deps.as_ref(), "user").tier, None);
+
+    // unassigning emits "none" as the tier name
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::AssignFeeTier(AssignFeeTierMsg {
+            addresses: vec!["other".to_string()],
+            tier: None,
+        }),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "fee_tier_name"), "none");
+
+    // removing a tier drops its assignments, so a new tier of the same name is not inherited
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::RemoveFeeTier(RemoveFeeTierMsg {
+            name: "partner".to_string(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "unassigned_addresses"), "1");
+    assert_eq!(query_address_fee_tier(deps.as_ref(), "partner").tier, None);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_fee_tier(fee_tier("partner", 50, None)),
+    )
+    .unwrap();
+    assert_eq!(query_address_fee_tier(deps.as_ref(), "partner").tier, None);
+}
+
+#[test]
+fn test_stake_based_fee_tier() {
+    let mut deps = mock_dependencies();
+    let mut msg = default_instantiate_msg();
+    msg.fee_tier_stake_contract = Some("stake_contract".to_string());
+    instantiate_warp(&mut deps, msg);
+
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let cw20::Cw20QueryMsg::Balance { address } = from_slice(msg).unwrap() else {
+                panic!("unexpected query")
+            };
+            let balance = match address.as_str() {
+                "whale" => Uint128::new(1_000_000),
+                "dolphin" => Uint128::new(10_000),
+                _ => Uint128::zero(),
+            };
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&cw20::BalanceResponse { balance }).unwrap(),
+            ))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    for tier in [
+        fee_tier("silver", 10, Some(10_000)),
+        fee_tier("gold", 50, Some(1_000_000)),
+        fee_tier("partner", 100, None),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            set_fee_tier(tier),
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        query_address_fee_tier(deps.as_ref(), "whale").tier,
+        Some(fee_tier("gold", 50, Some(1_000_000)))
+    );
+    assert_eq!(
+        query_address_fee_tier(deps.as_ref(), "dolphin").tier,
+        Some(fee_tier("silver", 10, Some(10_000)))
+    );
+    assert_eq!(query_address_fee_tier(deps.as_ref(), "shrimp").tier, None);
+
+    // explicit assignment takes precedence over stake
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::AssignFeeTier(AssignFeeTierMsg {
+            addresses: vec!["whale".to_string()],
+            tier: Some("partner".to_string()),
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_address_fee_tier(deps.as_ref(), "whale").tier,
+        Some(fee_tier("partner", 100, None))
+    );
+}
+
+#[test]
+fn test_failed_stake_query_errors() {
+    let mut deps = mock_dependencies();
+    let mut msg = default_instantiate_msg();
+    msg.fee_tier_stake_contract = Some("stake_contract".to_string());
+    instantiate_warp(&mut deps, msg);
+
+    deps.querier.update_wasm(|_| {
+        SystemResult::Err(SystemError::NoSuchContract {
+            addr: "stake_contract".to_string(),
+        })
+    });
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_fee_tier(fee_tier("gold", 50, Some(1_000_000))),
+    )
+    .unwrap();
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueryAddressFeeTier(QueryAddressFeeTierMsg {
+            address: "whale".to_string(),
+        }),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("stake_contract"));
+}
+
+#[test]
+fn test_stake_contract_not_queried_without_stake_tiers() {
+    let mut deps = mock_dependencies();
+    let mut msg = default_instantiate_msg();
+    msg.fee_tier_stake_contract = Some("stake_contract".to_string());
+    instantiate_warp(&mut deps, msg);
+
+    deps.querier
+        .update_wasm(|_| panic!("stake contract queried without stake based tiers"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_fee_tier(fee_tier("partner", 100, None)),
+    )
+    .unwrap();
+
+    assert_eq!(query_address_fee_tier(deps.as_ref(), "whale").tier, None);
+}
+
+// Resolver and account tracker share the "resolver" address until the tracker is instantiated
+fn mock_resolver_and_account_tracker(query: &WasmQuery) -> cosmwasm_std::QuerierResult {
+    let response = match query {
+        WasmQuery::Smart { msg, .. } => match from_slice::<resolver::QueryMsg>(msg) {
+            Ok(resolver::QueryMsg::QueryValidateJobCreation(_)) => to_binary(&None::<String>),
+            Ok(resolver::QueryMsg::QueryHydrateVars(_))
+            | Ok(resolver::QueryMsg::QueryApplyVarFn(_)) => to_binary(&"[]".to_string()),
+            _ => to_binary(&JobAccountResponse {
+                job_account: Some(JobAccount {
+                    account_addr: Addr::unchecked("job_account"),
+                    taken_by_job_id: Uint64::zero(),
+                    account_status: AccountStatus::Free,
+                }),
+            }),
+        },
+        _ => panic!("unexpected query"),
+    };
+
+    SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+}
+
+fn attribute(res: &Response, key: &str) -> String {
+    res.attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr: &Attribute| attr.value.clone())
+        .unwrap()
+}
+
+fn assign_partner_tier(deps: cosmwasm_std::DepsMut, address: &str) {
+    let mut deps = deps;
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_fee_tier(fee_tier("partner", 50, None)),
+    )
+    .unwrap();
+    execute(
+        deps,
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::AssignFeeTier(AssignFeeTierMsg {
+            addresses: vec![address.to_string()],
+            tier: Some("partner".to_string()),
+        }),
+    )
+    .unwrap();
+}
+
+fn create_job_msg(operational_amount: u128) -> ExecuteMsg {
+    ExecuteMsg::CreateJob(CreateJobMsg {
+        name: "job".to_string(),
+        description: "".to_string(),
+        labels: vec![],
+        terminate_condition: None,
+        executions: vec![],
+        vars: "[]".to_string(),
+        recurring: false,
+        reward: Uint128::new(1_000_000),
+        operational_amount: Uint128::new(operational_amount),
+        duration_days: Uint64::new(7),
+        assets_to_withdraw: None,
+        account_msgs: None,
+        cw_funds: None,
+        funding_account: None,
+    })
+}
+
+#[test]
+fn test_create_job_charges_discounted_fees() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+    deps.querier.update_wasm(mock_resolver_and_account_tracker);
+    assign_partner_tier(deps.as_mut(), "partner");
+
+    // full fees are 500_000 creation, 250_000 maintenance and 250_000 burn, halved for partner
+    let reward_plus_discounted_fees = 1_000_000 + 500_000;
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[coin(reward_plus_discounted_fees, "uluna")]),
+        create_job_msg(reward_plus_discounted_fees),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientOperationalFunds {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("partner", &[coin(reward_plus_discounted_fees, "uluna")]),
+        create_job_msg(reward_plus_discounted_fees),
+    )
+    .unwrap();
+
+    assert_eq!(attribute(&res, "job_creation_fee"), "250000");
+    assert_eq!(attribute(&res, "job_maintenance_fee"), "125000");
+    assert_eq!(attribute(&res, "job_burn_fee"), "125000");
+    assert_eq!(attribute(&res, "job_total_fees"), "500000");
+}
+
+#[test]
+fn test_recurring_job_charges_discounted_fees() {
+    let mut deps =
+        mock_dependencies_with_balances(&[("funding_account", &[coin(10_000_000, "uluna")])]);
+    instantiate_warp(&mut deps, default_instantiate_msg());
+    deps.querier.update_wasm(mock_resolver_and_account_tracker);
+    assign_partner_tier(deps.as_mut(), "partner");
+
+    let env = mock_env();
+    let state = STATE.load(deps.as_ref().storage).unwrap();
+    let job = JobQueue::add(
+        deps.as_mut().storage,
+        Job {
+            id: state.current_job_id,
+            prev_id: None,
+            owner: Addr::unchecked("partner"),
+            account: Addr::unchecked("job_account"),
+            last_update_time: Uint64::from(env.block.time.seconds()),
+            name: "job".to_string(),
+            status: JobStatus::Pending,
+            terminate_condition: None,
+            recurring: true,
+            vars: "[]".to_string(),
+            executions: vec![],
+            reward: Uint128::new(1_000_000),
+            description: "".to_string(),
+            labels: vec![],
+            assets_to_withdraw: vec![],
+            duration_days: Uint64::new(7),
+            created_at_time: Uint64::from(env.block.time.seconds()),
+            funding_account: Some(Addr::unchecked("funding_account")),
+            execution_count: Uint64::zero(),
+            last_execution_time: None,
+        },
+    )
+    .unwrap();
+
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: job.id.u64(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None::<Binary>,
+            }),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(attribute(&res, "job_total_fees"), "500000");
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == build_account_execute_generic_msgs(
+            "funding_account".to_string(),
+            vec![
+                build_transfer_native_funds_msg(
+                    "fee_collector".to_string(),
+                    vec![coin(500_000, "uluna")],
+                ),
+                build_transfer_native_funds_msg(
+                    env.contract.address.to_string(),
+                    vec![coin(1_000_000, "uluna")],
+                ),
+            ],
+        )));
+}
@@ -0,0 +1,231 @@
+use crate::contract::{execute, query};
+use crate::tests::helpers::{default_instantiate_msg, instantiate_warp, OWNER};
+use crate::ContractError;
+use common::ownership::OwnershipError;
+use controller::{
+    AcceptOwnershipMsg, CancelOwnershipProposalMsg, ConfigResponse, ExecuteMsg,
+    PendingOwnerResponse, ProposeOwnerMsg, QueryConfigMsg, QueryMsg, QueryPendingOwnerMsg,
+    UpdateConfigMsg,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_binary, Addr, Deps, Uint64};
+
+const NEW_OWNER: &str = "new_owner";
+
+fn propose_owner(expires_at_time: Option<Uint64>) -> ExecuteMsg {
+    ExecuteMsg::ProposeOwner(ProposeOwnerMsg {
+        owner: NEW_OWNER.to_string(),
+        expires_at_time,
+    })
+}
+
+fn query_owner(deps: Deps) -> Addr {
+    let res: ConfigResponse =
+        from_binary(&query(deps, mock_env(), QueryMsg::QueryConfig(QueryConfigMsg {})).unwrap())
+            .unwrap();
+    res.config.owner
+}
+
+fn query_pending_owner(deps: Deps) -> PendingOwnerResponse {
+    from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::QueryPendingOwner(QueryPendingOwnerMsg {}),
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_ownership_transfer() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(NEW_OWNER, &[]),
+        propose_owner(None),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Ownership(OwnershipError::Unauthorized {})
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        propose_owner(None),
+    )
+    .unwrap();
+
+    // owner is only replaced once the proposal is accepted
+    assert_eq!(query_owner(deps.as_ref()), Addr::unchecked(OWNER));
+    assert_eq!(
+        query_pending_owner(deps.as_ref())
+            .pending_owner
+            .unwrap()
+            .owner,
+        Addr::unchecked(NEW_OWNER)
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::AcceptOwnership(AcceptOwnershipMsg {}),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Ownership(OwnershipError::Unauthorized {})
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(NEW_OWNER, &[]),
+        ExecuteMsg::AcceptOwnership(AcceptOwnershipMsg {}),
+    )
+    .unwrap();
+
+    assert_eq!(query_owner(deps.as_ref()), Addr::unchecked(NEW_OWNER));
+    assert_eq!(query_pending_owner(deps.as_ref()).pending_owner, None);
+}
+
+#[test]
+fn test_ownership_proposal_expired() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let env = mock_env();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        propose_owner(Some(Uint64::new(env.block.time.seconds()))),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Ownership(OwnershipError::InvalidOwnershipProposalExpiry {})
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OWNER, &[]),
+        propose_owner(Some(Uint64::new(env.block.time.seconds() + 100))),
+    )
+    .unwrap();
+
+    let mut later_env = mock_env();
+    later_env.block.time = env.block.time.plus_seconds(100);
+
+    let err = execute(
+        deps.as_mut(),
+        later_env,
+        mock_info(NEW_OWNER, &[]),
+        ExecuteMsg::AcceptOwnership(AcceptOwnershipMsg {}),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Ownership(OwnershipError::OwnershipProposalExpired {})
+    );
+    assert_eq!(query_owner(deps.as_ref()), Addr::unchecked(OWNER));
+}
+
+#[test]
+fn test_cancel_ownership_proposal() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CancelOwnershipProposal(CancelOwnershipProposalMsg {}),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Ownership(OwnershipError::NoPendingOwner {})
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        propose_owner(None),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::CancelOwnershipProposal(CancelOwnershipProposalMsg {}),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(NEW_OWNER, &[]),
+        ExecuteMsg::AcceptOwnership(AcceptOwnershipMsg {}),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Ownership(OwnershipError::NoPendingOwner {})
+    );
+}
+
+#[test]
+fn test_update_config_proposes_owner() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            owner: Some(NEW_OWNER.to_string()),
+            fee_collector: None,
+            minimum_reward: None,
+            guardian: None,
+        }),
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "pending_owner" && a.value == NEW_OWNER));
+
+    // owner is only proposed, not replaced
+    assert_eq!(query_owner(deps.as_ref()), Addr::unchecked(OWNER));
+    assert_eq!(
+        query_pending_owner(deps.as_ref())
+            .pending_owner
+            .unwrap()
+            .owner,
+        Addr::unchecked(NEW_OWNER)
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(NEW_OWNER, &[]),
+        ExecuteMsg::AcceptOwnership(AcceptOwnershipMsg {}),
+    )
+    .unwrap();
+
+    assert_eq!(query_owner(deps.as_ref()), Addr::unchecked(NEW_OWNER));
+}
@@ -0,0 +1,187 @@
+use crate::contract::execute;
+use crate::tests::helpers::{default_instantiate_msg, instantiate_warp, GUARDIAN, OWNER};
+use crate::ContractError;
+use controller::job::{DeleteJobMsg, EvictJobMsg, ExecuteJobMsg};
+use controller::{ExecuteMsg, PausableAction, RemoveGuardianMsg, SetPausedMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::Uint64;
+
+fn set_paused(paused: Vec<PausableAction>) -> ExecuteMsg {
+    ExecuteMsg::SetPaused(SetPausedMsg { paused })
+}
+
+#[test]
+fn test_set_paused_unauthorized() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        set_paused(vec![PausableAction::ExecuteJob]),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_set_paused_blocks_actions() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        set_paused(vec![PausableAction::ExecuteJob, PausableAction::EvictJob]),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteJob(ExecuteJobMsg {
+            id: Uint64::new(10),
+            external_inputs: None,
+        }),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::ActionPaused {
+            action: PausableAction::ExecuteJob
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::EvictJob(EvictJobMsg {
+            id: Uint64::new(10),
+        }),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::ActionPaused {
+            action: PausableAction::EvictJob
+        }
+    );
+
+    // DeleteJob is never paused, it fails further down because the job does not exist
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::DeleteJob(DeleteJobMsg {
+            id: Uint64::new(10),
+        }),
+    )
+    .unwrap_err();
+
+    assert!(!matches!(err, ContractError::ActionPaused { .. }));
+
+    // owner can unpause
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_paused(vec![]),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::EvictJob(EvictJobMsg {
+            id: Uint64::new(10),
+        }),
+    )
+    .unwrap_err();
+
+    assert!(!matches!(err, ContractError::ActionPaused { .. }));
+}
+
+#[test]
+fn test_guardian_cannot_lift_pause() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        set_paused(vec![PausableAction::ExecuteJob]),
+    )
+    .unwrap();
+
+    // the guardian can only add to the paused actions
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        set_paused(vec![PausableAction::EvictJob]),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        set_paused(vec![PausableAction::ExecuteJob, PausableAction::EvictJob]),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        set_paused(vec![]),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn test_remove_guardian() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        ExecuteMsg::RemoveGuardian(RemoveGuardianMsg {}),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER, &[]),
+        ExecuteMsg::RemoveGuardian(RemoveGuardianMsg {}),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(GUARDIAN, &[]),
+        set_paused(vec![PausableAction::ExecuteJob]),
+    )
+    .unwrap_err();
+
+    assert_eq!(err, ContractError::Unauthorized {});
+}
@@ -0,0 +1,146 @@
+use crate::contract::{execute, instantiate};
+use crate::tests::helpers::{default_instantiate_msg, instantiate_warp, OWNER};
+use crate::ContractError;
+use controller::{ExecuteMsg, InstantiateMsg, ScheduleConfigUpdateMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{Uint128, Uint64};
+
+fn invalid_config_matrix() -> Vec<(InstantiateMsg, ContractError)> {
+    vec![
+        (
+            InstantiateMsg {
+                creation_fee_min: Uint128::new(2),
+                creation_fee_max: Uint128::new(1),
+                ..default_instantiate_msg()
+            },
+            ContractError::CreationMaxFeeUnderMinFee {},
+        ),
+        (
+            InstantiateMsg {
+                maintenance_fee_min: Uint128::new(2),
+                maintenance_fee_max: Uint128::new(1),
+                ..default_instantiate_msg()
+            },
+            ContractError::MaintenanceMaxFeeUnderMinFee {},
+        ),
+        (
+            InstantiateMsg {
+                duration_days_min: Uint64::new(90),
+                duration_days_max: Uint64::new(90),
+                ..default_instantiate_msg()
+            },
+            ContractError::DurationMaxDaysUnderMinDays {},
+        ),
+        (
+            InstantiateMsg {
+                duration_days_min: Uint64::new(91),
+                duration_days_max: Uint64::new(90),
+                ..default_instantiate_msg()
+            },
+            ContractError::DurationMaxDaysUnderMinDays {},
+        ),
+        (
+            InstantiateMsg {
+                duration_days_limit: Uint64::new(89),
+                ..default_instantiate_msg()
+            },
+            ContractError::DurationDaysLimit {},
+        ),
+        (
+            InstantiateMsg {
+                queue_size_left: Uint64::new(50_000),
+                queue_size_right: Uint64::new(50_000),
+                ..default_instantiate_msg()
+            },
+            ContractError::QueueSizeRightUnderQueueSizeLeft {},
+        ),
+        (
+            InstantiateMsg {
+                cancellation_fee_rate: Uint64::new(101),
+                ..default_instantiate_msg()
+            },
+            ContractError::CancellationFeeTooHigh {},
+        ),
+        (
+            InstantiateMsg {
+                burn_fee_rate: Uint128::new(101),
+                ..default_instantiate_msg()
+            },
+            ContractError::BurnFeeTooHigh {},
+        ),
+        (
+            InstantiateMsg {
+                fee_denom: "".to_string(),
+                ..default_instantiate_msg()
+            },
+            ContractError::FeeDenomEmpty {},
+        ),
+    ]
+}
+
+#[test]
+fn test_instantiate_invalid_config() {
+    for (msg, expected) in invalid_config_matrix() {
+        let mut deps = mock_dependencies();
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap_err();
+        assert_eq!(err, expected);
+    }
+}
+
+#[test]
+fn test_instantiate_boundary_config() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(
+        &mut deps,
+        InstantiateMsg {
+            creation_fee_min: Uint128::new(1),
+            creation_fee_max: Uint128::new(1),
+            maintenance_fee_min: Uint128::new(1),
+            maintenance_fee_max: Uint128::new(1),
+            duration_days_max: Uint64::new(180),
+            duration_days_limit: Uint64::new(180),
+            cancellation_fee_rate: Uint64::new(100),
+            burn_fee_rate: Uint128::new(100),
+            ..default_instantiate_msg()
+        },
+    );
+}
+
+#[test]
+fn test_schedule_config_update_invalid_config() {
+    for (msg, expected) in invalid_config_matrix() {
+        if msg.fee_denom.is_empty() {
+            // fee denom cannot be updated
+            continue;
+        }
+
+        let mut deps = mock_dependencies();
+        instantiate_warp(&mut deps, default_instantiate_msg());
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            ExecuteMsg::ScheduleConfigUpdate(ScheduleConfigUpdateMsg {
+                resolver_address: None,
+                warp_account_code_id: None,
+                cancellation_fee_rate: Some(msg.cancellation_fee_rate),
+                creation_fee_min: Some(msg.creation_fee_min),
+                creation_fee_max: Some(msg.creation_fee_max),
+                burn_fee_min: Some(msg.burn_fee_min),
+                maintenance_fee_min: Some(msg.maintenance_fee_min),
+                maintenance_fee_max: Some(msg.maintenance_fee_max),
+                duration_days_min: Some(msg.duration_days_min),
+                duration_days_max: Some(msg.duration_days_max),
+                duration_days_limit: Some(msg.duration_days_limit),
+                queue_size_left: Some(msg.queue_size_left),
+                queue_size_right: Some(msg.queue_size_right),
+                burn_fee_rate: Some(msg.burn_fee_rate),
+                fee_tier_stake_contract: None,
+                config_update_delay_seconds: None,
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(err, expected);
+    }
+}
@@ -1 +1,42 @@
+use crate::contract::instantiate;
+use controller::InstantiateMsg;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{OwnedDeps, Uint128, Uint64};
 
+pub const OWNER: &str = "owner";
+pub const GUARDIAN: &str = "guardian";
+pub const CONFIG_UPDATE_DELAY_SECONDS: u64 = 86_400;
+
+pub fn default_instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Some(OWNER.to_string()),
+        fee_denom: "uluna".to_string(),
+        fee_collector: Some("fee_collector".to_string()),
+        warp_account_code_id: Uint64::new(1),
+        account_tracker_code_id: Uint64::new(2),
+        minimum_reward: Uint128::new(100_000),
+        cancellation_fee_rate: Uint64::new(5),
+        resolver_address: "resolver".to_string(),
+        creation_fee_min: Uint128::new(500_000),
+        creation_fee_max: Uint128::new(100_000_000),
+        burn_fee_min: Uint128::new(250_000),
+        maintenance_fee_min: Uint128::new(250_000),
+        maintenance_fee_max: Uint128::new(10_000_000),
+        duration_days_min: Uint64::new(7),
+        duration_days_max: Uint64::new(90),
+        duration_days_limit: Uint64::new(180),
+        queue_size_left: Uint64::new(5_000),
+        queue_size_right: Uint64::new(50_000),
+        burn_fee_rate: Uint128::new(25),
+        fee_tier_stake_contract: None,
+        guardian: Some(GUARDIAN.to_string()),
+        config_update_delay_seconds: Uint64::new(CONFIG_UPDATE_DELAY_SECONDS),
+    }
+}
+
+pub fn instantiate_warp(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    msg: InstantiateMsg,
+) {
+    instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+}
@@ -0,0 +1,2 @@
+mod test_migrate;
+mod test_migrate_jobs;
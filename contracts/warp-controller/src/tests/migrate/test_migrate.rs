@@ -0,0 +1,175 @@
+use crate::contract::{
+    migrate, CONTRACT_NAME, CONTRACT_VERSION, DEFAULT_CONFIG_UPDATE_DELAY_SECONDS,
+};
+use crate::state::CONFIG;
+use crate::tests::helpers::{
+    default_instantiate_msg, instantiate_warp, CONFIG_UPDATE_DELAY_SECONDS,
+};
+use crate::ContractError;
+use common::migrate::MigrationError;
+use controller::MigrateMsg;
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{DepsMut, Storage, Uint64};
+use cw2::{get_contract_version, set_contract_version};
+
+#[test]
+fn test_instantiate_sets_contract_version() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, CONTRACT_VERSION);
+}
+
+#[test]
+fn test_migrate_from_unversioned_contract() {
+    let mut deps = mock_dependencies();
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            version: CONTRACT_VERSION.to_string(),
+        },
+    )
+    .unwrap();
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+}
+
+#[test]
+fn test_migrate_from_older_version() {
+    let mut deps = mock_dependencies();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.0.1").unwrap();
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            version: CONTRACT_VERSION.to_string(),
+        },
+    )
+    .unwrap();
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+}
+
+fn migrate_to_current(deps: DepsMut) {
+    migrate(
+        deps,
+        mock_env(),
+        MigrateMsg {
+            version: CONTRACT_VERSION.to_string(),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_migrate_sets_config_update_delay() {
+    // a config stored before the timelock existed has no delay field
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+    let config = serde_json_wasm::to_string(&CONFIG.load(&deps.storage).unwrap()).unwrap();
+    let legacy = config.replace(
+        &format!(
+            r#","config_update_delay_seconds":"{}""#,
+            CONFIG_UPDATE_DELAY_SECONDS
+        ),
+        "",
+    );
+    assert_ne!(legacy, config);
+    deps.storage.set(CONFIG.as_slice(), legacy.as_bytes());
+
+    migrate_to_current(deps.as_mut());
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(
+        config.config_update_delay_seconds,
+        Uint64::new(DEFAULT_CONFIG_UPDATE_DELAY_SECONDS)
+    );
+
+    // an existing delay is left untouched, including 0 which disables the timelock
+    for delay in [CONFIG_UPDATE_DELAY_SECONDS, 0] {
+        let mut deps = mock_dependencies();
+        let mut msg = default_instantiate_msg();
+        msg.config_update_delay_seconds = Uint64::new(delay);
+        instantiate_warp(&mut deps, msg);
+
+        migrate_to_current(deps.as_mut());
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.config_update_delay_seconds, Uint64::new(delay));
+    }
+}
+
+#[test]
+fn test_migrate_refuses_downgrade() {
+    let mut deps = mock_dependencies();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            version: CONTRACT_VERSION.to_string(),
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::Migration(MigrationError::Downgrade {
+            stored: "99.0.0".to_string(),
+            version: CONTRACT_VERSION.to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_migrate_target_version_mismatch() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            version: "99.0.0".to_string(),
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::Migration(MigrationError::VersionMismatch {
+            target: "99.0.0".to_string(),
+            version: CONTRACT_VERSION.to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_migrate_from_other_contract() {
+    let mut deps = mock_dependencies();
+    set_contract_version(&mut deps.storage, "crates.io:warp-resolver", "0.0.1").unwrap();
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            version: CONTRACT_VERSION.to_string(),
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::Migration(MigrationError::ContractMismatch {
+            contract: "crates.io:warp-resolver".to_string(),
+        })
+    );
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use crate::contract::execute;
+use crate::migrate::job::{run_job_migrations, JobMigration};
+use crate::state::{FINISHED_JOBS, PENDING_JOBS};
+use crate::tests::helpers::{default_instantiate_msg, instantiate_warp, OWNER};
+use crate::ContractError;
+use controller::job::Job;
+use controller::{ExecuteMsg, MigrateJobsMsg};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{Order, OwnedDeps, Storage, Uint128, Uint64};
+use cw_storage_plus::Map;
+use json_codec_wasm::Json;
+
+// The v5 -> v6 layout change, no longer registered but kept to exercise the machinery
+const V5_MIGRATION: &[JobMigration] = &[JobMigration {
+    pending_jobs_namespace: "pending_jobs_v5",
+    finished_jobs_namespace: "finished_jobs_v5",
+    index_namespaces: &["pending_jobs__reward_v5", "finished_jobs__reward_v5"],
+    upgrade: upgrade_v5_job,
+}];
+
+fn upgrade_v5_job(job: &mut HashMap<String, Json>) {
+    job.remove("requeue_on_evict");
+
+    let created_at_time = job.get("last_update_time").cloned().unwrap_or(Json::Null);
+    job.insert("created_at_time".to_string(), created_at_time);
+    job.insert("funding_account".to_string(), Json::Null);
+}
+
+// Job as stored before created_at_time and funding_account were added
+fn store_v5_job(storage: &mut dyn Storage, namespace: &str, id: u64, status: &str) {
+    let job = format!(
+        r#"{{"id":"{id}","prev_id":null,"owner":"owner","account":"account","last_update_time":"1000","name":"job","description":"desc","labels":[],"status":"{status}","terminate_condition":null,"duration_days":"30","executions":[],"vars":"[]","recurring":false,"requeue_on_evict":false,"reward":"{id}","assets_to_withdraw":[]}}"#
+    );
+    let key = Map::<u64, Job>::new(namespace).key(id);
+    storage.set(&key, job.as_bytes());
+}
+
+// Reward index entry as stored next to a v5 job
+fn store_v5_index(storage: &mut dyn Storage, namespace: &str, id: u64) {
+    let key = Map::<(u128, u64), u64>::new(namespace).key((id as u128, id));
+    storage.set(&key, &id.to_be_bytes());
+}
+
+fn migrate_jobs(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    sender: &str,
+    limit: u8,
+) -> Result<(String, String), ContractError> {
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::MigrateJobs(MigrateJobsMsg { limit }),
+    )?;
+
+    let attr = |key: &str| {
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .unwrap()
+            .value
+            .clone()
+    };
+
+    Ok((attr("migrated_jobs"), attr("done")))
+}
+
+#[test]
+fn test_migrate_jobs_in_batches() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    store_v5_job(&mut deps.storage, "pending_jobs_v5", 11, "Pending");
+    store_v5_job(&mut deps.storage, "pending_jobs_v5", 12, "Pending");
+    store_v5_job(&mut deps.storage, "finished_jobs_v5", 13, "Executed");
+
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 2).unwrap(),
+        (2, false)
+    );
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 2).unwrap(),
+        (1, true)
+    );
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 2).unwrap(),
+        (0, true)
+    );
+
+    let job = PENDING_JOBS().load(&deps.storage, 12).unwrap();
+    assert_eq!(job.created_at_time, Uint64::new(1000));
+    assert_eq!(job.funding_account, None);
+
+    let job = FINISHED_JOBS().load(&deps.storage, 13).unwrap();
+    assert_eq!(job.id, Uint64::new(13));
+
+    // owner index is populated for migrated jobs
+    let owner_jobs = PENDING_JOBS()
+        .idx
+        .owner
+        .prefix("owner".to_string())
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .count();
+    assert_eq!(owner_jobs, 2);
+}
+
+#[test]
+fn test_migrate_jobs_clears_legacy_indexes() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    store_v5_job(&mut deps.storage, "pending_jobs_v5", 11, "Pending");
+    store_v5_job(&mut deps.storage, "finished_jobs_v5", 12, "Executed");
+    store_v5_index(&mut deps.storage, "pending_jobs__reward_v5", 11);
+    store_v5_index(&mut deps.storage, "finished_jobs__reward_v5", 12);
+
+    // indexes are only cleared once every legacy job is moved
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 1).unwrap(),
+        (1, false)
+    );
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 1).unwrap(),
+        (1, false)
+    );
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 1).unwrap(),
+        (0, false)
+    );
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 1).unwrap(),
+        (0, true)
+    );
+    assert_eq!(
+        migrate_jobs(&mut deps, OWNER, 1).unwrap(),
+        ("0".to_string(), "true".to_string())
+    );
+
+    let legacy_index = Map::<(u128, u64), u64>::new("pending_jobs__reward_v5");
+    assert!(legacy_index
+        .keys_raw(&deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+    assert!(PENDING_JOBS().has(&deps.storage, 11));
+    assert!(FINISHED_JOBS().has(&deps.storage, 12));
+}
+
+#[test]
+fn test_migrate_jobs_keeps_current_jobs() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    store_v5_job(&mut deps.storage, "pending_jobs_v5", 11, "Pending");
+    run_job_migrations(&mut deps.storage, V5_MIGRATION, 10).unwrap();
+
+    let mut job = PENDING_JOBS().load(&deps.storage, 11).unwrap();
+    job.reward = Uint128::new(1_000_000);
+    PENDING_JOBS().save(&mut deps.storage, 11, &job).unwrap();
+
+    // legacy entries left behind by an earlier migration run
+    store_v5_job(&mut deps.storage, "pending_jobs_v5", 11, "Pending");
+
+    assert_eq!(
+        run_job_migrations(&mut deps.storage, V5_MIGRATION, 10).unwrap_err(),
+        ContractError::JobMigrationConflict { id: 11 }
+    );
+    assert_eq!(
+        PENDING_JOBS().load(&deps.storage, 11).unwrap().reward,
+        Uint128::new(1_000_000)
+    );
+}
+
+#[test]
+fn test_migrate_jobs_ignores_v5_jobs() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    store_v5_job(&mut deps.storage, "pending_jobs_v5", 11, "Pending");
+
+    assert_eq!(
+        migrate_jobs(&mut deps, OWNER, 10).unwrap(),
+        ("0".to_string(), "true".to_string())
+    );
+    assert!(!PENDING_JOBS().has(&deps.storage, 11));
+}
+
+#[test]
+fn test_migrate_jobs_unauthorized() {
+    let mut deps = mock_dependencies();
+    instantiate_warp(&mut deps, default_instantiate_msg());
+
+    assert_eq!(
+        migrate_jobs(&mut deps, "anyone", 10).unwrap_err(),
+        ContractError::Unauthorized {}
+    );
+}
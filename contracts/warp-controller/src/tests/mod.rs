@@ -1,4 +1,5 @@
 mod execute;
 mod helpers;
+mod migrate;
 mod query;
 mod util;
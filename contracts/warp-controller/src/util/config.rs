@@ -0,0 +1,49 @@
+use controller::Config;
+
+use crate::ContractError;
+
+pub trait ValidateConfig {
+    fn validate(&self) -> Result<(), ContractError>;
+}
+
+// Invariants relied upon by compute_*_fee and delete_job,
+// checked on instantiate and on every config update
+impl ValidateConfig for Config {
+    fn validate(&self) -> Result<(), ContractError> {
+        if self.creation_fee_max < self.creation_fee_min {
+            return Err(ContractError::CreationMaxFeeUnderMinFee {});
+        }
+
+        if self.maintenance_fee_max < self.maintenance_fee_min {
+            return Err(ContractError::MaintenanceMaxFeeUnderMinFee {});
+        }
+
+        if self.duration_days_max <= self.duration_days_min {
+            return Err(ContractError::DurationMaxDaysUnderMinDays {});
+        }
+
+        if self.duration_days_max > self.duration_days_limit
+            || self.duration_days_min > self.duration_days_limit
+        {
+            return Err(ContractError::DurationDaysLimit {});
+        }
+
+        if self.queue_size_right <= self.queue_size_left {
+            return Err(ContractError::QueueSizeRightUnderQueueSizeLeft {});
+        }
+
+        if self.cancellation_fee_rate.u64() > 100 {
+            return Err(ContractError::CancellationFeeTooHigh {});
+        }
+
+        if self.burn_fee_rate.u128() > 100 {
+            return Err(ContractError::BurnFeeTooHigh {});
+        }
+
+        if self.fee_denom.is_empty() {
+            return Err(ContractError::FeeDenomEmpty {});
+        }
+
+        Ok(())
+    }
+}
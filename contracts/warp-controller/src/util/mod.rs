@@ -1,3 +1,4 @@
+pub(crate) mod config;
 pub(crate) mod fee;
 pub(crate) mod filter;
 pub(crate) mod msg;
@@ -1,4 +1,4 @@
-use cosmwasm_std::{to_binary, BankMsg, Coin, CosmosMsg, Uint128, Uint64, WasmMsg};
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Uint128, Uint64, WasmMsg};
 
 use account_tracker::{
     FreeFundingAccountMsg, FreeJobAccountMsg, TakeFundingAccountMsg, TakeJobAccountMsg,
@@ -7,6 +7,8 @@ use controller::account::{
     AssetInfo, CwFund, FundTransferMsgs, TransferFromMsg, TransferNftMsg, WarpMsg, WarpMsgs,
     WithdrawAssetsMsg,
 };
+use controller::job::Job;
+use resolver::JobEnv;
 
 #[allow(clippy::too_many_arguments)]
 pub fn build_instantiate_account_tracker_msg(
@@ -216,3 +218,16 @@ pub fn build_account_withdraw_assets_msg(
         })],
     )
 }
+
+pub fn build_job_env(job: &Job, controller_addr: &Addr) -> JobEnv {
+    JobEnv {
+        warp_account_addr: Some(job.account.to_string()),
+        job_id: Some(job.id),
+        job_owner: Some(job.owner.to_string()),
+        funding_account_addr: job.funding_account.as_ref().map(|addr| addr.to_string()),
+        controller_addr: Some(controller_addr.to_string()),
+        execution_count: Some(job.execution_count),
+        job_created_at: Some(job.created_at_time),
+        last_execution_time: job.last_execution_time,
+    }
+}
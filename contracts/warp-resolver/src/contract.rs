@@ -1,7 +1,10 @@
+use crate::util::cache::QueryCache;
 use crate::util::condition::{resolve_cond, resolve_query_expr};
+use crate::util::explain::explain_cond;
+use crate::util::stargate::validate_stargate_vars;
 use crate::util::variable::{
-    apply_var_fn, has_duplicates, hydrate_msgs, hydrate_vars, msgs_valid, string_vars_in_vector,
-    vars_valid,
+    apply_var_fn, has_duplicates, hydrate_msgs, hydrate_vars, hydration_order, msgs_valid,
+    string_vars_in_vector, vars_valid,
 };
 use crate::ContractError;
 use controller::account::{warp_msgs_to_cosmos_msgs, WarpMsg};
@@ -9,24 +12,31 @@ use cosmwasm_std::{
     entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
 };
 
+use common::migrate::ensure_migratable;
+use cw2::set_contract_version;
 use cw_utils::nonpayable;
 use resolver::condition::Condition;
 use resolver::variable::{QueryExpr, Variable};
 use resolver::{
-    ExecuteApplyVarFnMsg, ExecuteHydrateMsgsMsg, ExecuteHydrateVarsMsg, ExecuteMsg,
+    ConditionTrace, ExecuteApplyVarFnMsg, ExecuteHydrateMsgsMsg, ExecuteHydrateVarsMsg, ExecuteMsg,
     ExecuteResolveConditionMsg, ExecuteSimulateQueryMsg, ExecuteValidateJobCreationMsg,
-    InstantiateMsg, MigrateMsg, QueryApplyVarFnMsg, QueryHydrateMsgsMsg, QueryHydrateVarsMsg,
-    QueryMsg, QueryResolveConditionMsg, QueryValidateJobCreationMsg, SimulateQueryMsg,
-    SimulateResponse, WarpMsgsToCosmosMsgsMsg,
+    InstantiateMsg, JobEnv, MigrateMsg, QueryApplyVarFnMsg, QueryExplainConditionMsg,
+    QueryHydrateMsgsMsg, QueryHydrateVarsMsg, QueryMsg, QueryResolveConditionMsg,
+    QueryValidateJobCreationMsg, SimulateQueryMsg, SimulateResponse, WarpMsgsToCosmosMsgsMsg,
 };
 
+pub const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
     _info: MessageInfo,
     _msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::new().add_attribute("action", "instantiate"))
 }
 
@@ -122,6 +132,7 @@ pub fn execute_hydrate_vars(
             vars: data.vars,
             external_inputs: data.external_inputs,
             warp_account_addr: data.warp_account_addr,
+            job_env: data.job_env,
         },
     )?;
 
@@ -143,6 +154,7 @@ pub fn execute_resolve_condition(
             condition: data.condition,
             vars: data.vars,
             warp_account_addr: data.warp_account_addr,
+            job_env: data.job_env,
         },
     )?;
 
@@ -164,6 +176,7 @@ pub fn execute_apply_var_fn(
             vars: data.vars,
             status: data.status,
             warp_account_addr: data.warp_account_addr,
+            job_env: data.job_env,
         },
     )?;
     Ok(Response::new()
@@ -202,6 +215,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::QueryResolveCondition(data) => {
             to_binary(&query_resolve_condition(deps, env, data)?)
         }
+        QueryMsg::QueryExplainCondition(data) => {
+            to_binary(&query_explain_condition(deps, env, data)?)
+        }
         QueryMsg::QueryApplyVarFn(data) => to_binary(&query_apply_var_fn(deps, env, data)?),
         QueryMsg::QueryHydrateMsgs(data) => to_binary(&query_hydrate_msgs(deps, env, data)?),
     }
@@ -237,6 +253,9 @@ fn query_validate_job_creation(
             ));
         }
 
+        hydration_order(&vars).map_err(|e| StdError::generic_err(e.to_string()))?;
+        validate_stargate_vars(&vars).map_err(|e| StdError::generic_err(e.to_string()))?;
+
         if !(string_vars_in_vector(&vars, &execution.condition)
             && string_vars_in_vector(&vars, &terminate_condition_str)
             && string_vars_in_vector(&vars, &execution.msgs))
@@ -260,6 +279,8 @@ fn query_validate_job_creation(
 }
 
 fn query_hydrate_vars(deps: Deps, env: Env, data: QueryHydrateVarsMsg) -> StdResult<String> {
+    let cache = QueryCache::new(&*deps.querier);
+    let deps = cache.deps(deps);
     let vars: Vec<Variable> =
         serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
 
@@ -269,7 +290,7 @@ fn query_hydrate_vars(deps: Deps, env: Env, data: QueryHydrateVarsMsg) -> StdRes
             env,
             vars,
             data.external_inputs,
-            data.warp_account_addr,
+            &job_env(data.warp_account_addr, data.job_env),
         )
         .map_err(|e| StdError::generic_err(e.to_string()))?,
     )
@@ -281,21 +302,58 @@ fn query_resolve_condition(
     env: Env,
     data: QueryResolveConditionMsg,
 ) -> StdResult<bool> {
+    let cache = QueryCache::new(&*deps.querier);
+    let deps = cache.deps(deps);
     let condition: Condition = serde_json_wasm::from_str(&data.condition)
         .map_err(|e| StdError::generic_err(e.to_string()))?;
     let vars: Vec<Variable> =
         serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
 
-    resolve_cond(deps, env, condition, &vars, data.warp_account_addr)
-        .map_err(|e| StdError::generic_err(e.to_string()))
+    resolve_cond(
+        deps,
+        env,
+        condition,
+        &vars,
+        &job_env(data.warp_account_addr, data.job_env),
+    )
+    .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+fn query_explain_condition(
+    deps: Deps,
+    env: Env,
+    data: QueryExplainConditionMsg,
+) -> StdResult<ConditionTrace> {
+    let cache = QueryCache::new(&*deps.querier);
+    let deps = cache.deps(deps);
+    let condition: Condition = serde_json_wasm::from_str(&data.condition)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let vars: Vec<Variable> =
+        serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok(explain_cond(
+        deps,
+        env,
+        condition,
+        &vars,
+        &job_env(data.warp_account_addr, data.job_env),
+    ))
 }
 
 fn query_apply_var_fn(deps: Deps, env: Env, data: QueryApplyVarFnMsg) -> StdResult<String> {
+    let cache = QueryCache::new(&*deps.querier);
+    let deps = cache.deps(deps);
     let vars: Vec<Variable> =
         serde_json_wasm::from_str(&data.vars).map_err(|e| StdError::generic_err(e.to_string()))?;
 
-    apply_var_fn(deps, env, vars, data.status, data.warp_account_addr)
-        .map_err(|e| StdError::generic_err(e.to_string()))
+    apply_var_fn(
+        deps,
+        env,
+        vars,
+        data.status,
+        &job_env(data.warp_account_addr, data.job_env),
+    )
+    .map_err(|e| StdError::generic_err(e.to_string()))
 }
 
 fn query_hydrate_msgs(
@@ -309,6 +367,15 @@ fn query_hydrate_msgs(
     hydrate_msgs(data.msgs, vars).map_err(|e| StdError::generic_err(e.to_string()))
 }
 
+// Legacy warp_account_addr is used when job_env does not provide one
+fn job_env(warp_account_addr: Option<String>, job_env: Option<JobEnv>) -> JobEnv {
+    let job_env = job_env.unwrap_or_default();
+    JobEnv {
+        warp_account_addr: job_env.warp_account_addr.or(warp_account_addr),
+        ..job_env
+    }
+}
+
 pub fn query_simulate_query(
     deps: Deps,
     env: Env,
@@ -328,6 +395,12 @@ pub fn query_simulate_query(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::new())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_migratable(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &msg.version)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("version", CONTRACT_VERSION))
 }
@@ -1,4 +1,5 @@
 use crate::ContractError::{CustomError, DecodeError, DeserializationError, SerializationError};
+use common::migrate::MigrationError;
 use cosmwasm_std::{OverflowError, StdError};
 use std::num::ParseIntError;
 use std::str::ParseBoolError;
@@ -9,6 +10,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Migration(#[from] MigrationError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -40,6 +44,9 @@ pub enum ContractError {
     #[error("Error resolving JSON path")]
     ResolveError {},
 
+    #[error("Selector error: {msg:?}")]
+    SelectorError { msg: String },
+
     #[error("Template fee not found.")]
     TemplateFeeNotFound {},
 
@@ -76,6 +83,9 @@ pub enum ContractError {
     #[error("Variable vector contains unused variables.")]
     ExcessVariablesInVector {},
 
+    #[error("Variables reference each other in a cycle: {path}.")]
+    VariableCycle { path: String },
+
     #[error("Msg error: {msg:?}")]
     MsgError { msg: String },
 }
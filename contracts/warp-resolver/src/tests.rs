@@ -7,7 +7,8 @@ use crate::util::variable::{hydrate_msgs, hydrate_vars};
 
 use cosmwasm_std::{testing::mock_env, WasmQuery};
 use cosmwasm_std::{
-    to_binary, BankQuery, Binary, ContractResult, CosmosMsg, OwnedDeps, Uint256, WasmMsg,
+    to_binary, BankQuery, Binary, Coin, ContractResult, CosmosMsg, OwnedDeps, Uint128, Uint256,
+    WasmMsg,
 };
 
 use crate::contract::query;
@@ -18,9 +19,24 @@ use cosmwasm_std::{from_slice, Empty, Querier, QueryRequest, SystemError, System
 use resolver::variable::{
     FnValue, QueryExpr, QueryVariable, StaticVariable, Variable, VariableKind,
 };
-use resolver::{QueryMsg, QueryValidateJobCreationMsg};
+use resolver::{JobEnv, QueryMsg, QueryValidateJobCreationMsg};
 use std::marker::PhantomData;
 
+mod test_cache;
+mod test_calendar;
+mod test_condition;
+mod test_explain;
+mod test_external;
+mod test_fallback;
+mod test_history;
+mod test_int256;
+mod test_math;
+mod test_path;
+mod test_placeholder;
+mod test_raw_query;
+mod test_stargate;
+mod test_var_order;
+
 #[cw_serde]
 struct TestStruct {
     test: String,
@@ -101,7 +117,10 @@ impl WasmMockQuerier {
         request: &QueryRequest<Empty>,
     ) -> SystemResult<ContractResult<Binary>> {
         match &request {
-            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+            QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr,
+                msg: _,
+            }) => {
                 // Check if the query is for the vault contract address to get subaccount_id
                 if contract_addr == "mock_vault_contract_addr" {
                     // Simulate response with subaccount_id
@@ -113,6 +132,37 @@ impl WasmMockQuerier {
                         }
                     });
                     SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
+                } else if contract_addr == "mock_cw20_contract_addr" {
+                    let response = cw20::BalanceResponse {
+                        balance: Uint128::new(500),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
+                } else if contract_addr == "mock_cw721_contract_addr" {
+                    let response = cw721::OwnerOfResponse {
+                        owner: "terra1owner".to_string(),
+                        approvals: vec![],
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
+                } else if contract_addr == "mock_pool_contract_addr" {
+                    let response = json!({
+                        "pools": [
+                            {
+                                "id": 1,
+                                "assets": [
+                                    { "denom": "uluna", "amount": "1000" },
+                                    { "denom": "uusdc", "amount": "2000" }
+                                ]
+                            },
+                            {
+                                "id": 2,
+                                "assets": [
+                                    { "denom": "uluna", "amount": "3000" },
+                                    { "denom": "uatom", "amount": "4000" }
+                                ]
+                            }
+                        ]
+                    });
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
                 } else {
                     // Default mock response for other smart contract queries
                     let response = json!({
@@ -123,6 +173,12 @@ impl WasmMockQuerier {
                     SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
                 }
             }
+            QueryRequest::Bank(BankQuery::Balance { address, denom }) if denom == "uluna" => {
+                let response = cosmwasm_std::BalanceResponse {
+                    amount: Coin::new(if address == "terra1owner" { 1_000 } else { 0 }, denom),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_binary(&response).unwrap()))
+            }
             QueryRequest::Bank(BankQuery::Balance {
                 address: contract_addr,
                 denom: _,
@@ -153,6 +209,8 @@ fn test_hydrate_vars_nested_variables_binary_json() {
         init_fn: FnValue::String(StringValue::Simple("contract_addr".to_string())),
         reinitialize: false,
         update_fn: None,
+        default: None,
+        on_error: None,
     });
 
     let var4 = Variable::Static(StaticVariable {
@@ -163,6 +221,8 @@ fn test_hydrate_vars_nested_variables_binary_json() {
         init_fn: FnValue::String(StringValue::Ref("$warp.variable.var5".to_string())),
         reinitialize: false,
         update_fn: None,
+        default: None,
+        on_error: None,
     });
 
     let var3 = Variable::Query(QueryVariable {
@@ -179,6 +239,8 @@ fn test_hydrate_vars_nested_variables_binary_json() {
         reinitialize: false,
         update_fn: None,
         encode: true,
+        default: None,
+        on_error: None,
     });
 
     let var1 = Variable::Query(QueryVariable {
@@ -195,6 +257,8 @@ fn test_hydrate_vars_nested_variables_binary_json() {
         reinitialize: false,
         update_fn: None,
         encode: true,
+        default: None,
+        on_error: None,
     });
 
     let var2 = Variable::Query(QueryVariable {
@@ -211,10 +275,12 @@ fn test_hydrate_vars_nested_variables_binary_json() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let vars = vec![var5, var4, var3, var1, var2];
-    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, None).unwrap();
+    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, &JobEnv::default()).unwrap();
 
     assert_eq!(
         hydrated_vars[4],
@@ -232,6 +298,8 @@ fn test_hydrate_vars_nested_variables_binary_json() {
             reinitialize: false,
             update_fn: None,
             encode: false,
+            default: None,
+            on_error: None,
         })
     );
 }
@@ -249,6 +317,8 @@ fn test_hydrate_vars_nested_variables_binary() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let init_fn = QueryExpr {
@@ -267,10 +337,12 @@ fn test_hydrate_vars_nested_variables_binary() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let vars = vec![var1, var2];
-    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, None).unwrap();
+    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, &JobEnv::default()).unwrap();
 
     assert_eq!(
         hydrated_vars[1],
@@ -288,6 +360,8 @@ fn test_hydrate_vars_nested_variables_binary() {
             reinitialize: false,
             update_fn: None,
             encode: false,
+            default: None,
+            on_error: None,
         })
     );
 }
@@ -304,6 +378,8 @@ fn test_hydrate_vars_nested_variables_non_binary() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let init_fn = QueryExpr {
@@ -322,10 +398,12 @@ fn test_hydrate_vars_nested_variables_non_binary() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let vars = vec![var1, var2];
-    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, None).unwrap();
+    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, &JobEnv::default()).unwrap();
 
     assert_eq!(
         hydrated_vars[1],
@@ -343,6 +421,8 @@ fn test_hydrate_vars_nested_variables_non_binary() {
             reinitialize: false,
             update_fn: None,
             encode: false,
+            default: None,
+            on_error: None,
         })
     );
 }
@@ -360,6 +440,8 @@ fn test_hydrate_static_nested_vars_and_hydrate_msgs() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     // ============ TEST HYDRATED VALUE  ============
@@ -381,10 +463,13 @@ fn test_hydrate_static_nested_vars_and_hydrate_msgs() {
         update_fn: None,
         // when encode is false, value will not be base64 encoded after msgs hydration
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let vars = vec![var1.clone(), var2];
-    let hydrated_vars = hydrate_vars(deps.as_ref(), env.clone(), vars, None, None).unwrap();
+    let hydrated_vars =
+        hydrate_vars(deps.as_ref(), env.clone(), vars, None, &JobEnv::default()).unwrap();
     let hydrated_var1 = hydrated_vars[0].clone();
     let hydrated_var2 = hydrated_vars[1].clone();
     match hydrated_var2.clone() {
@@ -407,10 +492,12 @@ fn test_hydrate_static_nested_vars_and_hydrate_msgs() {
         update_fn: None,
         // when encode is true, value will be base64 encoded after msgs hydration
         encode: true,
+        default: None,
+        on_error: None,
     });
 
     let vars = vec![var1, var3];
-    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, None).unwrap();
+    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, &JobEnv::default()).unwrap();
     let hydrated_var3 = hydrated_vars[1].clone();
     match hydrated_var3.clone() {
         Variable::Static(static_var) => {
@@ -484,6 +571,8 @@ fn test_hydrate_static_env_vars_and_hydrate_msgs() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let var2 = Variable::Static(StaticVariable {
@@ -494,6 +583,8 @@ fn test_hydrate_static_env_vars_and_hydrate_msgs() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let var3 = Variable::Static(StaticVariable {
@@ -504,6 +595,8 @@ fn test_hydrate_static_env_vars_and_hydrate_msgs() {
         reinitialize: false,
         update_fn: None,
         encode: true,
+        default: None,
+        on_error: None,
     });
 
     let var4 = Variable::Static(StaticVariable {
@@ -514,6 +607,8 @@ fn test_hydrate_static_env_vars_and_hydrate_msgs() {
         reinitialize: false,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     let vars = vec![var1, var2, var3, var4];
@@ -522,7 +617,10 @@ fn test_hydrate_static_env_vars_and_hydrate_msgs() {
         env,
         vars,
         None,
-        Some(dummy_warp_account_addr.clone()),
+        &JobEnv {
+            warp_account_addr: Some(dummy_warp_account_addr.clone()),
+            ..Default::default()
+        },
     )
     .unwrap();
 
@@ -615,6 +713,8 @@ fn test_hydrate_static_and_custom_query_vars() {
         },
         reinitialize: true,
         update_fn: None,
+        default: None,
+        on_error: None,
     });
 
     let next_config = Variable::Query(QueryVariable {
@@ -631,12 +731,14 @@ fn test_hydrate_static_and_custom_query_vars() {
         reinitialize: true,
         update_fn: None,
         encode: false,
+        default: None,
+        on_error: None,
     });
 
     // Hydrate variables
     let vars = vec![subaccount_id, next_config];
     // let vars = vec![next_config];
-    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, None).unwrap();
+    let hydrated_vars = hydrate_vars(deps.as_ref(), env, vars, None, &JobEnv::default()).unwrap();
 
     println!("{:?}", hydrated_vars);
 }
@@ -0,0 +1,83 @@
+use crate::util::cache::QueryCache;
+use crate::util::variable::hydrate_vars;
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{
+    to_binary, Binary, ContractResult, Deps, Querier, QuerierResult, QuerierWrapper, QueryRequest,
+    SystemResult, WasmQuery,
+};
+use resolver::variable::{QueryExpr, QueryVariable, Variable, VariableKind};
+use resolver::JobEnv;
+use std::cell::Cell;
+
+struct CountingQuerier {
+    count: Cell<u32>,
+}
+
+impl Querier for CountingQuerier {
+    fn raw_query(&self, _bin_request: &[u8]) -> QuerierResult {
+        self.count.set(self.count.get() + 1);
+        SystemResult::Ok(ContractResult::Ok(Binary::from(
+            r#"{"reserve_a":"10","reserve_b":"20"}"#.as_bytes(),
+        )))
+    }
+}
+
+fn reserve_var(name: &str, reserve: &str, pool: &str) -> Variable {
+    Variable::Query(QueryVariable {
+        kind: VariableKind::Uint,
+        name: name.to_string(),
+        encode: false,
+        init_fn: QueryExpr {
+            selector: format!("$.{}", reserve),
+            query: QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: pool.to_string(),
+                msg: to_binary(&"pool").unwrap(),
+            }),
+        },
+        reinitialize: false,
+        value: None,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })
+}
+
+#[test]
+fn test_hydrate_vars_queries_each_request_once() {
+    let querier = CountingQuerier {
+        count: Cell::new(0),
+    };
+    let storage = MockStorage::new();
+    let api = MockApi::default();
+    let cache = QueryCache::new(&querier);
+    let deps = cache.deps(Deps {
+        storage: &storage,
+        api: &api,
+        querier: QuerierWrapper::new(&querier),
+    });
+
+    let vars = vec![
+        reserve_var("a", "reserve_a", "pool"),
+        reserve_var("b", "reserve_b", "pool"),
+        reserve_var("c", "reserve_a", "other_pool"),
+    ];
+    let hydrated = hydrate_vars(deps, mock_env(), vars, None, &JobEnv::default()).unwrap();
+
+    let values: Vec<Option<String>> = hydrated
+        .into_iter()
+        .map(|var| match var {
+            Variable::Query(v) => v.value,
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            Some("10".to_string()),
+            Some("20".to_string()),
+            Some("10".to_string())
+        ]
+    );
+    // both pool reserves share one query, the other pool needs its own
+    assert_eq!(querier.count.get(), 2);
+}
@@ -0,0 +1,136 @@
+use crate::util::calendar::{add_months, resolve_calendar_fn, DateTime};
+use crate::util::condition::{resolve_cond, resolve_num_value_int};
+use crate::ContractError;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Uint256;
+use resolver::condition::{
+    Condition, Expr, GenExpr, IntFnOp, NumEnvValue, NumFnValue, NumOp, NumValue,
+};
+use resolver::int256::Int256;
+use resolver::JobEnv;
+
+use super::mock_dependencies;
+
+// 2019-10-23 02:23:39 UTC, a Wednesday, mock_env block time
+const MOCK_TIME: u64 = 1571797419;
+
+#[test]
+fn test_date_time_fields() {
+    let date = DateTime::from_timestamp(MOCK_TIME);
+    assert_eq!(
+        (
+            date.year,
+            date.month,
+            date.day,
+            date.weekday,
+            date.hour,
+            date.minute
+        ),
+        (2019, 10, 23, 3, 2, 23)
+    );
+
+    let leap_day = DateTime::from_timestamp(951782400);
+    assert_eq!((leap_day.year, leap_day.month, leap_day.day), (2000, 2, 29));
+
+    let epoch = DateTime::from_timestamp(0);
+    assert_eq!(
+        (epoch.year, epoch.month, epoch.day, epoch.weekday),
+        (1970, 1, 1, 4)
+    );
+}
+
+#[test]
+fn test_start_of() {
+    assert_eq!(
+        resolve_calendar_fn(&IntFnOp::StartOfDay, MOCK_TIME).unwrap(),
+        1571788800
+    );
+    assert_eq!(
+        resolve_calendar_fn(&IntFnOp::StartOfWeek, MOCK_TIME).unwrap(),
+        1571616000
+    );
+    assert_eq!(
+        resolve_calendar_fn(&IntFnOp::StartOfMonth, MOCK_TIME).unwrap(),
+        1569888000
+    );
+    assert!(resolve_calendar_fn(&IntFnOp::Abs, MOCK_TIME).is_err());
+
+    // 1970-01-01 is a Thursday, its week starts before the epoch
+    assert_eq!(
+        resolve_calendar_fn(&IntFnOp::StartOfWeek, 0),
+        Err(ContractError::ConditionError {
+            msg: "Start of week of 0 is before 1970-01-01.".to_string()
+        })
+    );
+    // 1970-01-05 is the first Monday
+    assert_eq!(
+        resolve_calendar_fn(&IntFnOp::StartOfWeek, 4 * 86400 + 1).unwrap(),
+        4 * 86400
+    );
+}
+
+#[test]
+fn test_add_months() {
+    // clamped to the end of shorter months, time of day is kept
+    assert_eq!(add_months(1706704200, 1).unwrap(), 1709209800);
+    assert_eq!(add_months(1675123200, 1).unwrap(), 1677542400);
+    // across year boundaries, in both directions
+    assert_eq!(add_months(1702627200, 1).unwrap(), 1705305600);
+    assert_eq!(add_months(1702627200, -2).unwrap(), 1697356800);
+    // 2100 is not a leap year
+    assert_eq!(add_months(951782400, 1200).unwrap(), 4107456000);
+    assert!(add_months(0, -1).is_err());
+}
+
+#[test]
+fn test_calendar_fns_in_conditions() {
+    let deps = mock_dependencies();
+
+    // runs on Wednesdays
+    let weekday = Condition::Expr(Box::new(Expr::Uint(GenExpr {
+        left: NumValue::Fn(NumFnValue {
+            op: IntFnOp::Weekday,
+            right: Box::new(NumValue::Env(NumEnvValue::Time)),
+        }),
+        op: NumOp::Eq,
+        right: NumValue::Simple(Uint256::from(3u64)),
+    })));
+    assert!(resolve_cond(
+        deps.as_ref(),
+        mock_env(),
+        weekday,
+        &vec![],
+        &JobEnv::default()
+    )
+    .unwrap());
+
+    // next monthly rebalance of a Timestamp variable
+    let next_run = resolve_num_value_int(
+        deps.as_ref(),
+        mock_env(),
+        NumValue::Fn(NumFnValue {
+            op: IntFnOp::AddMonths { months: 1 },
+            right: Box::new(NumValue::Fn(NumFnValue {
+                op: IntFnOp::StartOfMonth,
+                right: Box::new(NumValue::Simple(Int256::from(MOCK_TIME as i128))),
+            })),
+        }),
+        &vec![],
+        &JobEnv::default(),
+    )
+    .unwrap();
+    // 2019-11-01 00:00:00 UTC
+    assert_eq!(next_run, Int256::from(1572566400));
+
+    let negative = resolve_num_value_int(
+        deps.as_ref(),
+        mock_env(),
+        NumValue::Fn(NumFnValue {
+            op: IntFnOp::Year,
+            right: Box::new(NumValue::Simple(Int256::from(-1))),
+        }),
+        &vec![],
+        &JobEnv::default(),
+    );
+    assert!(negative.is_err());
+}
@@ -0,0 +1,557 @@
+use crate::util::condition::resolve_cond;
+use crate::util::regex::is_match;
+use crate::ContractError;
+use controller::account::AssetInfo;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_slice, Addr, Decimal256, Uint256, Uint64};
+use resolver::condition::{
+    BalanceExpr, Condition, DecimalFnOp, Expr, FixedTimeExpr, GenExpr, IntFnOp, NumEnvValue,
+    NumExprOp, NumExprValue, NumFnValue, NumOp, NumValue, StringEnvValue, StringOp, StringValue,
+    TimeExpr, TimeOp, TimestampExpr,
+};
+use resolver::int256::Int256;
+use resolver::variable::{FnValue, StaticVariable, Variable, VariableKind};
+use resolver::JobEnv;
+use std::str::FromStr;
+
+use super::mock_dependencies;
+
+fn resolve(cond: Condition, vars: &Vec<Variable>) -> Result<bool, ContractError> {
+    let deps = mock_dependencies();
+    resolve_cond(deps.as_ref(), mock_env(), cond, vars, &JobEnv::default())
+}
+
+fn string_cond(left: &str, op: StringOp, right: &str) -> Condition {
+    Condition::Expr(Box::new(Expr::String(GenExpr {
+        left: StringValue::Simple(left.to_string()),
+        op,
+        right: StringValue::Simple(right.to_string()),
+    })))
+}
+
+fn uint_cond(left: u64, op: NumOp<NumValue<Uint256, NumExprOp, IntFnOp>>, right: u64) -> Condition {
+    Condition::Expr(Box::new(Expr::Uint(GenExpr {
+        left: NumValue::Simple(Uint256::from(left)),
+        op,
+        right: NumValue::Simple(Uint256::from(right)),
+    })))
+}
+
+fn uint_between(left: u64, lower: u64, upper: u64) -> Condition {
+    let upper = NumValue::Simple(Uint256::from(upper));
+    uint_cond(left, NumOp::Between { upper }, lower)
+}
+
+fn uint_not_between(left: u64, lower: u64, upper: u64) -> Condition {
+    let upper = NumValue::Simple(Uint256::from(upper));
+    uint_cond(left, NumOp::NotBetween { upper }, lower)
+}
+
+fn int_cond(left: i128, op: NumOp<NumValue<Int256, NumExprOp, IntFnOp>>, right: i128) -> Condition {
+    Condition::Expr(Box::new(Expr::Int(GenExpr {
+        left: NumValue::Simple(left.into()),
+        op,
+        right: NumValue::Simple(right.into()),
+    })))
+}
+
+fn decimal_between(left: &str, lower: &str, upper: &str) -> Condition {
+    let dec = |s: &str| Decimal256::from_str(s).unwrap();
+    Condition::Expr(Box::new(Expr::Decimal(GenExpr {
+        left: NumValue::<Decimal256, NumExprOp, DecimalFnOp>::Simple(dec(left)),
+        op: NumOp::Between {
+            upper: NumValue::Simple(dec(upper)),
+        },
+        right: NumValue::Simple(dec(lower)),
+    })))
+}
+
+#[test]
+fn test_string_op_in() {
+    let values = StringOp::In(vec!["uluna".to_string(), "uatom".to_string()]);
+
+    assert!(resolve(string_cond("uluna", values.clone(), ""), &vec![]).unwrap());
+    assert!(!resolve(string_cond("uosmo", values, ""), &vec![]).unwrap());
+    assert!(!resolve(string_cond("uluna", StringOp::In(vec![]), "uluna"), &vec![]).unwrap());
+}
+
+#[test]
+fn test_string_op_matches() {
+    assert!(resolve(
+        string_cond("ibc/27394FB092D2ECCD", StringOp::Matches, "^ibc/[0-9A-F]+$"),
+        &vec![]
+    )
+    .unwrap());
+    assert!(!resolve(
+        string_cond("factory/terra1/uluna", StringOp::Matches, "^ibc/[0-9A-F]+$"),
+        &vec![]
+    )
+    .unwrap());
+    assert_eq!(
+        resolve(
+            string_cond("uluna", StringOp::Matches, "(unclosed"),
+            &vec![]
+        ),
+        Err(ContractError::ConditionError {
+            msg: "Invalid pattern (unclosed: unbalanced '('.".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_string_op_matches_ref_pattern() {
+    let vars = vec![Variable::Static(StaticVariable {
+        kind: VariableKind::String,
+        name: "pattern".to_string(),
+        encode: false,
+        value: Some("^terra1[a-z0-9]{38}$".to_string()),
+        init_fn: FnValue::String(StringValue::Simple("^terra1[a-z0-9]{38}$".to_string())),
+        reinitialize: false,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })];
+
+    let cond = Condition::Expr(Box::new(Expr::String(GenExpr {
+        left: StringValue::Simple("terra1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string()),
+        op: StringOp::Matches,
+        right: StringValue::Ref("$warp.variable.pattern".to_string()),
+    })));
+
+    assert!(resolve(cond, &vars).unwrap());
+}
+
+#[test]
+fn test_regex_subset() {
+    let cases = vec![
+        ("abc", "xxabcxx", true),
+        ("^abc", "xxabc", false),
+        ("abc$", "abcxx", false),
+        ("^$", "", true),
+        ("a.c", "abc", true),
+        ("a\\.c", "abc", false),
+        ("a\\.c", "a.c", true),
+        ("^a*$", "", true),
+        ("^a+$", "", false),
+        ("^a+$", "aaaa", true),
+        ("^colou?r$", "color", true),
+        ("^colou?r$", "colour", true),
+        ("^(ibc|factory)/", "factory/x", true),
+        ("^(ibc|factory)/", "gamm/pool/1", false),
+        ("^[^/]+$", "uluna", true),
+        ("^[^/]+$", "ibc/abc", false),
+        ("^\\d{3}$", "123", true),
+        ("^\\d{3}$", "1234", false),
+        ("^\\d{2,}$", "1", false),
+        ("^\\d{2,}$", "12345", true),
+        ("^\\w{1,3}-\\s?x$", "ab- x", true),
+        ("^[a-c-]+$", "a-b-c", true),
+        ("^(a|)b$", "b", true),
+        (
+            "^(a*)*b$",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaac",
+            false,
+        ),
+    ];
+
+    for (pattern, input, expected) in cases {
+        assert_eq!(
+            is_match(pattern, input).unwrap(),
+            expected,
+            "{} on {}",
+            pattern,
+            input
+        );
+    }
+}
+
+#[test]
+fn test_regex_invalid_patterns() {
+    let invalid = vec![
+        "(a", "a)", "[a", "*a", "a**", "a{2", "a{3,2}", "a{101}", "[z-a]", "\\q",
+    ];
+    for pattern in invalid {
+        assert!(is_match(pattern, "a").is_err(), "{}", pattern);
+    }
+
+    // bounded pattern length and program size
+    assert!(is_match(&"a".repeat(257), "a").is_err());
+    assert!(is_match("((a{100}){100}){100}", "a").is_err());
+}
+
+#[test]
+fn test_num_op_between() {
+    assert!(resolve(uint_between(5, 5, 10), &vec![]).unwrap());
+    assert!(resolve(uint_between(10, 5, 10), &vec![]).unwrap());
+    assert!(!resolve(uint_between(11, 5, 10), &vec![]).unwrap());
+    assert!(resolve(uint_not_between(4, 5, 10), &vec![]).unwrap());
+    assert!(!resolve(uint_not_between(7, 5, 10), &vec![]).unwrap());
+
+    let upper = || NumValue::Simple(Int256::from(0i128));
+    assert!(resolve(
+        int_cond(-5, NumOp::Between { upper: upper() }, -10),
+        &vec![]
+    )
+    .unwrap());
+    assert!(resolve(
+        int_cond(1, NumOp::NotBetween { upper: upper() }, -10),
+        &vec![]
+    )
+    .unwrap());
+
+    assert!(resolve(decimal_between("0.5", "0.25", "0.75"), &vec![]).unwrap());
+    assert!(!resolve(decimal_between("0.8", "0.25", "0.75"), &vec![]).unwrap());
+}
+
+#[test]
+fn test_num_op_json() {
+    let cond: Condition = from_slice(
+        br#"{"expr":{"uint":{"left":{"simple":"5"},"op":"lt","right":{"simple":"6"}}}}"#,
+    )
+    .unwrap();
+    assert!(resolve(cond, &vec![]).unwrap());
+
+    let cond: Condition = from_slice(
+        br#"{"expr":{"uint":{"left":{"simple":"5"},"op":{"between":{"upper":{"simple":"9"}}},"right":{"simple":"1"}}}}"#,
+    )
+    .unwrap();
+    assert_eq!(cond, uint_between(5, 1, 9));
+    assert!(resolve(cond, &vec![]).unwrap());
+
+    // between carries its own upper bound
+    assert!(from_slice::<Condition>(
+        br#"{"expr":{"uint":{"left":{"simple":"5"},"op":"between","right":{"simple":"1"}}}}"#,
+    )
+    .is_err());
+}
+
+fn balance_cond(
+    address: StringValue<String>,
+    asset: AssetInfo,
+    op: NumOp<NumValue<Uint256, NumExprOp, IntFnOp>>,
+    value: u64,
+) -> Condition {
+    Condition::Expr(Box::new(Expr::Balance(BalanceExpr {
+        address,
+        asset,
+        op,
+        value: NumValue::Simple(Uint256::from(value)),
+    })))
+}
+
+#[test]
+fn test_balance_expr_native() {
+    let owner = || StringValue::Simple("terra1owner".to_string());
+    let native = || AssetInfo::Native("uluna".to_string());
+
+    assert!(resolve(balance_cond(owner(), native(), NumOp::Eq, 1_000), &vec![]).unwrap());
+    assert!(!resolve(balance_cond(owner(), native(), NumOp::Gt, 1_000), &vec![]).unwrap());
+    assert!(resolve(
+        balance_cond(
+            StringValue::Simple("terra1other".to_string()),
+            native(),
+            NumOp::Eq,
+            0
+        ),
+        &vec![]
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_balance_expr_cw20() {
+    let cond = Condition::Expr(Box::new(Expr::Balance(BalanceExpr {
+        address: StringValue::Simple("terra1owner".to_string()),
+        asset: AssetInfo::Cw20(Addr::unchecked("mock_cw20_contract_addr")),
+        op: NumOp::Between {
+            upper: NumValue::Simple(Uint256::from(500u64)),
+        },
+        value: NumValue::Simple(Uint256::from(100u64)),
+    })));
+
+    assert!(resolve(cond, &vec![]).unwrap());
+}
+
+#[test]
+fn test_balance_expr_cw721() {
+    let nft = || AssetInfo::Cw721(Addr::unchecked("mock_cw721_contract_addr"), "1".to_string());
+
+    assert!(resolve(
+        balance_cond(
+            StringValue::Simple("terra1owner".to_string()),
+            nft(),
+            NumOp::Eq,
+            1
+        ),
+        &vec![]
+    )
+    .unwrap());
+    assert!(resolve(
+        balance_cond(
+            StringValue::Simple("terra1other".to_string()),
+            nft(),
+            NumOp::Eq,
+            0
+        ),
+        &vec![]
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_balance_expr_warp_account() {
+    let deps = mock_dependencies();
+    let cond = balance_cond(
+        StringValue::Env(StringEnvValue::WarpAccountAddr),
+        AssetInfo::Native("uluna".to_string()),
+        NumOp::Gte,
+        1_000,
+    );
+
+    assert!(resolve_cond(
+        deps.as_ref(),
+        mock_env(),
+        cond,
+        &vec![],
+        &JobEnv {
+            warp_account_addr: Some("terra1owner".to_string()),
+            ..Default::default()
+        }
+    )
+    .unwrap());
+}
+
+fn job_env() -> JobEnv {
+    JobEnv {
+        warp_account_addr: Some("terra1account".to_string()),
+        job_id: Some(Uint64::new(7)),
+        job_owner: Some("terra1owner".to_string()),
+        funding_account_addr: None,
+        controller_addr: Some("terra1controller".to_string()),
+        execution_count: Some(Uint64::new(3)),
+        job_created_at: Some(Uint64::new(1_000)),
+        last_execution_time: None,
+    }
+}
+
+fn resolve_with_job_env(cond: Condition) -> Result<bool, ContractError> {
+    let deps = mock_dependencies();
+    resolve_cond(deps.as_ref(), mock_env(), cond, &vec![], &job_env())
+}
+
+fn num_env_cond(
+    value: NumEnvValue,
+    op: NumOp<NumValue<Uint256, NumExprOp, IntFnOp>>,
+    right: u64,
+) -> Condition {
+    Condition::Expr(Box::new(Expr::Uint(GenExpr {
+        left: NumValue::Env(value),
+        op,
+        right: NumValue::Simple(Uint256::from(right)),
+    })))
+}
+
+fn string_env_cond(value: StringEnvValue, right: &str) -> Condition {
+    Condition::Expr(Box::new(Expr::String(GenExpr {
+        left: StringValue::Env(value),
+        op: StringOp::Eq,
+        right: StringValue::Simple(right.to_string()),
+    })))
+}
+
+#[test]
+fn test_num_env_values() {
+    assert!(resolve_with_job_env(num_env_cond(NumEnvValue::JobId, NumOp::Eq, 7)).unwrap());
+    assert!(resolve_with_job_env(num_env_cond(NumEnvValue::ExecutionCount, NumOp::Eq, 3)).unwrap());
+    assert!(
+        resolve_with_job_env(num_env_cond(NumEnvValue::JobCreatedAt, NumOp::Lt, 1_001)).unwrap()
+    );
+    assert_eq!(
+        resolve_with_job_env(num_env_cond(NumEnvValue::LastExecutionTime, NumOp::Gt, 0)),
+        Err(ContractError::HydrationError {
+            msg: "Last execution time not found.".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_num_env_values_in_int_and_decimal() {
+    let int = Condition::Expr(Box::new(Expr::Int(GenExpr {
+        left: NumValue::<Int256, NumExprOp, IntFnOp>::Env(NumEnvValue::ExecutionCount),
+        op: NumOp::Eq,
+        right: NumValue::Simple(Int256::from(3)),
+    })));
+    assert!(resolve_with_job_env(int).unwrap());
+
+    let decimal = Condition::Expr(Box::new(Expr::Decimal(GenExpr {
+        left: NumValue::<Decimal256, NumExprOp, DecimalFnOp>::Env(NumEnvValue::JobId),
+        op: NumOp::Eq,
+        right: NumValue::Simple(Decimal256::from_str("7").unwrap()),
+    })));
+    assert!(resolve_with_job_env(decimal).unwrap());
+}
+
+#[test]
+fn test_string_env_values() {
+    assert!(
+        resolve_with_job_env(string_env_cond(StringEnvValue::JobOwner, "terra1owner")).unwrap()
+    );
+    assert!(resolve_with_job_env(string_env_cond(
+        StringEnvValue::ControllerAddr,
+        "terra1controller"
+    ))
+    .unwrap());
+    assert!(resolve_with_job_env(string_env_cond(
+        StringEnvValue::ChainId,
+        &mock_env().block.chain_id
+    ))
+    .unwrap());
+    assert_eq!(
+        resolve_with_job_env(string_env_cond(StringEnvValue::FundingAccountAddr, "")),
+        Err(ContractError::HydrationError {
+            msg: "Funding account addr not found.".to_string()
+        })
+    );
+}
+
+// mock_env block time is 1571797419
+fn last_run_vars(last_run: u64) -> Vec<Variable> {
+    vec![Variable::Static(StaticVariable {
+        kind: VariableKind::Uint,
+        name: "last_run".to_string(),
+        encode: false,
+        value: Some(last_run.to_string()),
+        init_fn: FnValue::Uint(NumValue::Simple(Uint256::from(last_run))),
+        reinitialize: false,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })]
+}
+
+fn since_last_run(op: NumOp<NumValue<Uint256, NumExprOp, IntFnOp>>, days: u64) -> Condition {
+    Condition::Expr(Box::new(Expr::Timestamp(TimeExpr::Num(TimestampExpr {
+        op,
+        value: NumValue::Expr(NumExprValue {
+            left: Box::new(NumValue::Ref("$warp.variable.last_run".to_string())),
+            op: NumExprOp::Add,
+            right: Box::new(NumValue::Fn(NumFnValue {
+                op: IntFnOp::Days,
+                right: Box::new(NumValue::Simple(Uint256::from(days))),
+            })),
+        }),
+    }))))
+}
+
+#[test]
+fn test_timestamp_expr_from_vars() {
+    let two_days_ago = last_run_vars(1571797419 - 2 * 86400);
+
+    assert!(resolve(since_last_run(NumOp::Gte, 1), &two_days_ago).unwrap());
+    assert!(resolve(since_last_run(NumOp::Gte, 2), &two_days_ago).unwrap());
+    assert!(!resolve(since_last_run(NumOp::Gt, 2), &two_days_ago).unwrap());
+    assert!(resolve(since_last_run(NumOp::Lt, 3), &two_days_ago).unwrap());
+
+    let window = Condition::Expr(Box::new(Expr::Timestamp(TimeExpr::Num(TimestampExpr {
+        op: NumOp::Between {
+            upper: NumValue::Fn(NumFnValue {
+                op: IntFnOp::Weeks,
+                right: Box::new(NumValue::Simple(Uint256::from(3000u64))),
+            }),
+        },
+        value: NumValue::Simple(Uint256::from(1571797419u64 - 60)),
+    }))));
+    assert!(resolve(window, &vec![]).unwrap());
+}
+
+#[test]
+fn test_timestamp_expr_json() {
+    let legacy: Condition =
+        from_slice(br#"{"expr":{"timestamp":{"comparator":"1571797000","op":"gt"}}}"#).unwrap();
+    assert_eq!(
+        legacy,
+        Condition::Expr(Box::new(Expr::Timestamp(TimeExpr::Fixed(FixedTimeExpr {
+            comparator: 1571797000u64.into(),
+            op: TimeOp::Gt,
+        }))))
+    );
+    assert!(resolve(legacy, &vec![]).unwrap());
+
+    let cond: Condition = from_slice(
+        br#"{"expr":{"timestamp":{"op":"lte","value":{"fn":{"op":"hours","right":{"simple":"436611"}}}}}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        cond,
+        Condition::Expr(Box::new(Expr::Timestamp(TimeExpr::Num(TimestampExpr {
+            op: NumOp::Lte,
+            value: NumValue::Fn(NumFnValue {
+                op: IntFnOp::Hours,
+                right: Box::new(NumValue::Simple(Uint256::from(436611u64))),
+            }),
+        }))))
+    );
+    assert!(resolve(cond, &vec![]).unwrap());
+}
+
+fn failing_cond() -> Condition {
+    Condition::Expr(Box::new(Expr::Uint(GenExpr {
+        left: NumValue::Ref("$warp.variable.missing".to_string()),
+        op: NumOp::Eq,
+        right: NumValue::Simple(Uint256::zero()),
+    })))
+}
+
+fn bool_cond(value: bool) -> Condition {
+    uint_cond(1, if value { NumOp::Eq } else { NumOp::Neq }, 1)
+}
+
+#[test]
+fn test_and_or_short_circuit() {
+    let missing = Err(ContractError::VariableNotFound {
+        name: "$warp.variable.missing".to_string(),
+    });
+
+    // children after a decisive result are not resolved
+    let or = Condition::Or(vec![Box::new(bool_cond(true)), Box::new(failing_cond())]);
+    assert_eq!(resolve(or, &vec![]), Ok(true));
+    let and = Condition::And(vec![Box::new(bool_cond(false)), Box::new(failing_cond())]);
+    assert_eq!(resolve(and, &vec![]), Ok(false));
+
+    // errors before a decisive result propagate
+    let or = Condition::Or(vec![Box::new(failing_cond()), Box::new(bool_cond(true))]);
+    assert_eq!(resolve(or, &vec![]), missing);
+    let and = Condition::And(vec![Box::new(failing_cond()), Box::new(bool_cond(false))]);
+    assert_eq!(resolve(and, &vec![]), missing);
+}
+
+#[test]
+fn test_try_condition() {
+    let try_cond = |on_error: bool| Condition::Try {
+        cond: Box::new(failing_cond()),
+        on_error,
+    };
+    assert_eq!(resolve(try_cond(false), &vec![]), Ok(false));
+    assert_eq!(resolve(try_cond(true), &vec![]), Ok(true));
+    assert_eq!(
+        resolve(Condition::Not(Box::new(try_cond(false))), &vec![]),
+        Ok(true)
+    );
+    // successful conditions resolve as is
+    assert_eq!(
+        resolve(
+            Condition::Try {
+                cond: Box::new(bool_cond(false)),
+                on_error: true,
+            },
+            &vec![]
+        ),
+        Ok(false)
+    );
+
+    let cond: Condition = from_slice(
+        br#"{"try":{"cond":{"expr":{"uint":{"left":{"ref":"$warp.variable.missing"},"op":"eq","right":{"simple":"0"}}}},"on_error":true}}"#,
+    )
+    .unwrap();
+    assert_eq!(cond, try_cond(true));
+}
@@ -0,0 +1,168 @@
+use crate::contract::query;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_binary, Uint256};
+use resolver::condition::{
+    Condition, Expr, GenExpr, IntFnOp, NumExprOp, NumOp, NumValue, StringOp, StringValue, TimeExpr,
+    TimestampExpr,
+};
+use resolver::variable::{FnValue, StaticVariable, Variable, VariableKind};
+use resolver::{ConditionTrace, ConditionTraceNode, ExprTrace, QueryExplainConditionMsg, QueryMsg};
+
+use super::mock_dependencies;
+
+fn explain(cond: Condition, vars: Vec<Variable>) -> ConditionTrace {
+    let deps = mock_dependencies();
+    let msg = QueryMsg::QueryExplainCondition(QueryExplainConditionMsg {
+        condition: serde_json_wasm::to_string(&cond).unwrap(),
+        vars: serde_json_wasm::to_string(&vars).unwrap(),
+        warp_account_addr: None,
+        job_env: None,
+    });
+
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+fn uint_cond(
+    left: NumValue<Uint256, NumExprOp, IntFnOp>,
+    op: NumOp<NumValue<Uint256, NumExprOp, IntFnOp>>,
+    right: u64,
+) -> Condition {
+    Condition::Expr(Box::new(Expr::Uint(GenExpr {
+        left,
+        op,
+        right: NumValue::Simple(Uint256::from(right)),
+    })))
+}
+
+#[test]
+fn test_explain_expr_operands() {
+    let vars = vec![Variable::Static(StaticVariable {
+        kind: VariableKind::Uint,
+        name: "price".to_string(),
+        encode: false,
+        init_fn: FnValue::Uint(NumValue::Simple(Uint256::from(42u64))),
+        reinitialize: false,
+        value: Some("42".to_string()),
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })];
+    let cond = uint_cond(
+        NumValue::Ref("$warp.variable.price".to_string()),
+        NumOp::Gt,
+        50,
+    );
+
+    assert_eq!(
+        explain(cond, vars),
+        ConditionTrace {
+            node: ConditionTraceNode::Expr(ExprTrace {
+                kind: "uint".to_string(),
+                left: Some("42".to_string()),
+                op: "gt".to_string(),
+                right: Some("50".to_string()),
+                upper: None,
+            }),
+            result: Some(false),
+            error: None,
+        }
+    );
+}
+
+#[test]
+fn test_explain_timestamp_between() {
+    // mock_env block time is 1571797419
+    let cond = Condition::Expr(Box::new(Expr::Timestamp(TimeExpr::Num(TimestampExpr {
+        op: NumOp::Between {
+            upper: NumValue::Simple(Uint256::from(1571797500u64)),
+        },
+        value: NumValue::Simple(Uint256::from(1571797400u64)),
+    }))));
+
+    assert_eq!(
+        explain(cond, vec![]),
+        ConditionTrace {
+            node: ConditionTraceNode::Expr(ExprTrace {
+                kind: "timestamp".to_string(),
+                left: Some("1571797419".to_string()),
+                op: "between".to_string(),
+                right: Some("1571797400".to_string()),
+                upper: Some("1571797500".to_string()),
+            }),
+            result: Some(true),
+            error: None,
+        }
+    );
+}
+
+#[test]
+fn test_explain_evaluates_every_child() {
+    let failing = uint_cond(
+        NumValue::Ref("$warp.variable.missing".to_string()),
+        NumOp::Eq,
+        0,
+    );
+    let passing = Condition::Expr(Box::new(Expr::String(GenExpr {
+        left: StringValue::Simple("warp".to_string()),
+        op: StringOp::StartsWith,
+        right: StringValue::Simple("wa".to_string()),
+    })));
+    let cond = Condition::Or(vec![
+        Box::new(passing),
+        Box::new(Condition::Not(Box::new(failing))),
+    ]);
+
+    let trace = explain(cond, vec![]);
+    assert_eq!(trace.result, Some(true));
+    assert_eq!(trace.error, None);
+
+    let children = match trace.node {
+        ConditionTraceNode::Or(children) => children,
+        node => panic!("unexpected node {:?}", node),
+    };
+    assert_eq!(children[0].result, Some(true));
+
+    // the Or is decided by its first child, but the failing branch is still reported
+    let not = &children[1];
+    assert_eq!(not.result, None);
+    assert!(not.error.as_ref().unwrap().contains("missing"));
+    match &not.node {
+        ConditionTraceNode::Not(inner) => match &inner.node {
+            ConditionTraceNode::Expr(expr) => {
+                assert_eq!(expr.left, None);
+                assert_eq!(expr.right, Some("0".to_string()));
+            }
+            node => panic!("unexpected node {:?}", node),
+        },
+        node => panic!("unexpected node {:?}", node),
+    }
+}
+
+#[test]
+fn test_explain_try_and_results() {
+    let failing = uint_cond(
+        NumValue::Ref("$warp.variable.missing".to_string()),
+        NumOp::Eq,
+        0,
+    );
+    let cond = Condition::And(vec![
+        Box::new(Condition::Try {
+            cond: Box::new(failing.clone()),
+            on_error: true,
+        }),
+        Box::new(failing),
+    ]);
+
+    let trace = explain(cond, vec![]);
+    assert_eq!(trace.result, None);
+    assert!(trace.error.unwrap().contains("missing"));
+
+    match trace.node {
+        ConditionTraceNode::And(children) => {
+            assert_eq!(children[0].result, Some(true));
+            assert_eq!(children[0].error, None);
+            assert_eq!(children[1].result, None);
+        }
+        node => panic!("unexpected node {:?}", node),
+    }
+}
@@ -0,0 +1,246 @@
+use crate::util::variable::{hydrate_vars, vars_valid};
+use controller::job::{ExternalInput, ExternalInputSignDoc};
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{to_vec, Binary, Uint64};
+use k256::ecdsa::signature::Signer;
+use resolver::variable::{ExternalExpr, ExternalVariable, TrustedSigner, Variable, VariableKind};
+use resolver::JobEnv;
+
+const JOB_ID: u64 = 7;
+const CONTROLLER_ADDR: &str = "terra1controller";
+
+fn secp256k1_key() -> k256::ecdsa::SigningKey {
+    k256::ecdsa::SigningKey::from_bytes(&[1u8; 32]).unwrap()
+}
+
+fn ed25519_key() -> ed25519_zebra::SigningKey {
+    ed25519_zebra::SigningKey::from([2u8; 32])
+}
+
+fn secp256k1_signer() -> TrustedSigner {
+    let pubkey = secp256k1_key().verifying_key().to_bytes();
+    TrustedSigner::Secp256k1(Binary::from(pubkey.as_slice()))
+}
+
+fn ed25519_signer() -> TrustedSigner {
+    let pubkey = ed25519_zebra::VerificationKey::from(&ed25519_key());
+    TrustedSigner::Ed25519(Binary::from(<[u8; 32]>::from(pubkey).to_vec()))
+}
+
+fn sign_doc(job_id: u64, value: &str, timestamp: u64) -> Vec<u8> {
+    sign_doc_for(
+        &mock_env().block.chain_id,
+        CONTROLLER_ADDR,
+        job_id,
+        value,
+        timestamp,
+    )
+}
+
+fn sign_doc_for(
+    chain_id: &str,
+    controller_addr: &str,
+    job_id: u64,
+    value: &str,
+    timestamp: u64,
+) -> Vec<u8> {
+    to_vec(&ExternalInputSignDoc {
+        chain_id: chain_id.to_string(),
+        controller_addr: controller_addr.to_string(),
+        job_id: Uint64::new(job_id),
+        name: "price".to_string(),
+        value: value.to_string(),
+        timestamp: Uint64::new(timestamp),
+    })
+    .unwrap()
+}
+
+fn input(value: &str, timestamp: u64, signature: Option<Vec<u8>>) -> ExternalInput {
+    ExternalInput {
+        name: "price".to_string(),
+        input: value.to_string(),
+        signature: signature.map(Binary::from),
+        timestamp: Some(Uint64::new(timestamp)),
+    }
+}
+
+fn external_var(
+    trusted_signers: Option<Vec<TrustedSigner>>,
+    max_age_seconds: Option<u64>,
+) -> Variable {
+    Variable::External(ExternalVariable {
+        kind: VariableKind::Decimal,
+        name: "price".to_string(),
+        encode: false,
+        init_fn: ExternalExpr {
+            url: "https://prices.example".to_string(),
+            method: None,
+            headers: None,
+            body: None,
+            selector: "$.price".to_string(),
+            trusted_signers,
+            max_age_seconds,
+        },
+        reinitialize: true,
+        value: None,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })
+}
+
+fn hydrate(var: Variable, input: ExternalInput) -> Result<Variable, String> {
+    let deps = mock_dependencies();
+    let job_env = JobEnv {
+        job_id: Some(Uint64::new(JOB_ID)),
+        controller_addr: Some(CONTROLLER_ADDR.to_string()),
+        ..Default::default()
+    };
+    hydrate_vars(
+        deps.as_ref(),
+        mock_env(),
+        vec![var],
+        Some(vec![input]),
+        &job_env,
+    )
+    .map(|mut vars| vars.remove(0))
+    .map_err(|e| e.to_string())
+}
+
+fn external_value(var: Variable) -> Option<String> {
+    match var {
+        Variable::External(v) => v.value,
+        _ => panic!("Expected external variable"),
+    }
+}
+
+#[test]
+fn test_signed_external_inputs() {
+    let now = mock_env().block.time.seconds();
+    let signers = Some(vec![secp256k1_signer(), ed25519_signer()]);
+
+    let secp256k1_sig: k256::ecdsa::Signature = secp256k1_key().sign(&sign_doc(JOB_ID, "1.5", now));
+    let var = hydrate(
+        external_var(signers.clone(), Some(60)),
+        input("1.5", now, Some(secp256k1_sig.as_ref().to_vec())),
+    )
+    .unwrap();
+    assert_eq!(external_value(var), Some("1.5".to_string()));
+
+    let ed25519_sig = ed25519_key().sign(&sign_doc(JOB_ID, "2.5", now - 30));
+    let var = hydrate(
+        external_var(signers.clone(), Some(60)),
+        input(
+            "2.5",
+            now - 30,
+            Some(<[u8; 64]>::from(ed25519_sig).to_vec()),
+        ),
+    )
+    .unwrap();
+    assert_eq!(external_value(var), Some("2.5".to_string()));
+
+    // unsigned inputs are still accepted by vars without trusted signers
+    let var = hydrate(external_var(None, None), input("3.5", now, None)).unwrap();
+    assert_eq!(external_value(var), Some("3.5".to_string()));
+}
+
+#[test]
+fn test_rejected_external_inputs() {
+    let now = mock_env().block.time.seconds();
+    let signers = Some(vec![secp256k1_signer()]);
+    let sign = |job_id: u64, value: &str, timestamp: u64| {
+        let sig: k256::ecdsa::Signature = secp256k1_key().sign(&sign_doc(job_id, value, timestamp));
+        Some(sig.as_ref().to_vec())
+    };
+
+    let err = hydrate(external_var(signers.clone(), None), input("1.5", now, None)).unwrap_err();
+    assert!(err.contains("is not signed."));
+
+    // signed value does not match the input
+    let err = hydrate(
+        external_var(signers.clone(), None),
+        input("100", now, sign(JOB_ID, "1.5", now)),
+    )
+    .unwrap_err();
+    assert!(err.contains("not signed by a trusted signer"));
+
+    // signed for another job
+    let err = hydrate(
+        external_var(signers.clone(), None),
+        input("1.5", now, sign(JOB_ID + 1, "1.5", now)),
+    )
+    .unwrap_err();
+    assert!(err.contains("not signed by a trusted signer"));
+
+    // signed by a key that is not trusted
+    let err = hydrate(
+        external_var(Some(vec![ed25519_signer()]), None),
+        input("1.5", now, sign(JOB_ID, "1.5", now)),
+    )
+    .unwrap_err();
+    assert!(err.contains("not signed by a trusted signer"));
+
+    // signed for the same job id on another chain or controller
+    let sign_for = |chain_id: &str, controller_addr: &str| {
+        let sig: k256::ecdsa::Signature =
+            secp256k1_key().sign(&sign_doc_for(chain_id, controller_addr, JOB_ID, "1.5", now));
+        Some(sig.as_ref().to_vec())
+    };
+    let err = hydrate(
+        external_var(signers.clone(), None),
+        input("1.5", now, sign_for("other-chain", CONTROLLER_ADDR)),
+    )
+    .unwrap_err();
+    assert!(err.contains("not signed by a trusted signer"));
+    let chain_id = mock_env().block.chain_id;
+    let err = hydrate(
+        external_var(signers.clone(), None),
+        input("1.5", now, sign_for(&chain_id, "terra1other")),
+    )
+    .unwrap_err();
+    assert!(err.contains("not signed by a trusted signer"));
+
+    let err = hydrate(
+        external_var(signers.clone(), Some(60)),
+        input("1.5", now - 61, sign(JOB_ID, "1.5", now - 61)),
+    )
+    .unwrap_err();
+    assert!(err.contains("is stale"));
+
+    // far future timestamps would otherwise never go stale
+    let err = hydrate(
+        external_var(signers.clone(), Some(60)),
+        input("1.5", now + 61, sign(JOB_ID, "1.5", now + 61)),
+    )
+    .unwrap_err();
+    assert!(err.contains("is signed in the future"));
+    let err = hydrate(
+        external_var(signers.clone(), None),
+        input("1.5", u64::MAX, sign(JOB_ID, "1.5", u64::MAX)),
+    )
+    .unwrap_err();
+    assert!(err.contains("is signed in the future"));
+
+    // small clock skew is tolerated
+    let var = hydrate(
+        external_var(signers, Some(60)),
+        input("1.5", now + 60, sign(JOB_ID, "1.5", now + 60)),
+    )
+    .unwrap();
+    assert_eq!(external_value(var), Some("1.5".to_string()));
+}
+
+#[test]
+fn test_trusted_signers_validation() {
+    assert!(vars_valid(&vec![external_var(
+        Some(vec![secp256k1_signer(), ed25519_signer()]),
+        Some(60)
+    )]));
+    assert!(!vars_valid(&vec![external_var(
+        Some(vec![TrustedSigner::Ed25519(Binary::from(vec![1u8; 33]))]),
+        None
+    )]));
+    assert!(!vars_valid(&vec![external_var(Some(vec![]), None)]));
+    // freshness can only be checked on signed timestamps
+    assert!(!vars_valid(&vec![external_var(None, Some(60))]));
+}
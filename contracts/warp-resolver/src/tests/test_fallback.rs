@@ -0,0 +1,133 @@
+use crate::util::variable::{hydrate_vars, vars_valid};
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{to_binary, QueryRequest, WasmQuery};
+use resolver::variable::{
+    ExternalExpr, ExternalVariable, OnErrorPolicy, QueryExpr, QueryVariable, Variable, VariableKind,
+};
+use resolver::JobEnv;
+use schemars::_serde_json::json;
+
+// The mock querier knows no contracts, so the query always fails
+fn query_var(
+    value: Option<&str>,
+    default: Option<&str>,
+    on_error: Option<OnErrorPolicy>,
+) -> Variable {
+    Variable::Query(QueryVariable {
+        kind: VariableKind::Uint,
+        name: "price".to_string(),
+        encode: false,
+        init_fn: QueryExpr {
+            selector: "$.price".to_string(),
+            query: QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: "terra1oracle".to_string(),
+                msg: to_binary(&json!({"price": {}})).unwrap(),
+            }),
+        },
+        reinitialize: true,
+        value: value.map(String::from),
+        update_fn: None,
+        default: default.map(String::from),
+        on_error,
+    })
+}
+
+fn hydrated_value(var: Variable) -> Result<Option<String>, String> {
+    let deps = mock_dependencies();
+    let vars = hydrate_vars(
+        deps.as_ref(),
+        mock_env(),
+        vec![var],
+        None,
+        &JobEnv::default(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    match &vars[0] {
+        Variable::Query(v) => Ok(v.value.clone()),
+        Variable::External(v) => Ok(v.value.clone()),
+        _ => panic!("Expected query or external variable"),
+    }
+}
+
+#[test]
+fn test_query_var_fallback() {
+    assert_eq!(
+        hydrated_value(query_var(
+            Some("5"),
+            Some("1"),
+            Some(OnErrorPolicy::UseDefault)
+        )),
+        Ok(Some("1".to_string()))
+    );
+    assert_eq!(
+        hydrated_value(query_var(
+            Some("5"),
+            Some("1"),
+            Some(OnErrorPolicy::UseLastValue)
+        )),
+        Ok(Some("5".to_string()))
+    );
+    // uninitialized vars have no last value to use
+    assert_eq!(
+        hydrated_value(query_var(
+            None,
+            Some("1"),
+            Some(OnErrorPolicy::UseLastValue)
+        )),
+        Ok(Some("1".to_string()))
+    );
+    assert!(hydrated_value(query_var(None, None, Some(OnErrorPolicy::UseLastValue))).is_err());
+    assert!(hydrated_value(query_var(Some("5"), Some("1"), Some(OnErrorPolicy::Fail))).is_err());
+    assert!(hydrated_value(query_var(Some("5"), Some("1"), None)).is_err());
+}
+
+#[test]
+fn test_optional_external_var() {
+    let var = Variable::External(ExternalVariable {
+        kind: VariableKind::Decimal,
+        name: "price".to_string(),
+        encode: false,
+        init_fn: ExternalExpr {
+            url: "https://prices.example".to_string(),
+            method: None,
+            headers: None,
+            body: None,
+            selector: "$.price".to_string(),
+            trusted_signers: None,
+            max_age_seconds: None,
+        },
+        reinitialize: true,
+        value: None,
+        update_fn: None,
+        default: Some("1.5".to_string()),
+        on_error: Some(OnErrorPolicy::UseDefault),
+    });
+
+    assert_eq!(hydrated_value(var), Ok(Some("1.5".to_string())));
+}
+
+#[test]
+fn test_fallback_validation() {
+    assert!(vars_valid(&vec![query_var(
+        None,
+        Some("1"),
+        Some(OnErrorPolicy::UseDefault)
+    )]));
+    assert!(vars_valid(&vec![query_var(
+        None,
+        None,
+        Some(OnErrorPolicy::UseLastValue)
+    )]));
+    assert!(!vars_valid(&vec![query_var(
+        None,
+        None,
+        Some(OnErrorPolicy::UseDefault)
+    )]));
+    // defaults must be values of the var kind
+    assert!(!vars_valid(&vec![query_var(
+        None,
+        Some("one"),
+        Some(OnErrorPolicy::UseDefault)
+    )]));
+}
@@ -0,0 +1,155 @@
+use crate::util::condition::resolve_cond;
+use crate::util::history::resolve_history_fn;
+use crate::util::variable::{apply_var_fn, hydrate_vars, vars_valid};
+use controller::job::JobStatus;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::Decimal256;
+use resolver::condition::{
+    Condition, DecimalFnOp, Expr, GenExpr, HistoryFnOp, NumExprOp, NumExprValue, NumHistoryValue,
+    NumOp, NumValue,
+};
+use resolver::variable::{FnValue, HistoryVariable, UpdateFn, Variable, VariableKind};
+use resolver::JobEnv;
+use std::str::FromStr;
+
+use super::mock_dependencies;
+
+fn dec(value: &str) -> Decimal256 {
+    Decimal256::from_str(value).unwrap()
+}
+
+fn decs(values: &[&str]) -> Vec<Decimal256> {
+    values.iter().map(|value| dec(value)).collect()
+}
+
+fn history_var(value: &str, history: &[&str]) -> HistoryVariable {
+    HistoryVariable {
+        kind: VariableKind::Decimal,
+        name: "price".to_string(),
+        encode: false,
+        init_fn: FnValue::Decimal(NumValue::Simple(dec(value))),
+        reinitialize: false,
+        value: Some(value.to_string()),
+        update_fn: None,
+        capacity: 3,
+        history: history.iter().map(|value| value.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_history_fns() {
+    let values = decs(&["2", "4", "4", "4", "5", "5", "7", "9"]);
+
+    assert_eq!(resolve_history_fn(&values, HistoryFnOp::Avg), Ok(dec("5")));
+    assert_eq!(resolve_history_fn(&values, HistoryFnOp::Min), Ok(dec("2")));
+    assert_eq!(resolve_history_fn(&values, HistoryFnOp::Max), Ok(dec("9")));
+    assert_eq!(
+        resolve_history_fn(&values, HistoryFnOp::StdDev),
+        Ok(dec("2"))
+    );
+    assert_eq!(
+        resolve_history_fn(&values, HistoryFnOp::Last(1)),
+        Ok(dec("9"))
+    );
+    assert_eq!(
+        resolve_history_fn(&values, HistoryFnOp::Last(8)),
+        Ok(dec("2"))
+    );
+    assert!(resolve_history_fn(&values, HistoryFnOp::Last(0)).is_err());
+    assert!(resolve_history_fn(&values, HistoryFnOp::Last(9)).is_err());
+
+    // 10 -> 0.5 * 20 + 0.5 * 10 = 15 -> 0.5 * 40 + 0.5 * 15 = 27.5
+    let values = decs(&["10", "20", "40"]);
+    assert_eq!(
+        resolve_history_fn(&values, HistoryFnOp::Ema(dec("0.5"))),
+        Ok(dec("27.5"))
+    );
+    assert!(resolve_history_fn(&values, HistoryFnOp::Ema(dec("0"))).is_err());
+    assert!(resolve_history_fn(&values, HistoryFnOp::Ema(dec("1.5"))).is_err());
+
+    assert!(resolve_history_fn(&[], HistoryFnOp::Avg).is_err());
+}
+
+#[test]
+fn test_apply_var_fn_records_bounded_history() {
+    let deps = mock_dependencies();
+    let mut var = history_var("1", &[]);
+    var.update_fn = Some(UpdateFn {
+        on_success: Some(FnValue::Decimal(NumValue::Expr(NumExprValue {
+            left: Box::new(NumValue::Ref("$warp.variable.price".to_string())),
+            op: NumExprOp::Add,
+            right: Box::new(NumValue::Simple(Decimal256::one())),
+        }))),
+        on_error: None,
+    });
+
+    let mut vars = vec![Variable::History(var)];
+    for _ in 0..4 {
+        let applied = apply_var_fn(
+            deps.as_ref(),
+            mock_env(),
+            vars,
+            JobStatus::Executed,
+            &JobEnv::default(),
+        )
+        .unwrap();
+        vars = serde_json_wasm::from_str(&applied).unwrap();
+    }
+
+    match &vars[0] {
+        Variable::History(v) => {
+            assert_eq!(v.value, Some("5".to_string()));
+            assert_eq!(v.history, vec!["3", "4", "5"]);
+        }
+        var => panic!("unexpected variable {:?}", var),
+    }
+}
+
+#[test]
+fn test_history_condition() {
+    let deps = mock_dependencies();
+    let vars = hydrate_vars(
+        deps.as_ref(),
+        mock_env(),
+        vec![Variable::History(history_var("1.5", &["1", "2", "3"]))],
+        None,
+        &JobEnv::default(),
+    )
+    .unwrap();
+
+    // current price of 1.5 is below the average of 2
+    let cond = Condition::Expr(Box::new(Expr::Decimal(GenExpr {
+        left: NumValue::<Decimal256, NumExprOp, DecimalFnOp>::Ref(
+            "$warp.variable.price".to_string(),
+        ),
+        op: NumOp::Lt,
+        right: NumValue::History(NumHistoryValue {
+            var: "$warp.variable.price".to_string(),
+            op: HistoryFnOp::Avg,
+        }),
+    })));
+
+    assert_eq!(
+        resolve_cond(deps.as_ref(), mock_env(), cond, &vars, &JobEnv::default()),
+        Ok(true)
+    );
+}
+
+#[test]
+fn test_history_vars_valid() {
+    assert!(vars_valid(&vec![Variable::History(history_var(
+        "1",
+        &["1"]
+    ))]));
+
+    let mut var = history_var("1", &[]);
+    var.capacity = 0;
+    assert!(!vars_valid(&vec![Variable::History(var)]));
+
+    let var = history_var("1", &["1", "2", "3", "4"]);
+    assert!(!vars_valid(&vec![Variable::History(var)]));
+
+    let mut var = history_var("1", &[]);
+    var.kind = VariableKind::String;
+    assert!(!vars_valid(&vec![Variable::History(var)]));
+}
@@ -0,0 +1,83 @@
+use cosmwasm_std::{from_slice, to_vec, Uint256};
+use resolver::condition::{Condition, Expr, GenExpr, IntFnOp, NumExprOp, NumOp, NumValue};
+use resolver::int256::Int256;
+use std::str::FromStr;
+
+use super::mock_dependencies;
+use crate::util::condition::resolve_cond;
+use cosmwasm_std::testing::mock_env;
+use resolver::JobEnv;
+
+fn int(s: &str) -> Int256 {
+    Int256::from_str(s).unwrap()
+}
+
+#[test]
+fn test_int256_arithmetic() {
+    // 18 decimals PnL beyond the i128 range
+    let cost = int("-200000000000000000000000000000000000000000");
+    let revenue = int("350000000000000000000000000000000000000000");
+    assert_eq!(
+        revenue.checked_add(cost).unwrap(),
+        int("150000000000000000000000000000000000000000")
+    );
+    assert_eq!(
+        cost.checked_sub(revenue).unwrap(),
+        int("-550000000000000000000000000000000000000000")
+    );
+    assert_eq!(int("-6").checked_mul(int("7")).unwrap(), int("-42"));
+    assert_eq!(int("-7").checked_div(int("2")).unwrap(), int("-3"));
+    assert_eq!(int("-7").checked_rem(int("2")).unwrap(), int("-1"));
+    assert_eq!(int("-2").checked_pow(3).unwrap(), int("-8"));
+    assert_eq!(int("-2").checked_pow(2).unwrap(), int("4"));
+    assert_eq!(-int("5"), int("-5"));
+    assert_eq!(-Int256::zero(), Int256::zero());
+    assert_eq!(int("-0"), Int256::zero());
+
+    let max = Int256::from(Uint256::MAX);
+    assert!(max.checked_add(Int256::one()).is_err());
+    assert!((-max).checked_sub(Int256::one()).is_err());
+    assert!(max.checked_mul(int("-2")).is_err());
+    assert!(Int256::one().checked_div(Int256::zero()).is_err());
+}
+
+#[test]
+fn test_int256_ordering() {
+    let mut values = vec![int("3"), int("-10"), int("0"), int("-2"), int("10")];
+    values.sort();
+    assert_eq!(
+        values,
+        vec![int("-10"), int("-2"), int("0"), int("3"), int("10")]
+    );
+    assert!(Int256::from_str("1.5").is_err());
+    assert!(Int256::from_str("-").is_err());
+}
+
+#[test]
+fn test_int256_json() {
+    assert_eq!(to_vec(&int("-42")).unwrap(), br#""-42""#.to_vec());
+    assert_eq!(from_slice::<Int256>(br#""-42""#).unwrap(), int("-42"));
+
+    // Int jobs created before Int256 encode values as JSON numbers
+    let legacy: Condition =
+        from_slice(br#"{"expr":{"int":{"left":{"simple":-5},"op":"lt","right":{"simple":3}}}}"#)
+            .unwrap();
+    assert_eq!(
+        legacy,
+        Condition::Expr(Box::new(Expr::Int(GenExpr {
+            left: NumValue::<Int256, NumExprOp, IntFnOp>::Simple(int("-5")),
+            op: NumOp::Lt,
+            right: NumValue::Simple(int("3")),
+        })))
+    );
+
+    let deps = mock_dependencies();
+    assert!(resolve_cond(
+        deps.as_ref(),
+        mock_env(),
+        legacy,
+        &vec![],
+        &JobEnv::default()
+    )
+    .unwrap());
+}
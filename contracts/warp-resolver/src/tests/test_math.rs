@@ -0,0 +1,270 @@
+use crate::util::condition::{
+    resolve_num_value_decimal, resolve_num_value_int, resolve_num_value_uint,
+};
+use crate::util::math::{exp, ln, log10, pow, round, to_decimal, to_uint};
+use crate::ContractError;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Decimal256, Uint256};
+use resolver::condition::{
+    DecimalFnOp, IntFnOp, NumClampValue, NumConversion, NumExprOp, NumExprValue, NumFnValue,
+    NumValue, RoundingMode, StringValue,
+};
+use resolver::int256::Int256;
+use resolver::JobEnv;
+use std::str::FromStr;
+
+use super::mock_dependencies;
+
+fn dec(s: &str) -> Decimal256 {
+    Decimal256::from_str(s).unwrap()
+}
+
+// absolute difference within 1e-15
+fn assert_close(actual: Decimal256, expected: &str) {
+    let diff = actual.abs_diff(dec(expected));
+    assert!(
+        diff <= dec("0.000000000000001"),
+        "{} is not close to {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_ln_and_log10() {
+    assert_eq!(ln(Decimal256::one()).unwrap(), Decimal256::zero());
+    assert_close(ln(dec("2")).unwrap(), "0.693147180559945309");
+    assert_close(ln(dec("10")).unwrap(), "2.302585092994045684");
+    assert_close(ln(dec("123456.789")).unwrap(), "11.723646487185880956");
+    assert_close(log10(dec("1000")).unwrap(), "3");
+    assert_close(log10(dec("2")).unwrap(), "0.301029995663981195");
+    assert!(matches!(
+        ln(dec("0.5")),
+        Err(ContractError::ConditionError { .. })
+    ));
+}
+
+#[test]
+fn test_exp() {
+    assert_eq!(exp(Decimal256::zero()).unwrap(), Decimal256::one());
+    assert_close(exp(Decimal256::one()).unwrap(), "2.718281828459045235");
+    assert_close(exp(dec("0.5")).unwrap(), "1.648721270700128146");
+    assert_close(exp(ln(dec("42")).unwrap()).unwrap(), "42");
+    assert!(exp(dec("1000")).is_err());
+}
+
+#[test]
+fn test_pow() {
+    assert_eq!(pow(dec("1.5"), dec("2")).unwrap(), dec("2.25"));
+    assert_eq!(
+        pow(dec("7"), Decimal256::zero()).unwrap(),
+        Decimal256::one()
+    );
+    assert_close(pow(dec("4"), dec("0.5")).unwrap(), "2");
+    assert_close(pow(dec("0.25"), dec("0.5")).unwrap(), "0.5");
+    assert_eq!(
+        pow(Decimal256::zero(), dec("0.5")).unwrap(),
+        Decimal256::zero()
+    );
+    assert!(pow(dec("10"), dec("100")).is_err());
+}
+
+#[test]
+fn test_round() {
+    let cases = [
+        ("1.2345", 2, RoundingMode::Down, "1.23"),
+        ("1.2345", 2, RoundingMode::Up, "1.24"),
+        ("1.235", 2, RoundingMode::HalfUp, "1.24"),
+        ("1.235", 2, RoundingMode::HalfDown, "1.23"),
+        ("1.2351", 2, RoundingMode::HalfDown, "1.24"),
+        ("1.225", 2, RoundingMode::HalfEven, "1.22"),
+        ("1.235", 2, RoundingMode::HalfEven, "1.24"),
+        ("2.5", 0, RoundingMode::HalfEven, "2"),
+        ("1.20", 2, RoundingMode::Up, "1.2"),
+        ("1.5", 18, RoundingMode::Up, "1.5"),
+    ];
+
+    for (value, precision, mode, expected) in cases {
+        assert_eq!(
+            round(dec(value), precision, mode.clone()).unwrap(),
+            dec(expected),
+            "{} {} {:?}",
+            value,
+            precision,
+            mode
+        );
+    }
+}
+
+#[test]
+fn test_num_expr_ops() {
+    let deps = mock_dependencies();
+    let uint = |left: u64, op: NumExprOp, right: u64| {
+        resolve_num_value_uint(
+            deps.as_ref(),
+            mock_env(),
+            NumValue::<Uint256, NumExprOp, IntFnOp>::Expr(NumExprValue {
+                left: Box::new(NumValue::Simple(Uint256::from(left))),
+                op,
+                right: Box::new(NumValue::Simple(Uint256::from(right))),
+            }),
+            &vec![],
+            &JobEnv::default(),
+        )
+    };
+    assert_eq!(uint(3, NumExprOp::Pow, 4).unwrap(), Uint256::from(81u64));
+    assert_eq!(uint(3, NumExprOp::Min, 4).unwrap(), Uint256::from(3u64));
+    assert_eq!(uint(3, NumExprOp::Max, 4).unwrap(), Uint256::from(4u64));
+    assert!(uint(2, NumExprOp::Pow, 256).is_err());
+
+    let int = |left: i128, op: NumExprOp, right: i128| {
+        resolve_num_value_int(
+            deps.as_ref(),
+            mock_env(),
+            NumValue::<Int256, NumExprOp, IntFnOp>::Expr(NumExprValue {
+                left: Box::new(NumValue::Simple(left.into())),
+                op,
+                right: Box::new(NumValue::Simple(right.into())),
+            }),
+            &vec![],
+            &JobEnv::default(),
+        )
+    };
+    assert_eq!(int(-2, NumExprOp::Pow, 3).unwrap(), Int256::from(-8));
+    assert_eq!(int(-2, NumExprOp::Min, 3).unwrap(), Int256::from(-2));
+    assert!(int(2, NumExprOp::Pow, -1).is_err());
+}
+
+#[test]
+fn test_clamp_percentage_of_balance() {
+    // 2% of balance, at most 1000, at least 10
+    let clamp = |balance: &str| {
+        let deps = mock_dependencies();
+        resolve_num_value_decimal(
+            deps.as_ref(),
+            mock_env(),
+            NumValue::<Decimal256, NumExprOp, DecimalFnOp>::Clamp(NumClampValue {
+                value: Box::new(NumValue::Fn(NumFnValue {
+                    op: DecimalFnOp::Round {
+                        precision: 0,
+                        mode: RoundingMode::Down,
+                    },
+                    right: Box::new(NumValue::Expr(NumExprValue {
+                        left: Box::new(NumValue::Simple(dec(balance))),
+                        op: NumExprOp::Mul,
+                        right: Box::new(NumValue::Simple(dec("0.02"))),
+                    })),
+                })),
+                min: Box::new(NumValue::Simple(dec("10"))),
+                max: Box::new(NumValue::Simple(dec("1000"))),
+            }),
+            &vec![],
+            &JobEnv::default(),
+        )
+    };
+
+    assert_eq!(clamp("100").unwrap(), dec("10"));
+    assert_eq!(clamp("12345").unwrap(), dec("246"));
+    assert_eq!(clamp("1000000").unwrap(), dec("1000"));
+
+    let deps = mock_dependencies();
+    let inverted = resolve_num_value_int(
+        deps.as_ref(),
+        mock_env(),
+        NumValue::<Int256, NumExprOp, IntFnOp>::Clamp(NumClampValue {
+            value: Box::new(NumValue::Simple(Int256::from(5))),
+            min: Box::new(NumValue::Simple(Int256::from(10))),
+            max: Box::new(NumValue::Simple(Int256::from(1))),
+        }),
+        &vec![],
+        &JobEnv::default(),
+    );
+    assert_eq!(
+        inverted,
+        Err(ContractError::ConditionError {
+            msg: "Clamp min 10 is greater than max 1.".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_scale_conversions() {
+    assert_eq!(
+        to_decimal(Uint256::from(1_500_000u64), 6).unwrap(),
+        dec("1.5")
+    );
+    assert_eq!(
+        to_decimal(Uint256::from(1u64), 24).unwrap(),
+        Decimal256::zero()
+    );
+    assert_eq!(
+        to_uint(dec("1.2345678"), 6, RoundingMode::Down).unwrap(),
+        Uint256::from(1_234_567u64)
+    );
+    assert_eq!(
+        to_uint(dec("1.2345678"), 6, RoundingMode::HalfUp).unwrap(),
+        Uint256::from(1_234_568u64)
+    );
+    assert_eq!(
+        to_uint(dec("2"), 18, RoundingMode::Down).unwrap(),
+        Uint256::from(2_000_000_000_000_000_000u128)
+    );
+    assert!(to_uint(dec("2"), 100, RoundingMode::Down).is_err());
+}
+
+#[test]
+fn test_num_value_conversions() {
+    let deps = mock_dependencies();
+
+    // 6 decimals uint balance to an 18 decimals amount, through a decimal price
+    let amount = resolve_num_value_uint(
+        deps.as_ref(),
+        mock_env(),
+        NumValue::Convert(Box::new(NumConversion::ToUint {
+            value: NumValue::Expr(NumExprValue {
+                left: Box::new(NumValue::Convert(Box::new(NumConversion::ToDecimal {
+                    value: NumValue::Simple(Uint256::from(2_500_000u64)),
+                    scale: 6,
+                }))),
+                op: NumExprOp::Mul,
+                right: Box::new(NumValue::Simple(dec("0.5"))),
+            }),
+            scale: 18,
+            rounding: RoundingMode::Down,
+        })),
+        &vec![],
+        &JobEnv::default(),
+    )
+    .unwrap();
+    assert_eq!(amount, Uint256::from(1_250_000_000_000_000_000u128));
+
+    let parsed = resolve_num_value_decimal(
+        deps.as_ref(),
+        mock_env(),
+        NumValue::Convert(Box::new(NumConversion::ParseString(StringValue::Simple(
+            "0.25".to_string(),
+        )))),
+        &vec![],
+        &JobEnv::default(),
+    )
+    .unwrap();
+    assert_eq!(parsed, dec("0.25"));
+
+    let mismatched = resolve_num_value_decimal(
+        deps.as_ref(),
+        mock_env(),
+        NumValue::Convert(Box::new(NumConversion::ToUint {
+            value: NumValue::Simple(dec("1")),
+            scale: 6,
+            rounding: RoundingMode::Down,
+        })),
+        &vec![],
+        &JobEnv::default(),
+    );
+    assert_eq!(
+        mismatched,
+        Err(ContractError::ConditionError {
+            msg: "Decimal resolve ToUint.".to_string()
+        })
+    );
+}
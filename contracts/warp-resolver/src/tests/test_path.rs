@@ -0,0 +1,237 @@
+use crate::util::condition::{resolve_query_expr_string, resolve_query_expr_uint};
+use crate::util::path::resolve_path;
+use crate::ContractError;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{to_binary, QueryRequest, Uint256, WasmQuery};
+use json_codec_wasm::ast::Ref;
+use json_codec_wasm::{Decoder, Json};
+use resolver::variable::QueryExpr;
+use schemars::_serde_json::json;
+
+use super::mock_dependencies;
+
+const DOC: &str = r#"{
+    "pools": [
+        {
+            "id": 1,
+            "assets": [
+                { "denom": "uluna", "amount": "1000" },
+                { "denom": "uusdc", "amount": "2000" }
+            ]
+        },
+        {
+            "id": 2,
+            "assets": [
+                { "denom": "uluna", "amount": "3000" },
+                { "denom": "uatom", "amount": "4000", "native": true }
+            ]
+        }
+    ],
+    "config.v2": { "owner": "terra1owner", "fee": "-0.5" },
+    "with'quote": "quoted",
+    "balances": { "uluna": "10", "uatom": "20" },
+    "empty": [],
+    "nothing": null,
+    "negative": -5
+}"#;
+
+fn doc() -> Json {
+    Decoder::default(DOC.chars()).decode().unwrap()
+}
+
+fn resolve(path: &str) -> Result<Option<Json>, ContractError> {
+    let doc = doc();
+    let resolved = resolve_path(Ref::new(&doc), path.to_string())?;
+    Ok(resolved.value().cloned())
+}
+
+fn resolve_string(path: &str) -> String {
+    match resolve(path).unwrap() {
+        Some(Json::String(s)) => s,
+        other => panic!("{} resolved to {:?}", path, other),
+    }
+}
+
+fn assert_selector_error(path: &str) {
+    match resolve(path) {
+        Err(ContractError::SelectorError { .. }) => {}
+        other => panic!("{} resolved to {:?}", path, other),
+    }
+}
+
+#[test]
+fn test_path_root() {
+    assert_eq!(resolve("$").unwrap(), Some(doc()));
+    assert_eq!(resolve("").unwrap(), Some(doc()));
+}
+
+#[test]
+fn test_path_fields() {
+    assert_eq!(resolve("$.negative").unwrap(), Some(Json::I128(-5)));
+    assert_eq!(resolve("$.nothing").unwrap(), Some(Json::Null));
+    assert_eq!(resolve_string("$.balances.uluna"), "10");
+    // relative paths without the root
+    assert_eq!(resolve_string("balances.uatom"), "20");
+}
+
+#[test]
+fn test_path_missing_field() {
+    assert_eq!(resolve("$.missing").unwrap(), None);
+    assert_eq!(resolve("$.missing.nested[0]").unwrap(), None);
+    assert_eq!(resolve("$.balances.uluna.nested").unwrap(), None);
+}
+
+#[test]
+fn test_path_chained_indexes() {
+    assert_eq!(resolve_string("$.pools[0].assets[1].amount"), "2000");
+    assert_eq!(resolve_string("$.pools[1].assets[0].amount"), "3000");
+    assert_eq!(resolve_string("$.pools[1]['assets'][1]['denom']"), "uatom");
+    assert_eq!(resolve("$.pools[1].id").unwrap(), Some(Json::I128(2)));
+}
+
+#[test]
+fn test_path_negative_indexes() {
+    assert_eq!(resolve_string("$.pools[-1].assets[-1].denom"), "uatom");
+    assert_eq!(resolve_string("$.pools[-2].assets[-2].denom"), "uluna");
+}
+
+#[test]
+fn test_path_index_out_of_range() {
+    assert_eq!(resolve("$.pools[2]").unwrap(), None);
+    assert_eq!(resolve("$.pools[-3]").unwrap(), None);
+    assert_eq!(resolve("$.empty[0]").unwrap(), None);
+    assert_eq!(resolve("$.balances[0]").unwrap(), None);
+}
+
+#[test]
+fn test_path_quoted_keys() {
+    assert_eq!(resolve_string("$['config.v2'].owner"), "terra1owner");
+    assert_eq!(resolve_string("$[\"config.v2\"][\"owner\"]"), "terra1owner");
+    assert_eq!(resolve_string("$['with\\'quote']"), "quoted");
+    assert_eq!(resolve_string("$[ 'balances' ]['uluna']"), "10");
+}
+
+#[test]
+fn test_path_wildcards() {
+    // a single match resolves to that node
+    assert_eq!(
+        resolve_string("$['config.v2'][?(@ == 'terra1owner')]"),
+        "terra1owner"
+    );
+    assert_eq!(
+        resolve_string("$.pools[*].assets[?(@.native)].denom"),
+        "uatom"
+    );
+
+    // several matches are ambiguous
+    assert_selector_error("$.pools[*]");
+    assert_selector_error("$.balances.*");
+    assert_selector_error("$.pools[*].assets[*].amount");
+
+    // no match
+    assert_selector_error("$.empty[*]");
+    assert_selector_error("$.negative.*");
+    assert_selector_error("$['config.v2'].*[?(@ == 'terra1owner')]");
+}
+
+#[test]
+fn test_path_filters() {
+    assert_eq!(
+        resolve_string("$.pools[0].assets[?(@.denom=='uluna')].amount"),
+        "1000"
+    );
+    assert_eq!(
+        resolve_string("$.pools[1].assets[?(@.denom == \"uatom\")].amount"),
+        "4000"
+    );
+    assert_eq!(
+        resolve_string("$.pools[?(@.id == 2)].assets[?(@.denom != 'uluna')].amount"),
+        "4000"
+    );
+    assert_eq!(
+        resolve_string("$.pools[*].assets[?(@.amount > 3000)].denom"),
+        "uatom"
+    );
+    assert_eq!(
+        resolve_string("$.pools[*].assets[?(@.amount <= 1000)].denom"),
+        "uluna"
+    );
+    assert_eq!(
+        resolve_string("$.pools[?(@.assets[-1].denom == 'uusdc')].assets[0].amount"),
+        "1000"
+    );
+    assert_eq!(
+        resolve_string("$.pools[*].assets[?(@.native == true)].amount"),
+        "4000"
+    );
+    assert_eq!(resolve_string("$.balances[?(@ >= 20)]"), "20");
+    assert_eq!(resolve_string("$[?(@.fee < 0)].owner"), "terra1owner");
+    assert_eq!(resolve_string("$[?(@.fee == -0.50)].owner"), "terra1owner");
+    assert_eq!(resolve("$[?(@ == null)]").unwrap(), Some(Json::Null));
+}
+
+#[test]
+fn test_path_filter_no_match() {
+    assert_selector_error("$.pools[0].assets[?(@.denom == 'uosmo')]");
+    assert_selector_error("$.pools[*].assets[?(@.amount > 'abc')]");
+    assert_selector_error("$.pools[*].assets[?(@.missing)]");
+}
+
+#[test]
+fn test_path_invalid_selectors() {
+    assert_selector_error("$.");
+    assert_selector_error("$..pools");
+    assert_selector_error("$.pools[");
+    assert_selector_error("$.pools[0");
+    assert_selector_error("$.pools[a]");
+    assert_selector_error("$['pools");
+    assert_selector_error("$.pools[?(@.id == )]");
+    assert_selector_error("$.pools[?(@.id = 1)]");
+    assert_selector_error("$.pools[?(@.id == 1]");
+    assert_selector_error("$.pools[?(id == 1)]");
+    assert_selector_error("$.pools[?(@.id == maybe)]");
+    assert_selector_error("$pools");
+}
+
+fn pool_query_expr(selector: &str) -> QueryExpr {
+    QueryExpr {
+        selector: selector.to_string(),
+        query: QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: "mock_pool_contract_addr".to_string(),
+            msg: to_binary(&json!({ "pools": {} })).unwrap(),
+        }),
+    }
+}
+
+#[test]
+fn test_resolve_query_expr_with_selector() {
+    let deps = mock_dependencies();
+
+    assert_eq!(
+        resolve_query_expr_uint(
+            deps.as_ref(),
+            mock_env(),
+            pool_query_expr("$.pools[0].assets[1].amount")
+        )
+        .unwrap(),
+        Uint256::from(2000u64)
+    );
+    assert_eq!(
+        resolve_query_expr_uint(
+            deps.as_ref(),
+            mock_env(),
+            pool_query_expr("$.pools[?(@.id == 2)].assets[?(@.denom == 'uluna')].amount")
+        )
+        .unwrap(),
+        Uint256::from(3000u64)
+    );
+    assert_eq!(
+        resolve_query_expr_string(
+            deps.as_ref(),
+            mock_env(),
+            pool_query_expr("$.pools[-1].assets[-1].denom")
+        )
+        .unwrap(),
+        "uatom"
+    );
+}
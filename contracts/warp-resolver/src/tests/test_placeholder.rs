@@ -0,0 +1,195 @@
+use crate::util::variable::{hydrate_msgs, msgs_valid};
+use controller::account::WarpMsg;
+use cosmwasm_std::{from_slice, BankMsg, Binary, Coin, CosmosMsg, Uint128, WasmMsg};
+use resolver::condition::StringValue;
+use resolver::variable::{FnValue, StaticVariable, Variable, VariableKind};
+use schemars::_serde_json::{json, Value};
+
+fn static_var(name: &str, kind: VariableKind, value: &str) -> Variable {
+    Variable::Static(StaticVariable {
+        kind,
+        name: name.to_string(),
+        encode: false,
+        init_fn: FnValue::String(StringValue::Simple(value.to_string())),
+        reinitialize: false,
+        value: Some(value.to_string()),
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })
+}
+
+fn vars() -> Vec<Variable> {
+    vec![
+        static_var("recipient", VariableKind::String, "terra1recipient"),
+        static_var("amount", VariableKind::Uint, "1000"),
+        static_var("memo", VariableKind::String, "say \"hi\""),
+        static_var("routes", VariableKind::Json, r#"[{"pool":1},{"pool":2}]"#),
+    ]
+}
+
+fn execute_msg(inner: Value) -> String {
+    json!([{
+        "wasm": {
+            "execute": {
+                "contract_addr": "terra1token",
+                "msg": base64::encode(inner.to_string()),
+                "funds": []
+            }
+        }
+    }])
+    .to_string()
+}
+
+#[test]
+fn test_hydrate_msgs_in_base64_msg() {
+    let msgs = execute_msg(json!({
+        "transfer": {
+            "recipient": "$warp.variable.recipient",
+            "amount": "$warp.variable.amount",
+            "memo": "$warp.variable.memo",
+            "routes": "$warp.variable.routes"
+        }
+    }));
+
+    let hydrated_msgs = hydrate_msgs(msgs, vars()).unwrap();
+
+    match &hydrated_msgs[0] {
+        WarpMsg::Generic(CosmosMsg::Wasm(WasmMsg::Execute { msg, .. })) => {
+            let inner: Value = from_slice(msg.as_slice()).unwrap();
+            assert_eq!(
+                inner,
+                json!({
+                    "transfer": {
+                        "recipient": "terra1recipient",
+                        "amount": "1000",
+                        "memo": "say \"hi\"",
+                        "routes": [{"pool": 1}, {"pool": 2}]
+                    }
+                })
+            );
+        }
+        _ => panic!("Expected wasm execute msg"),
+    }
+}
+
+#[test]
+fn test_hydrate_msgs_nested_placeholders() {
+    let msgs = json!([{
+        "bank": {
+            "send": {
+                "to_address": "$warp.variable.recipient",
+                "amount": [{"denom": "uluna", "amount": "$warp.variable.amount"}]
+            }
+        }
+    }])
+    .to_string();
+
+    assert_eq!(
+        hydrate_msgs(msgs, vars()).unwrap(),
+        vec![WarpMsg::Generic(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1recipient".to_string(),
+            amount: vec![Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(1000),
+            }],
+        }))]
+    );
+
+    // base64 values without placeholders are kept as they are
+    let inner = Binary::from(br#"{"b":1,  "a":2}"#.to_vec());
+    let msgs = execute_msg(json!({})).replace(&base64::encode("{}"), &inner.to_base64());
+    match &hydrate_msgs(msgs, vars()).unwrap()[0] {
+        WarpMsg::Generic(CosmosMsg::Wasm(WasmMsg::Execute { msg, .. })) => {
+            assert_eq!(msg, &inner)
+        }
+        _ => panic!("Expected wasm execute msg"),
+    }
+}
+
+#[test]
+fn test_hydrate_msgs_errors() {
+    let msgs = execute_msg(json!({"transfer": {"recipient": "$warp.variable.unknown"}}));
+    let err = hydrate_msgs(msgs, vars()).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Unknown variable unknown at $[0].wasm.execute.msg(base64).transfer.recipient."));
+
+    let msgs = json!([{"bank": {"burn": {"amount": "$warp.variable.amount"}}}]).to_string();
+    let err = hydrate_msgs(msgs, vars()).unwrap_err();
+    assert!(err.to_string().contains("Invalid msg at $[0]"));
+
+    let err = hydrate_msgs("[{".to_string(), vars()).unwrap_err();
+    assert!(err.to_string().contains("Msgs are not valid JSON."));
+}
+
+#[test]
+fn test_hydrate_msgs_int_beyond_i128() {
+    let large = "-340282366920938463463374607431768211456";
+    let vars = vec![
+        static_var("small", VariableKind::Int, "-5"),
+        static_var("large_positive", VariableKind::Int, &u128::MAX.to_string()),
+        static_var("large", VariableKind::Int, large),
+        static_var(
+            "huge",
+            VariableKind::Int,
+            "1000000000000000000000000000000000000000000",
+        ),
+    ];
+    let msgs = execute_msg(json!({
+        "swap": {
+            "small": "$warp.variable.small",
+            "large_positive": "$warp.variable.large_positive",
+            "large": "$warp.variable.large",
+            "huge": "$warp.variable.huge"
+        }
+    }));
+
+    match &hydrate_msgs(msgs, vars).unwrap()[0] {
+        WarpMsg::Generic(CosmosMsg::Wasm(WasmMsg::Execute { msg, .. })) => {
+            // u128::MAX does not fit the test's JSON parser, so check the raw msg
+            let inner = String::from_utf8(msg.to_vec()).unwrap();
+            assert!(inner.contains(r#""small":-5"#));
+            assert!(inner.contains(&format!(r#""large_positive":{}"#, u128::MAX)));
+            assert!(inner.contains(&format!(r#""large":"{}""#, large)));
+            assert!(inner.contains(r#""huge":"1000000000000000000000000000000000000000000""#));
+        }
+        _ => panic!("Expected wasm execute msg"),
+    }
+
+    let vars = vec![static_var("large", VariableKind::Int, "1.5")];
+    let msgs = execute_msg(json!({"swap": {"large": "$warp.variable.large"}}));
+    let err = hydrate_msgs(msgs, vars).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Variable large value is not a valid Int."));
+}
+
+#[test]
+fn test_msgs_valid() {
+    let msgs = json!([{
+        "bank": {
+            "send": {
+                "to_address": "$warp.variable.recipient",
+                "amount": [{"denom": "uluna", "amount": "$warp.variable.amount"}]
+            }
+        }
+    }])
+    .to_string();
+    assert_eq!(msgs_valid(&msgs, &vars()), Ok(true));
+
+    // placeholders in base64 msgs are substituted like in hydrate_msgs
+    let msgs = execute_msg(json!({"transfer": {"routes": "$warp.variable.routes"}}));
+    assert_eq!(msgs_valid(&msgs, &vars()), Ok(true));
+    let msgs = execute_msg(json!({"transfer": {"recipient": "$warp.variable.unknown"}}));
+    assert!(msgs_valid(&msgs, &vars())
+        .unwrap_err()
+        .to_string()
+        .contains("Unknown variable unknown at $[0].wasm.execute.msg(base64).transfer.recipient."));
+
+    let msgs = json!([{"bank": {"burn": {"amount": "$warp.variable.amount"}}}]).to_string();
+    assert!(msgs_valid(&msgs, &vars())
+        .unwrap_err()
+        .to_string()
+        .contains("Invalid msg at $[0]"));
+}
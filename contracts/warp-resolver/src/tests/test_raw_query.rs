@@ -0,0 +1,169 @@
+use crate::util::raw::storage_key;
+use crate::util::variable::{hydrate_vars, vars_valid};
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{
+    from_slice, to_binary, Addr, Binary, ContractResult, Deps, Querier, QuerierResult,
+    QuerierWrapper, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cw_storage_plus::{Item, Map};
+use resolver::condition::StringValue;
+use resolver::variable::{
+    FnValue, OnErrorPolicy, RawKey, RawQueryExpr, RawQueryVariable, StaticVariable, Variable,
+    VariableKind,
+};
+use resolver::JobEnv;
+use schemars::_serde_json::json;
+
+// Answers raw queries of the pair contract for the balance of terra1owner only
+struct RawQuerier {
+    key: Vec<u8>,
+}
+
+impl Querier for RawQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        match from_slice(bin_request) {
+            Ok(QueryRequest::<String>::Wasm(WasmQuery::Raw { contract_addr, key }))
+                if contract_addr == "terra1pair" =>
+            {
+                let value = match key.as_slice() == self.key.as_slice() {
+                    true => to_binary(&json!({"amount": "1000", "denom": "uluna"})).unwrap(),
+                    false => Binary::default(),
+                };
+                SystemResult::Ok(ContractResult::Ok(value))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "raw".to_string(),
+            }),
+        }
+    }
+}
+
+fn raw_var(keys: Vec<RawKey>) -> Variable {
+    Variable::RawQuery(RawQueryVariable {
+        kind: VariableKind::Uint,
+        name: "balance".to_string(),
+        encode: false,
+        init_fn: RawQueryExpr {
+            contract_addr: "terra1pair".to_string(),
+            namespace: "balances".to_string(),
+            keys,
+            selector: "$.amount".to_string(),
+        },
+        reinitialize: false,
+        value: None,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })
+}
+
+fn owner_var() -> Variable {
+    Variable::Static(StaticVariable {
+        kind: VariableKind::String,
+        name: "owner".to_string(),
+        encode: false,
+        init_fn: FnValue::String(StringValue::Simple("terra1owner".to_string())),
+        reinitialize: false,
+        value: None,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })
+}
+
+#[test]
+fn test_storage_key_matches_cw_storage_plus() {
+    let map: Map<(&Addr, u64), Uint128> = Map::new("balances");
+    let key = map.key((&Addr::unchecked("terra1owner"), 7));
+    assert_eq!(
+        storage_key(
+            b"balances",
+            &[b"terra1owner".to_vec(), 7u64.to_be_bytes().to_vec()]
+        )
+        .as_slice(),
+        &*key
+    );
+
+    let item: Item<Uint128> = Item::new("config");
+    assert_eq!(storage_key(b"config", &[]).as_slice(), item.as_slice());
+}
+
+#[test]
+fn test_hydrate_raw_query_var() {
+    let map: Map<&Addr, Uint128> = Map::new("balances");
+    let querier = RawQuerier {
+        key: map.key(&Addr::unchecked("terra1owner")).to_vec(),
+    };
+    let storage = MockStorage::new();
+    let api = MockApi::default();
+    let deps = Deps {
+        storage: &storage,
+        api: &api,
+        querier: QuerierWrapper::new(&querier),
+    };
+
+    let vars = vec![
+        owner_var(),
+        raw_var(vec![RawKey::Addr("$warp.variable.owner".to_string())]),
+    ];
+    let hydrated_vars = hydrate_vars(deps, mock_env(), vars, None, &JobEnv::default()).unwrap();
+
+    match &hydrated_vars[1] {
+        Variable::RawQuery(v) => {
+            assert_eq!(v.value, Some("1000".to_string()));
+            // the reference stays in the init_fn for later executions
+            assert_eq!(
+                v.init_fn.keys,
+                vec![RawKey::Addr("$warp.variable.owner".to_string())]
+            );
+        }
+        _ => panic!("Expected raw query variable"),
+    }
+
+    // keys missing from the contract storage fail hydration
+    let vars = vec![raw_var(vec![RawKey::Addr("terra1other".to_string())])];
+    let err = hydrate_vars(deps, mock_env(), vars, None, &JobEnv::default()).unwrap_err();
+    assert!(err.to_string().contains("not found"));
+
+    // unless the on_error policy falls back to the default
+    let vars = vec![
+        match raw_var(vec![RawKey::Addr("terra1other".to_string())]) {
+            Variable::RawQuery(v) => Variable::RawQuery(RawQueryVariable {
+                default: Some("0".to_string()),
+                on_error: Some(OnErrorPolicy::UseDefault),
+                ..v
+            }),
+            _ => unreachable!(),
+        },
+    ];
+    let hydrated_vars = hydrate_vars(deps, mock_env(), vars, None, &JobEnv::default()).unwrap();
+    match &hydrated_vars[0] {
+        Variable::RawQuery(v) => assert_eq!(v.value, Some("0".to_string())),
+        _ => panic!("Expected raw query variable"),
+    }
+}
+
+#[test]
+fn test_raw_query_var_validation() {
+    assert!(vars_valid(&vec![raw_var(vec![RawKey::U64(
+        "7".to_string()
+    )])]));
+    assert!(vars_valid(&vec![raw_var(vec![RawKey::U64(
+        "$warp.variable.id".to_string()
+    )])]));
+    assert!(!vars_valid(&vec![raw_var(vec![RawKey::U64(
+        "seven".to_string()
+    )])]));
+
+    // defaults must be valid values of the var
+    let with_default = |default: &str| match raw_var(vec![]) {
+        Variable::RawQuery(v) => Variable::RawQuery(RawQueryVariable {
+            default: Some(default.to_string()),
+            on_error: Some(OnErrorPolicy::UseDefault),
+            ..v
+        }),
+        _ => unreachable!(),
+    };
+    assert!(vars_valid(&vec![with_default("0")]));
+    assert!(!vars_valid(&vec![with_default("zero")]));
+}
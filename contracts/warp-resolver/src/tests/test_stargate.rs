@@ -0,0 +1,175 @@
+use crate::util::stargate::{decode_stargate_response, validate_stargate_vars};
+use crate::util::variable::hydrate_vars;
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{
+    from_slice, Binary, ContractResult, Deps, Querier, QuerierResult, QuerierWrapper, QueryRequest,
+    SystemError, SystemResult,
+};
+use json_codec_wasm::ast::Ref;
+use resolver::variable::{QueryExpr, QueryVariable, Variable, VariableKind};
+use resolver::JobEnv;
+
+fn varint(mut value: u64) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn varint_field(number: u64, value: u64) -> Vec<u8> {
+    [varint(number << 3), varint(value)].concat()
+}
+
+fn len_field(number: u64, value: &[u8]) -> Vec<u8> {
+    [
+        varint(number << 3 | 2),
+        varint(value.len() as u64),
+        value.to_vec(),
+    ]
+    .concat()
+}
+
+fn coin(denom: &str, amount: &str) -> Vec<u8> {
+    [
+        len_field(1, denom.as_bytes()),
+        len_field(2, amount.as_bytes()),
+    ]
+    .concat()
+}
+
+struct StargateQuerier;
+
+impl Querier for StargateQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        match from_slice(bin_request) {
+            Ok(QueryRequest::<String>::Stargate { path, .. })
+                if path == "/cosmos.bank.v1beta1.Query/Balance" =>
+            {
+                let response = len_field(1, &coin("uluna", "1000"));
+                SystemResult::Ok(ContractResult::Ok(Binary::from(response)))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "stargate".to_string(),
+            }),
+        }
+    }
+}
+
+fn stargate_var(path: &str, selector: &str) -> Variable {
+    Variable::Query(QueryVariable {
+        kind: VariableKind::Uint,
+        name: "balance".to_string(),
+        encode: false,
+        init_fn: QueryExpr {
+            selector: selector.to_string(),
+            query: QueryRequest::Stargate {
+                path: path.to_string(),
+                data: Binary::from(len_field(1, b"terra1owner")),
+            },
+        },
+        reinitialize: false,
+        value: None,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })
+}
+
+#[test]
+fn test_hydrate_stargate_var() {
+    let querier = StargateQuerier;
+    let storage = MockStorage::new();
+    let api = MockApi::default();
+    let deps = Deps {
+        storage: &storage,
+        api: &api,
+        querier: QuerierWrapper::new(&querier),
+    };
+
+    let vars = vec![stargate_var(
+        "/cosmos.bank.v1beta1.Query/Balance",
+        "$.balance.amount",
+    )];
+    let hydrated = hydrate_vars(deps, mock_env(), vars, None, &JobEnv::default()).unwrap();
+
+    match &hydrated[0] {
+        Variable::Query(v) => assert_eq!(v.value, Some("1000".to_string())),
+        var => panic!("unexpected variable {:?}", var),
+    }
+}
+
+#[test]
+fn test_decode_validator() {
+    let validator = [
+        len_field(1, b"terravaloper1"),
+        varint_field(4, 3),
+        len_field(5, b"2500"),
+        len_field(6, b"1500000000000000000"),
+        len_field(7, &len_field(1, b"warp")),
+    ]
+    .concat();
+    let response = [len_field(1, &validator), varint_field(99, 7)].concat();
+
+    let decoded =
+        decode_stargate_response("/cosmos.staking.v1beta1.Query/Validator", &response).unwrap();
+    let validator = Ref::new(&decoded).get("validator");
+
+    assert_eq!(
+        validator.get("operator_address").string(),
+        Some("terravaloper1")
+    );
+    assert_eq!(validator.get("status").string(), Some("BOND_STATUS_BONDED"));
+    assert_eq!(validator.get("tokens").string(), Some("2500"));
+    assert_eq!(validator.get("delegator_shares").string(), Some("1.5"));
+    assert_eq!(
+        validator.get("description").get("moniker").string(),
+        Some("warp")
+    );
+    // missing fields decode to their defaults
+    assert_eq!(validator.get("jailed").bool(), Some(false));
+    assert_eq!(validator.get("unbonding_height").string(), Some("0"));
+}
+
+#[test]
+fn test_decode_repeated_and_truncated() {
+    let response = [
+        len_field(1, &coin("uluna", "1")),
+        len_field(1, &coin("uusd", "2")),
+        len_field(2, &varint_field(2, 2)),
+    ]
+    .concat();
+
+    let decoded =
+        decode_stargate_response("/cosmos.bank.v1beta1.Query/AllBalances", &response).unwrap();
+    let balances = Ref::new(&decoded).get("balances");
+    assert_eq!(balances.slice().map(|balances| balances.len()), Some(2));
+    assert_eq!(balances.at(1).get("denom").string(), Some("uusd"));
+    assert_eq!(
+        Ref::new(&decoded).get("pagination").get("total").string(),
+        Some("2")
+    );
+
+    assert!(
+        decode_stargate_response("/cosmos.bank.v1beta1.Query/AllBalances", &response[..5]).is_err()
+    );
+}
+
+#[test]
+fn test_validate_stargate_vars() {
+    assert!(validate_stargate_vars(&[stargate_var(
+        "/cosmos.bank.v1beta1.Query/Balance",
+        "$.balance.amount"
+    )])
+    .is_ok());
+    assert!(validate_stargate_vars(&[stargate_var(
+        "/cosmos.gov.v1beta1.Query/Proposal",
+        "$.proposal"
+    )])
+    .is_err());
+}
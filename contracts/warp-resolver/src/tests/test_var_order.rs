@@ -0,0 +1,144 @@
+use crate::contract::query;
+use crate::util::variable::{hydrate_vars, hydration_order};
+use crate::ContractError;
+use controller::job::Execution;
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Binary, QueryRequest, Uint256};
+use resolver::condition::{IntFnOp, NumExprOp, NumExprValue, NumValue};
+use resolver::variable::{
+    FnValue, QueryExpr, QueryVariable, StaticVariable, Variable, VariableKind,
+};
+use resolver::{JobEnv, QueryMsg, QueryValidateJobCreationMsg};
+
+use super::mock_dependencies;
+
+fn uint_var(name: &str, init_fn: NumValue<Uint256, NumExprOp, IntFnOp>) -> Variable {
+    Variable::Static(StaticVariable {
+        kind: VariableKind::Uint,
+        name: name.to_string(),
+        encode: false,
+        init_fn: FnValue::Uint(init_fn),
+        reinitialize: false,
+        value: None,
+        update_fn: None,
+        default: None,
+        on_error: None,
+    })
+}
+
+fn ref_plus_one(name: &str) -> NumValue<Uint256, NumExprOp, IntFnOp> {
+    NumValue::Expr(NumExprValue {
+        left: Box::new(NumValue::Ref(format!("$warp.variable.{}", name))),
+        op: NumExprOp::Add,
+        right: Box::new(NumValue::Simple(Uint256::one())),
+    })
+}
+
+fn value(var: &Variable) -> Option<String> {
+    match var {
+        Variable::Static(v) => v.value.clone(),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_hydrate_vars_in_dependency_order() {
+    let deps = mock_dependencies();
+    // c references b, which references a, all declared before their dependency
+    let vars = vec![
+        uint_var("c", ref_plus_one("b")),
+        uint_var("b", ref_plus_one("a")),
+        uint_var("a", NumValue::Simple(Uint256::from(1u64))),
+    ];
+
+    assert_eq!(hydration_order(&vars), Ok(vec![2, 1, 0]));
+
+    let hydrated = hydrate_vars(deps.as_ref(), mock_env(), vars, None, &JobEnv::default()).unwrap();
+    let values: Vec<Option<String>> = hydrated.iter().map(value).collect();
+    assert_eq!(
+        values,
+        vec![
+            Some("3".to_string()),
+            Some("2".to_string()),
+            Some("1".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_hydration_order_matches_longest_name() {
+    let vars = vec![
+        uint_var("ab", NumValue::Simple(Uint256::one())),
+        uint_var("x", ref_plus_one("ab")),
+        uint_var("a", ref_plus_one("x")),
+    ];
+
+    // $warp.variable.ab is a reference to ab only, so there is no cycle through a
+    assert_eq!(hydration_order(&vars), Ok(vec![0, 1, 2]));
+}
+
+#[test]
+fn test_hydration_order_matches_whole_names() {
+    let vars = vec![
+        uint_var("a", ref_plus_one("x")),
+        uint_var("x", ref_plus_one("ab")),
+    ];
+
+    // there is no ab var, $warp.variable.ab must not be taken for a reference to a
+    assert_eq!(hydration_order(&vars), Ok(vec![1, 0]));
+}
+
+#[test]
+fn test_hydration_order_scans_stargate_data() {
+    let vars = vec![
+        Variable::Query(QueryVariable {
+            kind: VariableKind::Uint,
+            name: "balance".to_string(),
+            encode: false,
+            init_fn: QueryExpr {
+                selector: "$.balance.amount".to_string(),
+                query: QueryRequest::Stargate {
+                    path: "/cosmos.bank.v1beta1.Query/Balance".to_string(),
+                    data: Binary::from(b"\x0a\x0f$warp.variable.a".to_vec()),
+                },
+            },
+            reinitialize: false,
+            value: None,
+            update_fn: None,
+            default: None,
+            on_error: None,
+        }),
+        uint_var("a", NumValue::Simple(Uint256::one())),
+    ];
+
+    assert_eq!(hydration_order(&vars), Ok(vec![1, 0]));
+}
+
+#[test]
+fn test_hydration_order_rejects_cycles() {
+    let vars = vec![
+        uint_var("a", ref_plus_one("b")),
+        uint_var("b", ref_plus_one("c")),
+        uint_var("c", ref_plus_one("a")),
+    ];
+
+    assert_eq!(
+        hydration_order(&vars),
+        Err(ContractError::VariableCycle {
+            path: "a -> b -> c -> a".to_string()
+        })
+    );
+
+    let deps = mock_dependencies();
+    let msg = QueryMsg::QueryValidateJobCreation(QueryValidateJobCreationMsg {
+        terminate_condition: None,
+        vars: serde_json_wasm::to_string(&vars).unwrap(),
+        executions: vec![Execution {
+            condition: r#"{"expr":{"uint":{"left":{"ref":"$warp.variable.a"},"op":"gt","right":{"simple":"0"}}}}"#.to_string(),
+            msgs: "[]".to_string(),
+        }],
+    });
+
+    let err = query(deps.as_ref(), mock_env(), msg).unwrap_err();
+    assert!(err.to_string().contains("a -> b -> c -> a"));
+}
@@ -0,0 +1,42 @@
+use cosmwasm_std::{Deps, Querier, QuerierResult, QuerierWrapper};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// Querier remembering raw results by serialized request, so identical queries made while
+// hydrating vars or resolving a condition (e.g. several selectors on one pool) hit the chain once.
+// Only meant to live for a single resolver call, results are never invalidated.
+pub struct QueryCache<'a> {
+    querier: &'a dyn Querier,
+    results: RefCell<HashMap<Vec<u8>, QuerierResult>>,
+}
+
+impl<'a> QueryCache<'a> {
+    pub fn new(querier: &'a dyn Querier) -> Self {
+        QueryCache {
+            querier,
+            results: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn deps<'b>(&'b self, deps: Deps<'b>) -> Deps<'b> {
+        Deps {
+            storage: deps.storage,
+            api: deps.api,
+            querier: QuerierWrapper::new(self),
+        }
+    }
+}
+
+impl Querier for QueryCache<'_> {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        if let Some(result) = self.results.borrow().get(bin_request) {
+            return result.clone();
+        }
+
+        let result = self.querier.raw_query(bin_request);
+        self.results
+            .borrow_mut()
+            .insert(bin_request.to_vec(), result.clone());
+        result
+    }
+}
@@ -0,0 +1,131 @@
+use crate::ContractError;
+use resolver::condition::IntFnOp;
+
+// UTC calendar arithmetic over unix timestamps (seconds), using the proleptic Gregorian
+// calendar. Day / civil date conversions follow http://howardhinnant.github.io/date_algorithms.html
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    // 1 (Monday) to 7 (Sunday)
+    pub weekday: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second_of_day: u64,
+}
+
+impl DateTime {
+    pub fn from_timestamp(timestamp: u64) -> Self {
+        let days = (timestamp / SECONDS_PER_DAY) as i64;
+        let second_of_day = timestamp % SECONDS_PER_DAY;
+        let (year, month, day) = civil_from_days(days);
+
+        DateTime {
+            year,
+            month,
+            day,
+            // 1970-01-01 is a Thursday
+            weekday: ((days + 3) % 7 + 1) as u32,
+            hour: (second_of_day / 3600) as u32,
+            minute: (second_of_day % 3600 / 60) as u32,
+            second_of_day,
+        }
+    }
+}
+
+pub fn resolve_calendar_fn(op: &IntFnOp, timestamp: u64) -> Result<u64, ContractError> {
+    let date = DateTime::from_timestamp(timestamp);
+    let start_of_day = timestamp - date.second_of_day;
+
+    match op {
+        IntFnOp::Year => Ok(date.year as u64),
+        IntFnOp::Month => Ok(date.month.into()),
+        IntFnOp::Day => Ok(date.day.into()),
+        IntFnOp::Weekday => Ok(date.weekday.into()),
+        IntFnOp::Hour => Ok(date.hour.into()),
+        IntFnOp::Minute => Ok(date.minute.into()),
+        IntFnOp::StartOfDay => Ok(start_of_day),
+        // the week of the first days of 1970 starts before the epoch
+        IntFnOp::StartOfWeek => start_of_day
+            .checked_sub(u64::from(date.weekday - 1) * SECONDS_PER_DAY)
+            .ok_or(ContractError::ConditionError {
+                msg: format!("Start of week of {} is before 1970-01-01.", timestamp),
+            }),
+        IntFnOp::StartOfMonth => to_timestamp(days_from_civil(date.year, date.month, 1), 0),
+        IntFnOp::AddMonths { months } => add_months(timestamp, *months),
+        _ => Err(ContractError::ConditionError {
+            msg: format!("{:?} is not a calendar function.", op),
+        }),
+    }
+}
+
+pub fn add_months(timestamp: u64, months: i64) -> Result<u64, ContractError> {
+    let date = DateTime::from_timestamp(timestamp);
+    let total = (date.year * 12 + i64::from(date.month) - 1)
+        .checked_add(months)
+        .ok_or_else(|| out_of_range(timestamp, months))?;
+    let year = total.div_euclid(12);
+    let month = (total.rem_euclid(12) + 1) as u32;
+    let day = date.day.min(days_in_month(year, month));
+
+    to_timestamp(days_from_civil(year, month, day), date.second_of_day)
+        .map_err(|_| out_of_range(timestamp, months))
+}
+
+fn to_timestamp(days: i64, second_of_day: u64) -> Result<u64, ContractError> {
+    u64::try_from(days)
+        .ok()
+        .and_then(|days| days.checked_mul(SECONDS_PER_DAY))
+        .and_then(|seconds| seconds.checked_add(second_of_day))
+        .ok_or(ContractError::ConditionError {
+            msg: "Timestamp out of range.".to_string(),
+        })
+}
+
+fn out_of_range(timestamp: u64, months: i64) -> ContractError {
+    ContractError::ConditionError {
+        msg: format!("Adding {} months to {} is out of range.", months, timestamp),
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 to (year, month, day)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+// (year, month, day) to days since 1970-01-01
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = i64::from(if month > 2 { month - 3 } else { month + 9 });
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
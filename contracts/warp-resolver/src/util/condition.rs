@@ -1,18 +1,27 @@
+use crate::util::calendar::resolve_calendar_fn;
+use crate::util::history::resolve_history_value;
+use crate::util::math;
 use crate::util::path::resolve_path;
+use crate::util::regex::is_match;
+use crate::util::stargate::decode_stargate_response;
 use crate::util::variable::get_var;
 use crate::ContractError;
+use controller::account::AssetInfo;
 use cosmwasm_std::{
-    to_vec, ContractResult, Decimal256, Deps, Env, StdError, SystemResult, Uint256,
+    to_vec, ContractResult, Decimal256, Deps, Env, QueryRequest, StdError, SystemResult, Uint128,
+    Uint256, WasmQuery,
 };
 use cw_storage_plus::KeyDeserialize;
 use json_codec_wasm::ast::Ref;
 use json_codec_wasm::Decoder;
 use resolver::condition::{
-    BlockExpr, Condition, DecimalFnOp, Expr, GenExpr, IntFnOp, NumEnvValue, NumExprOp,
-    NumExprValue, NumFnValue, NumOp, NumValue, StringEnvValue, StringOp, StringValue, TimeExpr,
-    TimeOp,
+    BalanceExpr, BlockExpr, Condition, DecimalFnOp, Expr, GenExpr, IntFnOp, NumClampValue,
+    NumConversion, NumEnvValue, NumExprOp, NumExprValue, NumFnValue, NumGenExpr, NumOp, NumValue,
+    StringEnvValue, StringOp, StringValue, TimeExpr, TimeOp,
 };
+use resolver::int256::Int256;
 use resolver::variable::{QueryExpr, Variable};
+use resolver::JobEnv;
 use std::str::FromStr;
 
 pub fn resolve_cond(
@@ -20,12 +29,14 @@ pub fn resolve_cond(
     env: Env,
     cond: Condition,
     vars: &Vec<Variable>,
-    warp_account_addr: Option<String>,
+    job_env: &JobEnv,
 ) -> Result<bool, ContractError> {
     match cond {
+        // children resolve in order, stopping at the first decisive result or error,
+        // errors are only tolerated through Condition::Try
         Condition::And(conds) => {
             for cond in conds {
-                if !resolve_cond(deps, env.clone(), *cond, vars, warp_account_addr.clone())? {
+                if !resolve_cond(deps, env.clone(), *cond, vars, job_env)? {
                     return Ok(false);
                 }
             }
@@ -33,14 +44,17 @@ pub fn resolve_cond(
         }
         Condition::Or(conds) => {
             for cond in conds {
-                if resolve_cond(deps, env.clone(), *cond, vars, warp_account_addr.clone())? {
+                if resolve_cond(deps, env.clone(), *cond, vars, job_env)? {
                     return Ok(true);
                 }
             }
             Ok(false)
         }
-        Condition::Not(cond) => Ok(!resolve_cond(deps, env, *cond, vars, warp_account_addr)?),
-        Condition::Expr(expr) => Ok(resolve_expr(deps, env, *expr, vars, warp_account_addr)?),
+        Condition::Not(cond) => Ok(!resolve_cond(deps, env, *cond, vars, job_env)?),
+        Condition::Expr(expr) => Ok(resolve_expr(deps, env, *expr, vars, job_env)?),
+        Condition::Try { cond, on_error } => {
+            Ok(resolve_cond(deps, env, *cond, vars, job_env).unwrap_or(on_error))
+        }
     }
 }
 
@@ -49,44 +63,62 @@ pub fn resolve_expr(
     env: Env,
     expr: Expr,
     vars: &Vec<Variable>,
-    warp_account_addr: Option<String>,
+    job_env: &JobEnv,
 ) -> Result<bool, ContractError> {
     match expr {
-        Expr::String(expr) => resolve_string_expr(deps, env, expr, vars, warp_account_addr),
-        Expr::Uint(expr) => resolve_uint_expr(deps, env, expr, vars),
-        Expr::Int(expr) => resolve_int_expr(deps, env, expr, vars),
-        Expr::Decimal(expr) => resolve_decimal_expr(deps, env, expr, vars),
-        Expr::Timestamp(expr) => resolve_timestamp_expr(deps, env, expr),
+        Expr::String(expr) => resolve_string_expr(deps, env, expr, vars, job_env),
+        Expr::Uint(expr) => resolve_uint_expr(deps, env, expr, vars, job_env),
+        Expr::Int(expr) => resolve_int_expr(deps, env, expr, vars, job_env),
+        Expr::Decimal(expr) => resolve_decimal_expr(deps, env, expr, vars, job_env),
+        Expr::Timestamp(expr) => resolve_timestamp_expr(deps, env, expr, vars, job_env),
         Expr::BlockHeight(expr) => resolve_block_expr(deps, env, expr),
         Expr::Bool(expr) => resolve_ref_bool(deps, env, expr, vars),
+        Expr::Balance(expr) => resolve_balance_expr(deps, env, expr, vars, job_env),
     }
 }
 
 pub fn resolve_int_expr(
     deps: Deps,
     env: Env,
-    expr: GenExpr<NumValue<i128, NumExprOp, IntFnOp>, NumOp>,
+    expr: NumGenExpr<Int256, IntFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<bool, ContractError> {
-    let left = resolve_num_value_int(deps, env.clone(), expr.left, vars)?;
-    let right = resolve_num_value_int(deps, env.clone(), expr.right, vars)?;
+    let left = resolve_num_value_int(deps, env.clone(), expr.left, vars, job_env)?;
+    let right = resolve_num_value_int(deps, env.clone(), expr.right, vars, job_env)?;
+    let op = resolve_num_op_bounds(expr.op, |upper| {
+        resolve_num_value_int(deps, env.clone(), upper, vars, job_env)
+    })?;
 
-    Ok(resolve_int_op(deps, env, left, right, expr.op))
+    resolve_int_op(deps, env, left, right, op)
 }
 
 pub fn resolve_num_value_int(
     deps: Deps,
     env: Env,
-    value: NumValue<i128, NumExprOp, IntFnOp>,
+    value: NumValue<Int256, NumExprOp, IntFnOp>,
     vars: &Vec<Variable>,
-) -> Result<i128, ContractError> {
+    job_env: &JobEnv,
+) -> Result<Int256, ContractError> {
     match value {
         NumValue::Simple(value) => Ok(value),
-        NumValue::Expr(expr) => resolve_num_expr_int(deps, env, expr, vars),
+        NumValue::Expr(expr) => resolve_num_expr_int(deps, env, expr, vars, job_env),
         NumValue::Ref(expr) => resolve_ref_int(deps, env, expr, vars),
-        NumValue::Fn(expr) => resolve_num_fn_int(deps, env, expr, vars),
-        NumValue::Env(_expr) => Err(ContractError::ConditionError {
-            msg: "Int resolve Env.".to_string(),
+        NumValue::Fn(expr) => resolve_num_fn_int(deps, env, expr, vars, job_env),
+        NumValue::Env(expr) => Ok(Int256::from(resolve_num_env_uint(
+            deps, env, expr, vars, job_env,
+        )?)),
+        NumValue::Clamp(expr) => resolve_num_clamp_int(deps, env, expr, vars, job_env),
+        NumValue::Convert(conversion) => match *conversion {
+            NumConversion::ParseString(value) => Ok(Int256::from_str(&resolve_string_value(
+                deps, env, value, vars, job_env,
+            )?)?),
+            _ => Err(ContractError::ConditionError {
+                msg: "Int resolve Convert.".to_string(),
+            }),
+        },
+        NumValue::History(_) => Err(ContractError::ConditionError {
+            msg: "Int resolve History.".to_string(),
         }),
     }
 }
@@ -96,26 +128,38 @@ fn resolve_ref_int(
     _env: Env,
     r: String,
     vars: &Vec<Variable>,
-) -> Result<i128, ContractError> {
+) -> Result<Int256, ContractError> {
     let var = get_var(r, vars)?;
     let res = match var {
         Variable::Static(s) => {
             let val = s.clone().value.ok_or(ContractError::ConditionError {
                 msg: format!("Int Static value not found: {}", s.name),
             })?;
-            str::parse::<i128>(&val)?
+            Int256::from_str(&val)?
         }
         Variable::Query(q) => {
             let val = q.clone().value.ok_or(ContractError::ConditionError {
                 msg: format!("Int Query value not found: {}", q.name),
             })?;
-            str::parse::<i128>(&val)?
+            Int256::from_str(&val)?
         }
         Variable::External(e) => {
             let val = e.clone().value.ok_or(ContractError::ConditionError {
                 msg: format!("Int External value not found: {}", e.name),
             })?;
-            str::parse::<i128>(&val)?
+            Int256::from_str(&val)?
+        }
+        Variable::History(h) => {
+            let val = h.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Int History value not found: {}", h.name),
+            })?;
+            Int256::from_str(&val)?
+        }
+        Variable::RawQuery(r) => {
+            let val = r.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Int RawQuery value not found: {}", r.name),
+            })?;
+            Int256::from_str(&val)?
         }
     };
 
@@ -125,71 +169,131 @@ fn resolve_ref_int(
 fn resolve_num_fn_int(
     deps: Deps,
     env: Env,
-    expr: NumFnValue<i128, NumExprOp, IntFnOp>,
+    expr: NumFnValue<Int256, NumExprOp, IntFnOp>,
     vars: &Vec<Variable>,
-) -> Result<i128, ContractError> {
-    let right = resolve_num_value_int(deps, env, *expr.right, vars)?;
+    job_env: &JobEnv,
+) -> Result<Int256, ContractError> {
+    let right = resolve_num_value_int(deps, env, *expr.right, vars, job_env)?;
 
     match expr.op {
         IntFnOp::Abs => Ok(right.abs()),
-        IntFnOp::Neg => {
-            Ok(right
-                .checked_mul(i128::from(-1i64))
-                .ok_or(ContractError::ConditionError {
-                    msg: "Int negation error.".to_string(),
-                })?)
-        }
+        IntFnOp::Neg => Ok(-right),
+        op => match duration_seconds(&op) {
+            Some(seconds) => {
+                Ok(right
+                    .checked_mul(Int256::from(seconds as i128))
+                    .map_err(|_| ContractError::ConditionError {
+                        msg: "Int checked mul error.".to_string(),
+                    })?)
+            }
+            None => Ok(Int256::from(
+                resolve_calendar_fn(&op, int_timestamp(right)?)? as i128,
+            )),
+        },
     }
 }
 
 pub fn resolve_num_expr_int(
     deps: Deps,
     env: Env,
-    expr: NumExprValue<i128, NumExprOp, IntFnOp>,
+    expr: NumExprValue<Int256, NumExprOp, IntFnOp>,
     vars: &Vec<Variable>,
-) -> Result<i128, ContractError> {
-    let left = resolve_num_value_int(deps, env.clone(), *expr.left, vars)?;
-    let right = resolve_num_value_int(deps, env, *expr.right, vars)?;
+    job_env: &JobEnv,
+) -> Result<Int256, ContractError> {
+    let left = resolve_num_value_int(deps, env.clone(), *expr.left, vars, job_env)?;
+    let right = resolve_num_value_int(deps, env, *expr.right, vars, job_env)?;
 
     match expr.op {
-        NumExprOp::Sub => Ok(left
-            .checked_sub(right)
-            .ok_or(ContractError::ConditionError {
-                msg: "Int checked sub error.".to_string(),
-            })?),
-        NumExprOp::Add => Ok(left
-            .checked_add(right)
-            .ok_or(ContractError::ConditionError {
-                msg: "Int checked add error.".to_string(),
-            })?),
-        NumExprOp::Div => Ok(left
-            .checked_div(right)
-            .ok_or(ContractError::ConditionError {
-                msg: "Int checked div error.".to_string(),
-            })?),
-        NumExprOp::Mul => Ok(left
-            .checked_mul(right)
-            .ok_or(ContractError::ConditionError {
-                msg: "Int checked mul error.".to_string(),
-            })?),
-        NumExprOp::Mod => Ok(left
-            .checked_rem(right)
-            .ok_or(ContractError::ConditionError {
-                msg: "Int checked rem error.".to_string(),
-            })?),
+        NumExprOp::Sub => {
+            Ok(left
+                .checked_sub(right)
+                .map_err(|_| ContractError::ConditionError {
+                    msg: "Int checked sub error.".to_string(),
+                })?)
+        }
+        NumExprOp::Add => {
+            Ok(left
+                .checked_add(right)
+                .map_err(|_| ContractError::ConditionError {
+                    msg: "Int checked add error.".to_string(),
+                })?)
+        }
+        NumExprOp::Div => {
+            Ok(left
+                .checked_div(right)
+                .map_err(|_| ContractError::ConditionError {
+                    msg: "Int checked div error.".to_string(),
+                })?)
+        }
+        NumExprOp::Mul => {
+            Ok(left
+                .checked_mul(right)
+                .map_err(|_| ContractError::ConditionError {
+                    msg: "Int checked mul error.".to_string(),
+                })?)
+        }
+        NumExprOp::Mod => {
+            Ok(left
+                .checked_rem(right)
+                .map_err(|_| ContractError::ConditionError {
+                    msg: "Int checked rem error.".to_string(),
+                })?)
+        }
+        NumExprOp::Pow => {
+            if right.is_negative() {
+                return Err(ContractError::ConditionError {
+                    msg: "Int negative pow exponent.".to_string(),
+                });
+            }
+            Ok(left
+                .checked_pow(math::to_u32(right.unsigned_abs())?)
+                .map_err(|_| ContractError::ConditionError {
+                    msg: "Int checked pow error.".to_string(),
+                })?)
+        }
+        NumExprOp::Min => Ok(left.min(right)),
+        NumExprOp::Max => Ok(left.max(right)),
+    }
+}
+
+fn int_timestamp(value: Int256) -> Result<u64, ContractError> {
+    if value.is_negative() {
+        return Err(ContractError::ConditionError {
+            msg: format!("Timestamp {} is negative.", value),
+        });
     }
+
+    math::to_u64(value.unsigned_abs())
+}
+
+fn resolve_num_clamp_int(
+    deps: Deps,
+    env: Env,
+    expr: NumClampValue<Int256, NumExprOp, IntFnOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Result<Int256, ContractError> {
+    let value = resolve_num_value_int(deps, env.clone(), *expr.value, vars, job_env)?;
+    let min = resolve_num_value_int(deps, env.clone(), *expr.min, vars, job_env)?;
+    let max = resolve_num_value_int(deps, env, *expr.max, vars, job_env)?;
+
+    clamp(value, min, max)
 }
 
 pub fn resolve_uint_expr(
     deps: Deps,
     env: Env,
-    expr: GenExpr<NumValue<Uint256, NumExprOp, IntFnOp>, NumOp>,
+    expr: NumGenExpr<Uint256, IntFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<bool, ContractError> {
-    let left = resolve_num_value_uint(deps, env.clone(), expr.left, vars)?;
-    let right = resolve_num_value_uint(deps, env.clone(), expr.right, vars)?;
+    let left = resolve_num_value_uint(deps, env.clone(), expr.left, vars, job_env)?;
+    let right = resolve_num_value_uint(deps, env.clone(), expr.right, vars, job_env)?;
+    let op = resolve_num_op_bounds(expr.op, |upper| {
+        resolve_num_value_uint(deps, env.clone(), upper, vars, job_env)
+    })?;
 
-    Ok(resolve_uint_op(deps, env, left, right, expr.op))
+    resolve_uint_op(deps, env, left, right, op)
 }
 
 pub fn resolve_num_value_uint(
@@ -197,15 +301,54 @@ pub fn resolve_num_value_uint(
     env: Env,
     value: NumValue<Uint256, NumExprOp, IntFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<Uint256, ContractError> {
     match value {
         NumValue::Simple(value) => Ok(value),
-        NumValue::Expr(expr) => resolve_num_expr_uint(deps, env, expr, vars),
+        NumValue::Expr(expr) => resolve_num_expr_uint(deps, env, expr, vars, job_env),
         NumValue::Ref(expr) => resolve_ref_uint(deps, env, expr, vars),
-        NumValue::Fn(_) => Err(ContractError::ConditionError {
-            msg: "Uint resolve Fn.".to_string(),
+        NumValue::Fn(expr) => resolve_num_fn_uint(deps, env, expr, vars, job_env),
+        NumValue::Env(expr) => resolve_num_env_uint(deps, env, expr, vars, job_env),
+        NumValue::Clamp(expr) => resolve_num_clamp_uint(deps, env, expr, vars, job_env),
+        NumValue::Convert(conversion) => match *conversion {
+            NumConversion::ToUint {
+                value,
+                scale,
+                rounding,
+            } => math::to_uint(
+                resolve_num_value_decimal(deps, env, value, vars, job_env)?,
+                scale,
+                rounding,
+            ),
+            NumConversion::ParseString(value) => Ok(Uint256::from_str(&resolve_string_value(
+                deps, env, value, vars, job_env,
+            )?)?),
+            NumConversion::ToDecimal { .. } => Err(ContractError::ConditionError {
+                msg: "Uint resolve ToDecimal.".to_string(),
+            }),
+        },
+        NumValue::History(_) => Err(ContractError::ConditionError {
+            msg: "Uint resolve History.".to_string(),
         }),
-        NumValue::Env(expr) => resolve_num_env_uint(deps, env, expr, vars),
+    }
+}
+
+fn resolve_num_fn_uint(
+    deps: Deps,
+    env: Env,
+    expr: NumFnValue<Uint256, NumExprOp, IntFnOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Result<Uint256, ContractError> {
+    let right = resolve_num_value_uint(deps, env, *expr.right, vars, job_env)?;
+
+    match duration_seconds(&expr.op) {
+        Some(seconds) => Ok(right.checked_mul(Uint256::from(seconds)).map_err(|_| {
+            ContractError::ConditionError {
+                msg: "Uint checked mul error.".to_string(),
+            }
+        })?),
+        None => Ok(resolve_calendar_fn(&expr.op, math::to_u64(right)?)?.into()),
     }
 }
 
@@ -235,6 +378,18 @@ fn resolve_ref_uint(
             })?;
             Uint256::from_str(&val)?
         }
+        Variable::History(h) => {
+            let val = h.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Uint History value not found: {}", h.name),
+            })?;
+            Uint256::from_str(&val)?
+        }
+        Variable::RawQuery(r) => {
+            let val = r.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Uint RawQuery value not found: {}", r.name),
+            })?;
+            Uint256::from_str(&val)?
+        }
     };
 
     Ok(res)
@@ -245,9 +400,10 @@ pub fn resolve_num_expr_uint(
     env: Env,
     expr: NumExprValue<Uint256, NumExprOp, IntFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<Uint256, ContractError> {
-    let left = resolve_num_value_uint(deps, env.clone(), *expr.left, vars)?;
-    let right = resolve_num_value_uint(deps, env, *expr.right, vars)?;
+    let left = resolve_num_value_uint(deps, env.clone(), *expr.left, vars, job_env)?;
+    let right = resolve_num_value_uint(deps, env, *expr.right, vars, job_env)?;
 
     match expr.op {
         NumExprOp::Sub => {
@@ -285,31 +441,68 @@ pub fn resolve_num_expr_uint(
                     msg: "Uint checked rem error.".to_string(),
                 })?)
         }
+        NumExprOp::Pow => Ok(left.checked_pow(math::to_u32(right)?).map_err(|_| {
+            ContractError::ConditionError {
+                msg: "Uint checked pow error.".to_string(),
+            }
+        })?),
+        NumExprOp::Min => Ok(left.min(right)),
+        NumExprOp::Max => Ok(left.max(right)),
     }
 }
 
+fn resolve_num_clamp_uint(
+    deps: Deps,
+    env: Env,
+    expr: NumClampValue<Uint256, NumExprOp, IntFnOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Result<Uint256, ContractError> {
+    let value = resolve_num_value_uint(deps, env.clone(), *expr.value, vars, job_env)?;
+    let min = resolve_num_value_uint(deps, env.clone(), *expr.min, vars, job_env)?;
+    let max = resolve_num_value_uint(deps, env, *expr.max, vars, job_env)?;
+
+    clamp(value, min, max)
+}
+
 pub fn resolve_num_env_uint(
     _deps: Deps,
     env: Env,
     expr: NumEnvValue,
     _vars: &[Variable],
+    job_env: &JobEnv,
 ) -> Result<Uint256, ContractError> {
-    match expr {
-        NumEnvValue::Time => Ok(env.block.time.seconds().into()),
-        NumEnvValue::BlockHeight => Ok(env.block.height.into()),
+    let value = match expr {
+        NumEnvValue::Time => return Ok(env.block.time.seconds().into()),
+        NumEnvValue::BlockHeight => return Ok(env.block.height.into()),
+        NumEnvValue::JobId => (job_env.job_id, "Job id"),
+        NumEnvValue::ExecutionCount => (job_env.execution_count, "Execution count"),
+        NumEnvValue::JobCreatedAt => (job_env.job_created_at, "Job created at"),
+        NumEnvValue::LastExecutionTime => (job_env.last_execution_time, "Last execution time"),
+    };
+
+    match value {
+        (Some(value), _) => Ok(value.u64().into()),
+        (None, name) => Err(ContractError::HydrationError {
+            msg: format!("{} not found.", name),
+        }),
     }
 }
 
 pub fn resolve_decimal_expr(
     deps: Deps,
     env: Env,
-    expr: GenExpr<NumValue<Decimal256, NumExprOp, DecimalFnOp>, NumOp>,
+    expr: NumGenExpr<Decimal256, DecimalFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<bool, ContractError> {
-    let left = resolve_num_value_decimal(deps, env.clone(), expr.left, vars)?;
-    let right = resolve_num_value_decimal(deps, env.clone(), expr.right, vars)?;
+    let left = resolve_num_value_decimal(deps, env.clone(), expr.left, vars, job_env)?;
+    let right = resolve_num_value_decimal(deps, env.clone(), expr.right, vars, job_env)?;
+    let op = resolve_num_op_bounds(expr.op, |upper| {
+        resolve_num_value_decimal(deps, env.clone(), upper, vars, job_env)
+    })?;
 
-    Ok(resolve_decimal_op(deps, env, left, right, expr.op))
+    resolve_decimal_op(deps, env, left, right, op)
 }
 
 pub fn resolve_num_value_decimal(
@@ -317,15 +510,30 @@ pub fn resolve_num_value_decimal(
     env: Env,
     value: NumValue<Decimal256, NumExprOp, DecimalFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<Decimal256, ContractError> {
     match value {
         NumValue::Simple(value) => Ok(value),
-        NumValue::Expr(expr) => resolve_num_expr_decimal(deps, env, expr, vars),
+        NumValue::Expr(expr) => resolve_num_expr_decimal(deps, env, expr, vars, job_env),
         NumValue::Ref(expr) => resolve_ref_decimal(deps, env, expr, vars),
-        NumValue::Fn(expr) => resolve_num_fn_decimal(deps, env, expr, vars),
-        NumValue::Env(_expr) => Err(ContractError::ConditionError {
-            msg: "Decimal resolve Env.".to_string(),
-        }),
+        NumValue::Fn(expr) => resolve_num_fn_decimal(deps, env, expr, vars, job_env),
+        NumValue::Env(expr) => {
+            math::to_decimal(resolve_num_env_uint(deps, env, expr, vars, job_env)?, 0)
+        }
+        NumValue::Clamp(expr) => resolve_num_clamp_decimal(deps, env, expr, vars, job_env),
+        NumValue::Convert(conversion) => match *conversion {
+            NumConversion::ToDecimal { value, scale } => math::to_decimal(
+                resolve_num_value_uint(deps, env, value, vars, job_env)?,
+                scale,
+            ),
+            NumConversion::ParseString(value) => Ok(Decimal256::from_str(&resolve_string_value(
+                deps, env, value, vars, job_env,
+            )?)?),
+            NumConversion::ToUint { .. } => Err(ContractError::ConditionError {
+                msg: "Decimal resolve ToUint.".to_string(),
+            }),
+        },
+        NumValue::History(value) => resolve_history_value(value, vars),
     }
 }
 
@@ -355,6 +563,18 @@ fn resolve_ref_decimal(
             })?;
             Decimal256::from_str(&val)?
         }
+        Variable::History(h) => {
+            let val = h.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Decimal History value not found: {}", h.name),
+            })?;
+            Decimal256::from_str(&val)?
+        }
+        Variable::RawQuery(r) => {
+            let val = r.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Decimal RawQuery value not found: {}", r.name),
+            })?;
+            Decimal256::from_str(&val)?
+        }
     };
 
     Ok(res)
@@ -365,8 +585,9 @@ fn resolve_num_fn_decimal(
     env: Env,
     expr: NumFnValue<Decimal256, NumExprOp, DecimalFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<Decimal256, ContractError> {
-    let right = resolve_num_value_decimal(deps, env, *expr.right, vars)?;
+    let right = resolve_num_value_decimal(deps, env, *expr.right, vars, job_env)?;
 
     match expr.op {
         DecimalFnOp::Abs => Ok(right.abs_diff(Decimal256::zero())),
@@ -376,6 +597,10 @@ fn resolve_num_fn_decimal(
         DecimalFnOp::Floor => Ok(right.floor()),
         DecimalFnOp::Sqrt => Ok(right.sqrt()),
         DecimalFnOp::Ceil => Ok(right.ceil()),
+        DecimalFnOp::Log10 => math::log10(right),
+        DecimalFnOp::Ln => math::ln(right),
+        DecimalFnOp::Exp => math::exp(right),
+        DecimalFnOp::Round { precision, mode } => math::round(right, precision, mode),
     }
 }
 
@@ -384,9 +609,10 @@ pub fn resolve_num_expr_decimal(
     env: Env,
     expr: NumExprValue<Decimal256, NumExprOp, DecimalFnOp>,
     vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<Decimal256, ContractError> {
-    let left = resolve_num_value_decimal(deps, env.clone(), *expr.left, vars)?;
-    let right = resolve_num_value_decimal(deps, env, *expr.right, vars)?;
+    let left = resolve_num_value_decimal(deps, env.clone(), *expr.left, vars, job_env)?;
+    let right = resolve_num_value_decimal(deps, env, *expr.right, vars, job_env)?;
 
     match expr.op {
         NumExprOp::Sub => {
@@ -424,55 +650,164 @@ pub fn resolve_num_expr_decimal(
                     msg: "Decimal checked sub error.".to_string(),
                 })?)
         }
+        NumExprOp::Pow => math::pow(left, right),
+        NumExprOp::Min => Ok(left.min(right)),
+        NumExprOp::Max => Ok(left.max(right)),
     }
 }
 
+fn resolve_num_clamp_decimal(
+    deps: Deps,
+    env: Env,
+    expr: NumClampValue<Decimal256, NumExprOp, DecimalFnOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Result<Decimal256, ContractError> {
+    let value = resolve_num_value_decimal(deps, env.clone(), *expr.value, vars, job_env)?;
+    let min = resolve_num_value_decimal(deps, env.clone(), *expr.min, vars, job_env)?;
+    let max = resolve_num_value_decimal(deps, env, *expr.max, vars, job_env)?;
+
+    clamp(value, min, max)
+}
+
+// Seconds per unit of the IntFnOp duration helpers
+fn duration_seconds(op: &IntFnOp) -> Option<u64> {
+    match op {
+        IntFnOp::Seconds => Some(1),
+        IntFnOp::Minutes => Some(60),
+        IntFnOp::Hours => Some(60 * 60),
+        IntFnOp::Days => Some(24 * 60 * 60),
+        IntFnOp::Weeks => Some(7 * 24 * 60 * 60),
+        _ => None,
+    }
+}
+
+fn clamp<T: Ord + std::fmt::Display>(value: T, min: T, max: T) -> Result<T, ContractError> {
+    if min > max {
+        return Err(ContractError::ConditionError {
+            msg: format!("Clamp min {} is greater than max {}.", min, max),
+        });
+    }
+
+    Ok(value.clamp(min, max))
+}
+
 pub fn resolve_timestamp_expr(
-    _deps: Deps,
+    deps: Deps,
     env: Env,
     expr: TimeExpr,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
 ) -> Result<bool, ContractError> {
-    let res = match expr.op {
-        TimeOp::Lt => env.block.time.seconds().lt(&expr.comparator.u64()),
-        TimeOp::Gt => env.block.time.seconds().gt(&expr.comparator.u64()),
-    };
+    // block time as left, the legacy fixed comparator being a Uint comparison as well
+    let left = Uint256::from(env.block.time.seconds());
 
-    Ok(res)
+    match expr {
+        TimeExpr::Num(expr) => {
+            let right = resolve_num_value_uint(deps, env.clone(), expr.value, vars, job_env)?;
+            let op = resolve_num_op_bounds(expr.op, |upper| {
+                resolve_num_value_uint(deps, env.clone(), upper, vars, job_env)
+            })?;
+
+            resolve_uint_op(deps, env, left, right, op)
+        }
+        TimeExpr::Fixed(expr) => resolve_uint_op(
+            deps,
+            env,
+            left,
+            expr.comparator.u64().into(),
+            resolve_time_op(expr.op),
+        ),
+    }
+}
+
+pub fn resolve_time_op(op: TimeOp) -> NumOp<Uint256> {
+    match op {
+        TimeOp::Lt => NumOp::Lt,
+        TimeOp::Gt => NumOp::Gt,
+    }
+}
+
+pub fn resolve_block_expr(deps: Deps, env: Env, expr: BlockExpr) -> Result<bool, ContractError> {
+    let op = resolve_num_op_bounds(expr.op, |upper| Ok(upper.u64()))?;
+
+    resolve_block_op(
+        deps,
+        env.clone(),
+        env.block.height,
+        expr.comparator.u64(),
+        op,
+    )
+}
+
+pub fn resolve_balance_expr(
+    deps: Deps,
+    env: Env,
+    expr: BalanceExpr,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Result<bool, ContractError> {
+    // balance of address as left
+    let address = resolve_string_value(deps, env.clone(), expr.address, vars, job_env)?;
+    let left = resolve_balance(deps, address, expr.asset)?;
+    let right = resolve_num_value_uint(deps, env.clone(), expr.value, vars, job_env)?;
+    let op = resolve_num_op_bounds(expr.op, |upper| {
+        resolve_num_value_uint(deps, env.clone(), upper, vars, job_env)
+    })?;
+
+    resolve_uint_op(deps, env, left, right, op)
 }
 
-pub fn resolve_block_expr(_deps: Deps, env: Env, expr: BlockExpr) -> Result<bool, ContractError> {
-    let res = match expr.op {
-        NumOp::Eq => env.block.height.eq(&expr.comparator.u64()),
-        NumOp::Neq => env.block.height.ne(&expr.comparator.u64()),
-        NumOp::Lt => env.block.height.lt(&expr.comparator.u64()),
-        NumOp::Gt => env.block.height.gt(&expr.comparator.u64()),
-        NumOp::Gte => env.block.height.ge(&expr.comparator.u64()),
-        NumOp::Lte => env.block.height.le(&expr.comparator.u64()),
+pub fn resolve_balance(
+    deps: Deps,
+    address: String,
+    asset: AssetInfo,
+) -> Result<Uint256, ContractError> {
+    let balance = match asset {
+        AssetInfo::Native(denom) => deps.querier.query_balance(address, denom)?.amount,
+        AssetInfo::Cw20(contract_addr) => {
+            let res: cw20::BalanceResponse = deps
+                .querier
+                .query_wasm_smart(contract_addr, &cw20::Cw20QueryMsg::Balance { address })?;
+            res.balance
+        }
+        AssetInfo::Cw721(contract_addr, token_id) => {
+            let res: cw721::OwnerOfResponse = deps.querier.query_wasm_smart(
+                contract_addr,
+                &cw721::Cw721QueryMsg::OwnerOf {
+                    token_id,
+                    include_expired: None,
+                },
+            )?;
+            if res.owner == address {
+                Uint128::one()
+            } else {
+                Uint128::zero()
+            }
+        }
     };
 
-    Ok(res)
+    Ok(balance.into())
 }
 
-pub fn resolve_uint_op(_deps: Deps, _env: Env, left: Uint256, right: Uint256, op: NumOp) -> bool {
-    match op {
-        NumOp::Eq => left.eq(&right),
-        NumOp::Neq => left.ne(&right),
-        NumOp::Lt => left.lt(&right),
-        NumOp::Gt => left.gt(&right),
-        NumOp::Gte => left.ge(&right),
-        NumOp::Lte => left.le(&right),
-    }
+pub fn resolve_uint_op(
+    _deps: Deps,
+    _env: Env,
+    left: Uint256,
+    right: Uint256,
+    op: NumOp<Uint256>,
+) -> Result<bool, ContractError> {
+    resolve_num_op(left, right, op)
 }
 
-pub fn resolve_int_op(_deps: Deps, _env: Env, left: i128, right: i128, op: NumOp) -> bool {
-    match op {
-        NumOp::Eq => left.eq(&right),
-        NumOp::Neq => left.ne(&right),
-        NumOp::Lt => left.lt(&right),
-        NumOp::Gt => left.gt(&right),
-        NumOp::Gte => left.ge(&right),
-        NumOp::Lte => left.le(&right),
-    }
+pub fn resolve_int_op(
+    _deps: Deps,
+    _env: Env,
+    left: Int256,
+    right: Int256,
+    op: NumOp<Int256>,
+) -> Result<bool, ContractError> {
+    resolve_num_op(left, right, op)
 }
 
 pub fn resolve_decimal_op(
@@ -480,16 +815,57 @@ pub fn resolve_decimal_op(
     _env: Env,
     left: Decimal256,
     right: Decimal256,
-    op: NumOp,
-) -> bool {
-    match op {
+    op: NumOp<Decimal256>,
+) -> Result<bool, ContractError> {
+    resolve_num_op(left, right, op)
+}
+
+pub fn resolve_block_op(
+    _deps: Deps,
+    _env: Env,
+    left: u64,
+    right: u64,
+    op: NumOp<u64>,
+) -> Result<bool, ContractError> {
+    resolve_num_op(left, right, op)
+}
+
+fn resolve_num_op<T: PartialOrd>(left: T, right: T, op: NumOp<T>) -> Result<bool, ContractError> {
+    let res = match op {
         NumOp::Eq => left.eq(&right),
         NumOp::Neq => left.ne(&right),
         NumOp::Lt => left.lt(&right),
         NumOp::Gt => left.gt(&right),
         NumOp::Gte => left.ge(&right),
         NumOp::Lte => left.le(&right),
-    }
+        NumOp::Between { upper } => right <= left && left <= upper,
+        NumOp::NotBetween { upper } => !(right <= left && left <= upper),
+    };
+
+    Ok(res)
+}
+
+// Resolves the operands carried by the op, only evaluated for the ops which have any
+pub fn resolve_num_op_bounds<T, U>(
+    op: NumOp<T>,
+    resolve: impl FnOnce(T) -> Result<U, ContractError>,
+) -> Result<NumOp<U>, ContractError> {
+    let op = match op {
+        NumOp::Eq => NumOp::Eq,
+        NumOp::Neq => NumOp::Neq,
+        NumOp::Lt => NumOp::Lt,
+        NumOp::Gt => NumOp::Gt,
+        NumOp::Gte => NumOp::Gte,
+        NumOp::Lte => NumOp::Lte,
+        NumOp::Between { upper } => NumOp::Between {
+            upper: resolve(upper)?,
+        },
+        NumOp::NotBetween { upper } => NumOp::NotBetween {
+            upper: resolve(upper)?,
+        },
+    };
+
+    Ok(op)
 }
 
 pub fn resolve_string_expr(
@@ -497,19 +873,12 @@ pub fn resolve_string_expr(
     env: Env,
     expr: GenExpr<StringValue<String>, StringOp>,
     vars: &Vec<Variable>,
-    warp_account_addr: Option<String>,
+    job_env: &JobEnv,
 ) -> Result<bool, ContractError> {
-    let left = match expr.left {
-        StringValue::Simple(left) => left,
-        StringValue::Ref(left) => resolve_ref_string(deps, env.clone(), left, vars)?,
-        StringValue::Env(left) => resolve_string_value_env(deps, left, warp_account_addr.clone())?,
-    };
-    let right = match expr.right {
-        StringValue::Simple(right) => right,
-        StringValue::Ref(right) => resolve_ref_string(deps, env.clone(), right, vars)?,
-        StringValue::Env(right) => resolve_string_value_env(deps, right, warp_account_addr)?,
-    };
-    Ok(resolve_str_op(deps, env, left, right, expr.op))
+    let left = resolve_string_value(deps, env.clone(), expr.left, vars, job_env)?;
+    let right = resolve_string_value(deps, env.clone(), expr.right, vars, job_env)?;
+
+    resolve_str_op(deps, env, left, right, expr.op)
 }
 
 pub fn resolve_string_value(
@@ -517,30 +886,39 @@ pub fn resolve_string_value(
     env: Env,
     value: StringValue<String>,
     vars: &Vec<Variable>,
-    warp_account_addr: Option<String>,
+    job_env: &JobEnv,
 ) -> Result<String, ContractError> {
     match value {
         StringValue::Simple(value) => Ok(value),
         StringValue::Ref(r) => resolve_ref_string(deps, env, r, vars),
-        StringValue::Env(value) => resolve_string_value_env(deps, value, warp_account_addr),
+        StringValue::Env(value) => resolve_string_value_env(deps, env, value, job_env),
     }
 }
 
 pub fn resolve_string_value_env(
     deps: Deps,
+    env: Env,
     value: StringEnvValue,
-    warp_account_addr: Option<String>,
+    job_env: &JobEnv,
 ) -> Result<String, ContractError> {
-    match value {
-        StringEnvValue::WarpAccountAddr => match warp_account_addr {
-            Some(addr) => {
-                deps.api.addr_validate(&addr)?;
-                Ok(addr)
-            }
-            None => Err(ContractError::HydrationError {
-                msg: "Warp account addr not found.".to_string(),
-            }),
-        },
+    let (addr, name) = match value {
+        StringEnvValue::ChainId => return Ok(env.block.chain_id),
+        StringEnvValue::WarpAccountAddr => (&job_env.warp_account_addr, "Warp account addr"),
+        StringEnvValue::JobOwner => (&job_env.job_owner, "Job owner"),
+        StringEnvValue::FundingAccountAddr => {
+            (&job_env.funding_account_addr, "Funding account addr")
+        }
+        StringEnvValue::ControllerAddr => (&job_env.controller_addr, "Controller addr"),
+    };
+
+    match addr {
+        Some(addr) => {
+            deps.api.addr_validate(addr)?;
+            Ok(addr.clone())
+        }
+        None => Err(ContractError::HydrationError {
+            msg: format!("{} not found.", name),
+        }),
     }
 }
 
@@ -561,19 +939,35 @@ fn resolve_ref_string(
         Variable::External(e) => e.value.clone().ok_or(ContractError::ConditionError {
             msg: format!("String External value not found: {}", e.name),
         })?,
+        Variable::History(h) => h.value.clone().ok_or(ContractError::ConditionError {
+            msg: format!("String History value not found: {}", h.name),
+        })?,
+        Variable::RawQuery(r) => r.value.clone().ok_or(ContractError::ConditionError {
+            msg: format!("String RawQuery value not found: {}", r.name),
+        })?,
     };
 
     Ok(res)
 }
 
-pub fn resolve_str_op(_deps: Deps, _env: Env, left: String, right: String, op: StringOp) -> bool {
-    match op {
+pub fn resolve_str_op(
+    _deps: Deps,
+    _env: Env,
+    left: String,
+    right: String,
+    op: StringOp,
+) -> Result<bool, ContractError> {
+    let res = match op {
         StringOp::StartsWith => left.starts_with(&right),
         StringOp::EndsWith => left.ends_with(&right),
         StringOp::Contains => left.contains(&right),
         StringOp::Eq => left.eq(&right),
         StringOp::Neq => left.ne(&right),
-    }
+        StringOp::In(values) => values.contains(&left),
+        StringOp::Matches => is_match(&right, &left)?,
+    };
+
+    Ok(res)
 }
 
 pub fn resolve_query_expr(deps: Deps, _env: Env, expr: QueryExpr) -> Result<String, ContractError> {
@@ -593,6 +987,21 @@ pub fn resolve_query_expr(deps: Deps, _env: Env, expr: QueryExpr) -> Result<Stri
         SystemResult::Ok(ContractResult::Ok(value)) => Ok(value),
     }?;
 
+    // raw queries of missing keys return no data
+    if let QueryRequest::Wasm(WasmQuery::Raw { key, .. }) = &expr.query {
+        if query_result_binary.is_empty() {
+            return Err(ContractError::HydrationError {
+                msg: format!("Raw query key {} not found.", key),
+            });
+        }
+    }
+
+    // Stargate responses are protobuf, decoded to JSON for selectors
+    if let QueryRequest::Stargate { path, .. } = &expr.query {
+        let value = decode_stargate_response(path, query_result_binary.as_slice())?;
+        return Ok(Json { value }.to_string());
+    }
+
     let query_result_str = String::from_vec(base64::decode(query_result_binary.to_string())?)?;
 
     Ok(query_result_str)
@@ -637,6 +1046,18 @@ pub fn resolve_ref_bool(
             })?;
             str::parse::<bool>(&val)?
         }
+        Variable::History(h) => {
+            let val = h.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Bool History value not found: {}", h.name),
+            })?;
+            str::parse::<bool>(&val)?
+        }
+        Variable::RawQuery(r) => {
+            let val = r.clone().value.ok_or(ContractError::ConditionError {
+                msg: format!("Bool RawQuery value not found: {}", r.name),
+            })?;
+            str::parse::<bool>(&val)?
+        }
     };
     Ok(res)
 }
@@ -665,13 +1086,19 @@ pub fn resolve_query_expr_int(
     deps: Deps,
     env: Env,
     expr: QueryExpr,
-) -> Result<i128, ContractError> {
+) -> Result<Int256, ContractError> {
     let query_result_str = resolve_query_expr(deps, env, expr.clone())?;
     let value = Decoder::default(query_result_str.chars()).decode()?;
     let r = Ref::new(&value);
     let resolved = resolve_path(r, expr.selector)?;
 
-    resolved.i128().ok_or(ContractError::DecodeError {})
+    // large values are encoded as strings like Uint256
+    match resolved.string() {
+        Some(value) => Ok(Int256::from_str(value)?),
+        None => Ok(Int256::from(
+            resolved.i128().ok_or(ContractError::DecodeError {})?,
+        )),
+    }
 }
 
 pub fn resolve_query_expr_decimal(
@@ -753,8 +1180,8 @@ impl Json {
     }
 }
 
-impl ToString for Json {
-    fn to_string(&self) -> String {
-        self.to_json_string(false)
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_json_string(false))
     }
 }
@@ -0,0 +1,354 @@
+use crate::util::condition::{
+    resolve_balance, resolve_block_op, resolve_decimal_op, resolve_int_op, resolve_num_op_bounds,
+    resolve_num_value_decimal, resolve_num_value_int, resolve_num_value_uint, resolve_ref_bool,
+    resolve_str_op, resolve_string_value, resolve_time_op, resolve_uint_op,
+};
+use crate::ContractError;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Decimal256, Deps, Env, Uint256};
+use resolver::condition::{
+    BalanceExpr, BlockExpr, Condition, DecimalFnOp, Expr, GenExpr, IntFnOp, NumGenExpr, NumOp,
+    StringOp, StringValue, TimeExpr,
+};
+use resolver::int256::Int256;
+use resolver::variable::Variable;
+use resolver::{ConditionTrace, ConditionTraceNode, ExprTrace, JobEnv};
+
+// Mirrors resolve_cond, but resolves every node instead of short-circuiting so the whole
+// tree can be inspected. Node results follow resolve_cond: an And / Or node takes the first
+// decisive result or error among its children.
+pub fn explain_cond(
+    deps: Deps,
+    env: Env,
+    cond: Condition,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> ConditionTrace {
+    match cond {
+        Condition::And(conds) => {
+            let traces = explain_conds(deps, env, conds, vars, job_env);
+            let result = combine(&traces, false);
+            trace(ConditionTraceNode::And(traces), result)
+        }
+        Condition::Or(conds) => {
+            let traces = explain_conds(deps, env, conds, vars, job_env);
+            let result = combine(&traces, true);
+            trace(ConditionTraceNode::Or(traces), result)
+        }
+        Condition::Not(cond) => {
+            let inner = explain_cond(deps, env, *cond, vars, job_env);
+            let result = outcome(&inner).map(|result| !result);
+            trace(ConditionTraceNode::Not(Box::new(inner)), result)
+        }
+        Condition::Try { cond, on_error } => {
+            let inner = explain_cond(deps, env, *cond, vars, job_env);
+            let result = Ok(inner.result.unwrap_or(on_error));
+            trace(
+                ConditionTraceNode::Try {
+                    cond: Box::new(inner),
+                    on_error,
+                },
+                result,
+            )
+        }
+        Condition::Expr(expr) => explain_expr(deps, env, *expr, vars, job_env),
+    }
+}
+
+fn explain_conds(
+    deps: Deps,
+    env: Env,
+    conds: impl IntoIterator<Item = Box<Condition>>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Vec<ConditionTrace> {
+    conds
+        .into_iter()
+        .map(|cond| explain_cond(deps, env.clone(), *cond, vars, job_env))
+        .collect()
+}
+
+// decisive is the child result deciding the node, true for Or and false for And
+fn combine(traces: &[ConditionTrace], decisive: bool) -> Result<bool, String> {
+    for trace in traces {
+        if let Some(error) = &trace.error {
+            return Err(error.clone());
+        }
+        if trace.result == Some(decisive) {
+            return Ok(decisive);
+        }
+    }
+
+    Ok(!decisive)
+}
+
+pub fn explain_expr(
+    deps: Deps,
+    env: Env,
+    expr: Expr,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> ConditionTrace {
+    match expr {
+        Expr::String(e) => explain_operands(
+            "string",
+            op_name(&e.op),
+            resolve_string_operands(deps, env.clone(), e, vars, job_env),
+            |_| None,
+            |left, right, op| resolve_str_op(deps, env.clone(), left, right, op),
+        ),
+        Expr::Uint(e) => explain_operands(
+            "uint",
+            op_name(&e.op),
+            resolve_uint_operands(deps, env.clone(), e, vars, job_env),
+            bound,
+            |left, right, op| resolve_uint_op(deps, env.clone(), left, right, op),
+        ),
+        Expr::Int(e) => explain_operands(
+            "int",
+            op_name(&e.op),
+            resolve_int_operands(deps, env.clone(), e, vars, job_env),
+            bound,
+            |left, right, op| resolve_int_op(deps, env.clone(), left, right, op),
+        ),
+        Expr::Decimal(e) => explain_operands(
+            "decimal",
+            op_name(&e.op),
+            resolve_decimal_operands(deps, env.clone(), e, vars, job_env),
+            bound,
+            |left, right, op| resolve_decimal_op(deps, env.clone(), left, right, op),
+        ),
+        Expr::Timestamp(e) => explain_operands(
+            "timestamp",
+            match &e {
+                TimeExpr::Num(e) => op_name(&e.op),
+                TimeExpr::Fixed(e) => op_name(&e.op),
+            },
+            resolve_timestamp_operands(deps, env.clone(), e, vars, job_env),
+            bound,
+            |left, right, op| resolve_uint_op(deps, env.clone(), left, right, op),
+        ),
+        Expr::BlockHeight(e) => explain_operands(
+            "block_height",
+            op_name(&e.op),
+            resolve_block_operands(deps, env.clone(), e),
+            bound,
+            |left, right, op| resolve_block_op(deps, env.clone(), left, right, op),
+        ),
+        Expr::Bool(r) => {
+            let result = resolve_ref_bool(deps, env, r, vars);
+            let node = ConditionTraceNode::Expr(ExprTrace {
+                kind: "bool".to_string(),
+                left: result.as_ref().ok().map(ToString::to_string),
+                op: "ref".to_string(),
+                right: None,
+                upper: None,
+            });
+            trace(node, result.map_err(|e| e.to_string()))
+        }
+        Expr::Balance(e) => explain_operands(
+            "balance",
+            op_name(&e.op),
+            resolve_balance_operands(deps, env.clone(), e, vars, job_env),
+            bound,
+            |left, right, op| resolve_uint_op(deps, env.clone(), left, right, op),
+        ),
+    }
+}
+
+// Operands of an expression, each resolved on its own so explain_expr can report all of them.
+// resolve_cond resolves them sequentially instead, stopping at the first error.
+struct Operands<T, Op> {
+    left: Result<T, ContractError>,
+    right: Result<T, ContractError>,
+    op: Result<Op, ContractError>,
+}
+
+impl<T, Op> Operands<T, Op> {
+    // operands fail in resolution order, left first
+    fn resolve(
+        self,
+        resolve_op: impl FnOnce(T, T, Op) -> Result<bool, ContractError>,
+    ) -> Result<bool, ContractError> {
+        resolve_op(self.left?, self.right?, self.op?)
+    }
+}
+
+fn resolve_int_operands(
+    deps: Deps,
+    env: Env,
+    expr: NumGenExpr<Int256, IntFnOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Operands<Int256, NumOp<Int256>> {
+    Operands {
+        left: resolve_num_value_int(deps, env.clone(), expr.left, vars, job_env),
+        right: resolve_num_value_int(deps, env.clone(), expr.right, vars, job_env),
+        op: resolve_num_op_bounds(expr.op, |upper| {
+            resolve_num_value_int(deps, env, upper, vars, job_env)
+        }),
+    }
+}
+
+fn resolve_uint_operands(
+    deps: Deps,
+    env: Env,
+    expr: NumGenExpr<Uint256, IntFnOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Operands<Uint256, NumOp<Uint256>> {
+    Operands {
+        left: resolve_num_value_uint(deps, env.clone(), expr.left, vars, job_env),
+        right: resolve_num_value_uint(deps, env.clone(), expr.right, vars, job_env),
+        op: resolve_num_op_bounds(expr.op, |upper| {
+            resolve_num_value_uint(deps, env, upper, vars, job_env)
+        }),
+    }
+}
+
+fn resolve_decimal_operands(
+    deps: Deps,
+    env: Env,
+    expr: NumGenExpr<Decimal256, DecimalFnOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Operands<Decimal256, NumOp<Decimal256>> {
+    Operands {
+        left: resolve_num_value_decimal(deps, env.clone(), expr.left, vars, job_env),
+        right: resolve_num_value_decimal(deps, env.clone(), expr.right, vars, job_env),
+        op: resolve_num_op_bounds(expr.op, |upper| {
+            resolve_num_value_decimal(deps, env, upper, vars, job_env)
+        }),
+    }
+}
+
+// Block time as left, the legacy fixed comparator being a Uint comparison as well
+fn resolve_timestamp_operands(
+    deps: Deps,
+    env: Env,
+    expr: TimeExpr,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Operands<Uint256, NumOp<Uint256>> {
+    let left = Ok(Uint256::from(env.block.time.seconds()));
+
+    match expr {
+        TimeExpr::Num(expr) => Operands {
+            left,
+            right: resolve_num_value_uint(deps, env.clone(), expr.value, vars, job_env),
+            op: resolve_num_op_bounds(expr.op, |upper| {
+                resolve_num_value_uint(deps, env, upper, vars, job_env)
+            }),
+        },
+        TimeExpr::Fixed(expr) => Operands {
+            left,
+            right: Ok(expr.comparator.u64().into()),
+            op: Ok(resolve_time_op(expr.op)),
+        },
+    }
+}
+
+fn resolve_block_operands(_deps: Deps, env: Env, expr: BlockExpr) -> Operands<u64, NumOp<u64>> {
+    Operands {
+        left: Ok(env.block.height),
+        right: Ok(expr.comparator.u64()),
+        op: resolve_num_op_bounds(expr.op, |upper| Ok(upper.u64())),
+    }
+}
+
+// Balance of address as left
+fn resolve_balance_operands(
+    deps: Deps,
+    env: Env,
+    expr: BalanceExpr,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Operands<Uint256, NumOp<Uint256>> {
+    Operands {
+        left: resolve_string_value(deps, env.clone(), expr.address, vars, job_env)
+            .and_then(|address| resolve_balance(deps, address, expr.asset)),
+        right: resolve_num_value_uint(deps, env.clone(), expr.value, vars, job_env),
+        op: resolve_num_op_bounds(expr.op, |upper| {
+            resolve_num_value_uint(deps, env, upper, vars, job_env)
+        }),
+    }
+}
+
+fn resolve_string_operands(
+    deps: Deps,
+    env: Env,
+    expr: GenExpr<StringValue<String>, StringOp>,
+    vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Operands<String, StringOp> {
+    Operands {
+        left: resolve_string_value(deps, env.clone(), expr.left, vars, job_env),
+        right: resolve_string_value(deps, env, expr.right, vars, job_env),
+        op: Ok(expr.op),
+    }
+}
+
+fn explain_operands<T: ToString, Op>(
+    kind: &str,
+    op_name: String,
+    operands: Operands<T, Op>,
+    upper: impl FnOnce(&Op) -> Option<String>,
+    resolve_op: impl FnOnce(T, T, Op) -> Result<bool, ContractError>,
+) -> ConditionTrace {
+    let node = ConditionTraceNode::Expr(ExprTrace {
+        kind: kind.to_string(),
+        left: operands.left.as_ref().ok().map(ToString::to_string),
+        op: op_name,
+        right: operands.right.as_ref().ok().map(ToString::to_string),
+        upper: operands.op.as_ref().ok().and_then(upper),
+    });
+
+    trace(
+        node,
+        operands.resolve(resolve_op).map_err(|e| e.to_string()),
+    )
+}
+
+// Resolved upper bound of NumOp::Between / NumOp::NotBetween
+fn bound<T: ToString>(op: &NumOp<T>) -> Option<String> {
+    match op {
+        NumOp::Between { upper } | NumOp::NotBetween { upper } => Some(upper.to_string()),
+        _ => None,
+    }
+}
+
+fn trace(node: ConditionTraceNode, result: Result<bool, String>) -> ConditionTrace {
+    match result {
+        Ok(result) => ConditionTrace {
+            node,
+            result: Some(result),
+            error: None,
+        },
+        Err(error) => ConditionTrace {
+            node,
+            result: None,
+            error: Some(error),
+        },
+    }
+}
+
+fn outcome(trace: &ConditionTrace) -> Result<bool, String> {
+    match (trace.result, &trace.error) {
+        (Some(result), _) => Ok(result),
+        (None, error) => Err(error.clone().unwrap_or_default()),
+    }
+}
+
+// Name of the op as serialized, e.g. "gte" or "between" for {"between":{"upper":...}}
+fn op_name<T: Serialize>(op: &T) -> String {
+    serde_json_wasm::to_string(op)
+        .map(|op| {
+            let name = op.trim_start_matches('{');
+            name.split(':')
+                .next()
+                .unwrap_or(name)
+                .trim_matches('"')
+                .to_string()
+        })
+        .unwrap_or_default()
+}
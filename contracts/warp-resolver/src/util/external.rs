@@ -0,0 +1,115 @@
+use crate::ContractError;
+use controller::job::{ExternalInput, ExternalInputSignDoc};
+use cosmwasm_std::{to_vec, Api, Env};
+use resolver::variable::{ExternalExpr, TrustedSigner};
+use resolver::JobEnv;
+use sha2::{Digest, Sha256};
+
+const MAX_CLOCK_SKEW_SECONDS: u64 = 60;
+
+// Inputs of external vars with trusted signers must be signed by one of them for this job, and
+// be fresh when max_age_seconds is set. Vars without trusted signers accept any input.
+pub fn verify_external_input(
+    api: &dyn Api,
+    env: &Env,
+    expr: &ExternalExpr,
+    input: &ExternalInput,
+    job_env: &JobEnv,
+) -> Result<(), ContractError> {
+    let signers = match &expr.trusted_signers {
+        Some(signers) if !signers.is_empty() => signers,
+        _ => return Ok(()),
+    };
+
+    let (signature, timestamp) = match (&input.signature, input.timestamp) {
+        (Some(signature), Some(timestamp)) => (signature, timestamp),
+        _ => {
+            return Err(external_error(format!(
+                "External input {} is not signed.",
+                input.name
+            )))
+        }
+    };
+    let job_id = job_env.job_id.ok_or_else(|| {
+        external_error(format!(
+            "Job id is required to verify external input {}.",
+            input.name
+        ))
+    })?;
+    let controller_addr = job_env.controller_addr.clone().ok_or_else(|| {
+        external_error(format!(
+            "Controller addr is required to verify external input {}.",
+            input.name
+        ))
+    })?;
+
+    // signers' clocks may run slightly ahead of the block time, but no further
+    if timestamp.u64()
+        > env
+            .block
+            .time
+            .seconds()
+            .saturating_add(MAX_CLOCK_SKEW_SECONDS)
+    {
+        return Err(external_error(format!(
+            "External input {} is signed in the future.",
+            input.name
+        )));
+    }
+
+    if let Some(max_age) = expr.max_age_seconds {
+        if env.block.time.seconds().saturating_sub(timestamp.u64()) > max_age {
+            return Err(external_error(format!(
+                "External input {} is stale.",
+                input.name
+            )));
+        }
+    }
+
+    let sign_doc = to_vec(&ExternalInputSignDoc {
+        chain_id: env.block.chain_id.clone(),
+        controller_addr,
+        job_id,
+        name: input.name.clone(),
+        value: input.input.clone(),
+        timestamp,
+    })?;
+    let hash = Sha256::digest(&sign_doc);
+
+    // malformed keys or signatures count as not signed by that signer
+    let signed = signers.iter().any(|signer| match signer {
+        TrustedSigner::Secp256k1(pubkey) => api
+            .secp256k1_verify(&hash, signature, pubkey)
+            .unwrap_or(false),
+        TrustedSigner::Ed25519(pubkey) => api
+            .ed25519_verify(&sign_doc, signature, pubkey)
+            .unwrap_or(false),
+    });
+
+    if !signed {
+        return Err(external_error(format!(
+            "External input {} is not signed by a trusted signer.",
+            input.name
+        )));
+    }
+
+    Ok(())
+}
+
+// Public keys are checked by length only, secp256k1 keys may be compressed or not
+pub fn trusted_signers_valid(expr: &ExternalExpr) -> bool {
+    match &expr.trusted_signers {
+        None => expr.max_age_seconds.is_none(),
+        Some(signers) => {
+            !signers.is_empty()
+                && signers.iter().all(|signer| match signer {
+                    TrustedSigner::Secp256k1(pubkey) => matches!(pubkey.len(), 33 | 65),
+                    TrustedSigner::Ed25519(pubkey) => pubkey.len() == 32,
+                })
+        }
+    }
+}
+
+fn external_error(msg: String) -> ContractError {
+    ContractError::HydrationError { msg }
+}
@@ -0,0 +1,96 @@
+use crate::util::variable::get_var;
+use crate::ContractError;
+use cosmwasm_std::Decimal256;
+use resolver::condition::{HistoryFnOp, NumHistoryValue};
+use resolver::variable::{HistoryVariable, Variable};
+use std::str::FromStr;
+
+// Keeps jobs small enough to store and resolve; moving averages rarely need more samples
+pub const MAX_HISTORY_CAPACITY: u32 = 100;
+
+// Appends the current value, dropping the oldest values beyond capacity
+pub fn record_history(var: &mut HistoryVariable) {
+    if let Some(value) = var.value.clone() {
+        var.history.push(value);
+    }
+
+    let excess = var.history.len().saturating_sub(var.capacity as usize);
+    var.history.drain(..excess);
+}
+
+pub fn resolve_history_value(
+    value: NumHistoryValue,
+    vars: &Vec<Variable>,
+) -> Result<Decimal256, ContractError> {
+    let var = match get_var(value.var.clone(), vars)? {
+        Variable::History(v) => v,
+        _ => {
+            return Err(history_error(format!(
+                "Variable {} is not a history variable.",
+                value.var
+            )))
+        }
+    };
+
+    let values = var
+        .history
+        .iter()
+        .map(|value| Decimal256::from_str(value))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    resolve_history_fn(&values, value.op)
+        .map_err(|e| history_error(format!("History of {}: {}", var.name, e)))
+}
+
+pub fn resolve_history_fn(values: &[Decimal256], op: HistoryFnOp) -> Result<Decimal256, String> {
+    let latest = *values.last().ok_or("no values recorded yet.")?;
+    let count = Decimal256::from_ratio(values.len() as u128, 1u128);
+
+    let result = match op {
+        HistoryFnOp::Avg => sum(values)? / count,
+        HistoryFnOp::Min => values.iter().copied().fold(latest, Decimal256::min),
+        HistoryFnOp::Max => values.iter().copied().fold(latest, Decimal256::max),
+        HistoryFnOp::Last(n) => {
+            if n == 0 || n as usize > values.len() {
+                return Err(format!(
+                    "Last({}) is out of range for {} values.",
+                    n,
+                    values.len()
+                ));
+            }
+            values[values.len() - n as usize]
+        }
+        HistoryFnOp::Ema(alpha) => {
+            if alpha.is_zero() || alpha > Decimal256::one() {
+                return Err(format!("Ema alpha {} must be in (0, 1].", alpha));
+            }
+            values.iter().skip(1).fold(values[0], |ema, value| {
+                alpha * *value + (Decimal256::one() - alpha) * ema
+            })
+        }
+        HistoryFnOp::StdDev => {
+            let mean = sum(values)? / count;
+            // Decimal256 is unsigned, so deviations are taken as absolute differences
+            let squares = values
+                .iter()
+                .map(|value| {
+                    let deviation = value.abs_diff(mean);
+                    deviation.checked_mul(deviation).map_err(|e| e.to_string())
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            (sum(&squares)? / count).sqrt()
+        }
+    };
+
+    Ok(result)
+}
+
+fn sum(values: &[Decimal256]) -> Result<Decimal256, String> {
+    values.iter().try_fold(Decimal256::zero(), |sum, value| {
+        sum.checked_add(*value).map_err(|e| e.to_string())
+    })
+}
+
+fn history_error(msg: String) -> ContractError {
+    ContractError::ConditionError { msg }
+}
@@ -0,0 +1,160 @@
+use crate::ContractError;
+use cosmwasm_std::{Decimal256, Uint128, Uint256};
+use resolver::condition::RoundingMode;
+
+// Fixed point math backing DecimalFnOp and NumExprOp::Pow. Decimal256 is unsigned with 18
+// decimal places, so results below zero (e.g. ln of values below one) are rejected and
+// series are evaluated until their terms underflow the precision.
+const LN_2: Decimal256 = Decimal256::new(Uint256::from_u128(693_147_180_559_945_309));
+const LN_10: Decimal256 = Decimal256::new(Uint256::from_u128(2_302_585_092_994_045_684));
+
+pub fn ln(x: Decimal256) -> Result<Decimal256, ContractError> {
+    if x < Decimal256::one() {
+        return Err(math_error(format!("Decimal ln of {} is negative.", x)));
+    }
+
+    // x = m * 2^k with m in [1, 2)
+    let two = Decimal256::from_ratio(2u32, 1u32);
+    let mut m = x;
+    let mut k = 0u32;
+    while m >= two {
+        m /= two;
+        k += 1;
+    }
+
+    // ln(m) = 2 * (z + z^3 / 3 + z^5 / 5 + ...), z = (m - 1) / (m + 1) in [0, 1/3)
+    let z = (m - Decimal256::one()) / (m + Decimal256::one());
+    let z2 = z * z;
+    let mut term = z;
+    let mut sum = Decimal256::zero();
+    let mut n = 1u32;
+    while !term.is_zero() {
+        sum += term / Decimal256::from_ratio(n, 1u32);
+        term *= z2;
+        n += 2;
+    }
+
+    Ok(sum
+        .checked_mul(Decimal256::from_ratio(2u32, 1u32))?
+        .checked_add(LN_2.checked_mul(Decimal256::from_ratio(k, 1u32))?)?)
+}
+
+pub fn log10(x: Decimal256) -> Result<Decimal256, ContractError> {
+    Ok(ln(x)? / LN_10)
+}
+
+pub fn exp(x: Decimal256) -> Result<Decimal256, ContractError> {
+    // exp(x) = 2^k * exp(r), r = x - k * ln(2) in [0, ln(2))
+    let k = to_u32(x.atomics() / LN_2.atomics())
+        .map_err(|_| math_error(format!("Decimal exp of {} overflows.", x)))?;
+    let r = x.checked_sub(LN_2.checked_mul(Decimal256::from_ratio(k, 1u32))?)?;
+
+    let mut term = Decimal256::one();
+    let mut sum = Decimal256::one();
+    let mut n = 1u32;
+    loop {
+        term = term * r / Decimal256::from_ratio(n, 1u32);
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+        n += 1;
+    }
+
+    Ok(sum.checked_mul(Decimal256::from_ratio(2u32, 1u32).checked_pow(k)?)?)
+}
+
+pub fn pow(base: Decimal256, exponent: Decimal256) -> Result<Decimal256, ContractError> {
+    if exponent.floor() == exponent {
+        let exponent = to_u32(exponent.atomics() / Decimal256::one().atomics())?;
+        return Ok(base.checked_pow(exponent)?);
+    }
+
+    if base.is_zero() {
+        return Ok(Decimal256::zero());
+    }
+
+    // base^exponent = exp(exponent * ln(base)), inverted for bases below one to keep ln positive
+    if base >= Decimal256::one() {
+        exp(exponent.checked_mul(ln(base)?)?)
+    } else {
+        let inverted = exp(exponent.checked_mul(ln(Decimal256::one() / base)?)?)?;
+        Ok(Decimal256::one() / inverted)
+    }
+}
+
+pub fn round(
+    x: Decimal256,
+    precision: u32,
+    mode: RoundingMode,
+) -> Result<Decimal256, ContractError> {
+    if precision >= Decimal256::DECIMAL_PLACES {
+        return Ok(x);
+    }
+
+    let factor = Uint256::from(10u32).checked_pow(Decimal256::DECIMAL_PLACES - precision)?;
+    let quotient = x.atomics() / factor;
+    let remainder = x.atomics() % factor;
+    let twice_remainder = remainder * Uint256::from(2u32);
+
+    let round_up = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => !remainder.is_zero(),
+        RoundingMode::HalfUp => twice_remainder >= factor,
+        RoundingMode::HalfDown => twice_remainder > factor,
+        RoundingMode::HalfEven => {
+            twice_remainder > factor
+                || (twice_remainder == factor && quotient % Uint256::from(2u32) == Uint256::one())
+        }
+    };
+
+    let quotient = if round_up {
+        quotient.checked_add(Uint256::one())?
+    } else {
+        quotient
+    };
+
+    Ok(Decimal256::new(quotient.checked_mul(factor)?))
+}
+
+pub fn to_decimal(value: Uint256, scale: u32) -> Result<Decimal256, ContractError> {
+    Decimal256::from_atomics(value, scale).map_err(|_| {
+        math_error(format!(
+            "Uint {} with scale {} is out of range.",
+            value, scale
+        ))
+    })
+}
+
+pub fn to_uint(
+    value: Decimal256,
+    scale: u32,
+    rounding: RoundingMode,
+) -> Result<Uint256, ContractError> {
+    let multiplier = Uint256::from(10u32)
+        .checked_pow(scale)
+        .ok()
+        .and_then(|multiplier| Decimal256::from_atomics(multiplier, 0).ok())
+        .ok_or_else(|| math_error(format!("Scale {} is out of range.", scale)))?;
+    let scaled = round(value.checked_mul(multiplier)?, 0, rounding)?;
+
+    Ok(scaled.atomics() / Decimal256::one().atomics())
+}
+
+pub fn to_u32(value: Uint256) -> Result<u32, ContractError> {
+    Uint128::try_from(value)
+        .ok()
+        .and_then(|value| u32::try_from(value.u128()).ok())
+        .ok_or_else(|| math_error(format!("Exponent {} is too large.", value)))
+}
+
+pub fn to_u64(value: Uint256) -> Result<u64, ContractError> {
+    Uint128::try_from(value)
+        .ok()
+        .and_then(|value| u64::try_from(value.u128()).ok())
+        .ok_or_else(|| math_error(format!("Value {} is too large.", value)))
+}
+
+fn math_error(msg: String) -> ContractError {
+    ContractError::ConditionError { msg }
+}
@@ -1,3 +1,14 @@
+pub mod cache;
+pub mod calendar;
 pub mod condition;
+pub mod explain;
+pub mod external;
+pub mod history;
+pub mod math;
 pub mod path;
+pub mod placeholder;
+pub mod proto;
+pub mod raw;
+pub mod regex;
+pub mod stargate;
 pub mod variable;
@@ -1,79 +1,442 @@
 use crate::ContractError;
-use cosmwasm_std::StdError;
+use cosmwasm_std::Decimal256;
 use json_codec_wasm::ast::Ref;
+use json_codec_wasm::Json;
+use std::cmp::Ordering;
+use std::str::FromStr;
 
-// supports custom jsonpath-like expression with options for indexing
-// - fields - $.field
-// - array entries - $.field[0]
-// - multiple nested fields / entries - $.field1.field2[0].field3
+// supports a jsonpath subset
+// - root - $
+// - fields - $.field, $['field.with.dots'], $["field"]
+// - array entries - $.field[0], counted from the end - $.field[-1]
+// - wildcards over array entries / object values - $.field[*], $.field.*
+// - filters over array entries / object values - $.field[?(@.denom == 'uluna')], $.field[?(@.amount)]
+//   with ==, !=, <, <=, >, >= against strings, numbers, true, false and null
+// - any chain of the above - $.pools[0].assets[-1].amount
+// wildcards and filters may select several nodes, the selector must end up matching exactly one
 pub fn resolve_path(r: Ref, path: String) -> Result<Ref, ContractError> {
-    let mut obj = r;
-    let mut curr = String::new();
-    let mut idx = 0;
+    let segments = parse_path(&path)?;
+    let definite = segments
+        .iter()
+        .all(|segment| matches!(segment, Segment::Key(_) | Segment::Index(_)));
 
-    while idx < path.len() {
-        let co = path.chars().nth(idx);
+    let mut nodes = vec![r];
+    for segment in &segments {
+        nodes = nodes
+            .into_iter()
+            .flat_map(|node| select(node, segment))
+            .collect();
+    }
 
-        if let Some(c) = co {
-            match c {
-                '$' => {
-                    idx += 1;
-                }
-                '[' => {
-                    // field[0] case
-                    if !curr.is_empty() {
-                        obj = obj.get(curr);
-                        curr = String::new();
-                    }
-
-                    let (_, array_idx) = read_array_index(path, idx + 1)?;
-                    obj = obj.at(array_idx);
-                    break;
+    // a definite path always yields a single node, possibly missing
+    if !definite {
+        nodes.retain(|node| node.value().is_some());
+    }
+
+    match nodes.len() {
+        1 => Ok(nodes.remove(0)),
+        n => Err(ContractError::SelectorError {
+            msg: format!("Selector {} matched {} nodes, expected 1.", path, n),
+        }),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    Filter(Filter),
+}
+
+#[derive(Debug, PartialEq)]
+struct Filter {
+    // relative path from @, only keys and indexes
+    path: Vec<Segment>,
+    // None checks for existence
+    comparison: Option<(FilterOp, Literal)>,
+}
+
+#[derive(Debug, PartialEq)]
+enum FilterOp {
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+#[derive(Debug, PartialEq)]
+enum Literal {
+    String(String),
+    Number(String),
+    Bool(bool),
+    Null,
+}
+
+fn select<'r>(node: Ref<'r>, segment: &Segment) -> Vec<Ref<'r>> {
+    match segment {
+        Segment::Key(key) => vec![node.get(key.as_str())],
+        Segment::Index(idx) => vec![at(&node, *idx)],
+        Segment::Wildcard => children(node),
+        Segment::Filter(filter) => children(node)
+            .into_iter()
+            .filter(|child| matches_filter(child, filter))
+            .collect(),
+    }
+}
+
+fn at<'r>(node: &Ref<'r>, idx: i64) -> Ref<'r> {
+    let len = node.slice().map(|arr| arr.len()).unwrap_or(0) as i64;
+    let idx = if idx < 0 { len + idx } else { idx };
+
+    // out of range indexes resolve to a missing node
+    match usize::try_from(idx) {
+        Ok(idx) => node.at(idx),
+        Err(_) => node.at(usize::MAX),
+    }
+}
+
+fn children(node: Ref) -> Vec<Ref> {
+    match node.value() {
+        Some(Json::Array(arr)) => (0..arr.len()).map(|idx| node.at(idx)).collect(),
+        Some(Json::Object(obj)) => {
+            // sorted for a deterministic order across nodes
+            let mut keys: Vec<&String> = obj.keys().collect();
+            keys.sort();
+            keys.into_iter().map(|key| node.get(key.as_str())).collect()
+        }
+        _ => vec![],
+    }
+}
+
+fn matches_filter(node: &Ref, filter: &Filter) -> bool {
+    let mut target = match filter.path.first() {
+        Some(segment) => step(node, segment),
+        None => return compare(node, filter),
+    };
+    for segment in &filter.path[1..] {
+        target = step(&target, segment);
+    }
+
+    compare(&target, filter)
+}
+
+fn step<'r>(node: &Ref<'r>, segment: &Segment) -> Ref<'r> {
+    match segment {
+        Segment::Key(key) => node.get(key.as_str()),
+        Segment::Index(idx) => at(node, *idx),
+        // the parser only allows keys and indexes in filter paths
+        _ => node.at(usize::MAX),
+    }
+}
+
+fn compare(node: &Ref, filter: &Filter) -> bool {
+    let value = match node.value() {
+        Some(value) => value,
+        None => return false,
+    };
+
+    let (op, literal) = match &filter.comparison {
+        Some(comparison) => comparison,
+        None => return true,
+    };
+
+    let ordering = match (value, literal) {
+        (Json::Null, Literal::Null) => Some(Ordering::Equal),
+        (Json::Bool(a), Literal::Bool(b)) => Some(a.cmp(b)),
+        // numeric strings are compared as numbers, as most cosmos amounts are strings
+        (_, Literal::Number(b)) => match (json_number(value), Number::parse(b)) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => None,
+        },
+        (Json::String(a), Literal::String(b)) => Some(a.as_str().cmp(b.as_str())),
+        _ => None,
+    };
+
+    match (op, ordering) {
+        (FilterOp::Neq, None) => true,
+        (_, None) => false,
+        (FilterOp::Eq, Some(ordering)) => ordering == Ordering::Equal,
+        (FilterOp::Neq, Some(ordering)) => ordering != Ordering::Equal,
+        (FilterOp::Lt, Some(ordering)) => ordering == Ordering::Less,
+        (FilterOp::Lte, Some(ordering)) => ordering != Ordering::Greater,
+        (FilterOp::Gt, Some(ordering)) => ordering == Ordering::Greater,
+        (FilterOp::Gte, Some(ordering)) => ordering != Ordering::Less,
+    }
+}
+
+fn json_number(value: &Json) -> Option<Number> {
+    match value {
+        Json::I128(i) => Number::parse(&i.to_string()),
+        Json::U128(u) => Number::parse(&u.to_string()),
+        Json::String(s) => Number::parse(s),
+        _ => None,
+    }
+}
+
+#[derive(PartialEq, Eq)]
+struct Number {
+    negative: bool,
+    magnitude: Decimal256,
+}
+
+impl Number {
+    fn parse(s: &str) -> Option<Number> {
+        let (negative, magnitude) = match s.strip_prefix('-') {
+            Some(magnitude) => (true, magnitude),
+            None => (false, s),
+        };
+        let magnitude = Decimal256::from_str(magnitude).ok()?;
+
+        Some(Number {
+            // -0 == 0
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        })
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+struct Parser<'a> {
+    path: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, ContractError> {
+    let mut parser = Parser {
+        path,
+        chars: path.chars().collect(),
+        pos: 0,
+    };
+    let mut segments = vec![];
+
+    match parser.peek() {
+        Some('$') => parser.pos += 1,
+        // relative paths start with a bare field - field.nested
+        Some('.') | Some('[') | None => {}
+        Some(_) => segments.push(Segment::Key(parser.bare_key()?)),
+    }
+
+    while let Some(c) = parser.next() {
+        match c {
+            '.' => {
+                if parser.peek() == Some('*') {
+                    parser.pos += 1;
+                    segments.push(Segment::Wildcard);
+                } else {
+                    segments.push(Segment::Key(parser.bare_key()?));
                 }
-                '.' => {
-                    // $ case
-                    if curr.is_empty() {
-                        idx += 1;
-                        continue;
-                    }
-
-                    obj = obj.get(curr);
-                    curr = String::new();
-                    idx += 1;
+            }
+            '[' => segments.push(parser.bracket()?),
+            c => return Err(parser.error(&format!("unexpected '{}'", c))),
+        }
+    }
+
+    Ok(segments)
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn error(&self, msg: &str) -> ContractError {
+        ContractError::SelectorError {
+            msg: format!("Invalid selector {} at {}: {}.", self.path, self.pos, msg),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ContractError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected '{}'", expected))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn bare_key(&mut self) -> Result<String, ContractError> {
+        let mut key = String::new();
+        while let Some(c) = self.peek() {
+            if matches!(c, '.' | '[' | ']' | '(' | ')' | '=' | '!' | '<' | '>') || c.is_whitespace()
+            {
+                break;
+            }
+            key.push(c);
+            self.pos += 1;
+        }
+
+        if key.is_empty() {
+            return Err(self.error("expected field name"));
+        }
+
+        Ok(key)
+    }
+
+    fn quoted(&mut self) -> Result<String, ContractError> {
+        let quote = self.next().ok_or_else(|| self.error("expected quote"))?;
+        let mut s = String::new();
+
+        loop {
+            match self.next() {
+                Some('\\') => match self.next() {
+                    Some(c) => s.push(c),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) if c == quote => return Ok(s),
+                Some(c) => s.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn index(&mut self) -> Result<i64, ContractError> {
+        let mut s = String::new();
+        if self.peek() == Some('-') {
+            s.push('-');
+            self.pos += 1;
+        }
+        while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+            s.push(c);
+            self.pos += 1;
+        }
+
+        s.parse::<i64>().map_err(|_| self.error("expected index"))
+    }
+
+    // contents of [...] following the opening bracket
+    fn bracket(&mut self) -> Result<Segment, ContractError> {
+        self.skip_whitespace();
+        let segment = match self.peek() {
+            Some('\'') | Some('"') => Segment::Key(self.quoted()?),
+            Some('*') => {
+                self.pos += 1;
+                Segment::Wildcard
+            }
+            Some('?') => {
+                self.pos += 1;
+                self.expect('(')?;
+                let filter = self.filter()?;
+                self.expect(')')?;
+                Segment::Filter(filter)
+            }
+            _ => Segment::Index(self.index()?),
+        };
+        self.expect(']')?;
+
+        Ok(segment)
+    }
+
+    fn filter(&mut self) -> Result<Filter, ContractError> {
+        self.expect('@')?;
+
+        let mut path = vec![];
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.pos += 1;
+                    path.push(Segment::Key(self.bare_key()?));
                 }
-                c => {
-                    curr.push(c);
-                    idx += 1;
+                Some('[') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    let segment = match self.peek() {
+                        Some('\'') | Some('"') => Segment::Key(self.quoted()?),
+                        _ => Segment::Index(self.index()?),
+                    };
+                    self.expect(']')?;
+                    path.push(segment);
                 }
+                _ => break,
             }
-        } else {
-            return Err(ContractError::ResolveError {});
         }
-    }
 
-    if !curr.is_empty() {
-        obj = obj.get(curr);
+        self.skip_whitespace();
+        if self.peek() == Some(')') {
+            return Ok(Filter {
+                path,
+                comparison: None,
+            });
+        }
+
+        let op = self.filter_op()?;
+        self.skip_whitespace();
+        let literal = self.literal()?;
+
+        Ok(Filter {
+            path,
+            comparison: Some((op, literal)),
+        })
     }
 
-    Ok(obj)
-}
+    fn filter_op(&mut self) -> Result<FilterOp, ContractError> {
+        let op = match (self.next(), self.peek()) {
+            (Some('='), Some('=')) => FilterOp::Eq,
+            (Some('!'), Some('=')) => FilterOp::Neq,
+            (Some('<'), Some('=')) => FilterOp::Lte,
+            (Some('>'), Some('=')) => FilterOp::Gte,
+            (Some('<'), _) => return Ok(FilterOp::Lt),
+            (Some('>'), _) => return Ok(FilterOp::Gt),
+            _ => return Err(self.error("expected comparison operator")),
+        };
+        self.pos += 1;
 
-fn read_array_index(path: String, from: usize) -> Result<(usize, usize), ContractError> {
-    let mut idx = from;
-    let mut curr = String::new();
-    while path
-        .chars()
-        .nth(idx)
-        .ok_or(ContractError::Unauthorized {})?
-        != ']'
-    {
-        curr.push(
-            path.chars()
-                .nth(idx)
-                .ok_or_else(|| StdError::generic_err("Array indexing error"))?,
-        );
-        idx += 1;
+        Ok(op)
     }
 
-    Ok((idx + 1, str::parse::<usize>(curr.as_str())?))
+    fn literal(&mut self) -> Result<Literal, ContractError> {
+        match self.peek() {
+            Some('\'') | Some('"') => Ok(Literal::String(self.quoted()?)),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let mut s = String::new();
+                while let Some(c) = self
+                    .peek()
+                    .filter(|c| *c == '-' || *c == '.' || c.is_ascii_digit())
+                {
+                    s.push(c);
+                    self.pos += 1;
+                }
+                match Number::parse(&s) {
+                    Some(_) => Ok(Literal::Number(s)),
+                    None => Err(self.error("invalid number")),
+                }
+            }
+            _ => match self.bare_key()?.as_str() {
+                "true" => Ok(Literal::Bool(true)),
+                "false" => Ok(Literal::Bool(false)),
+                "null" => Ok(Literal::Null),
+                _ => Err(self.error("expected literal")),
+            },
+        }
+    }
 }
@@ -0,0 +1,100 @@
+use crate::ContractError;
+use json_codec_wasm::{Decoder, Json};
+use std::collections::HashMap;
+
+const PLACEHOLDER: &str = "$warp.variable.";
+
+// Replaces "$warp.variable.<name>" strings at any depth of a JSON tree with the values of vars.
+// Base64 strings holding JSON with placeholders (e.g. wasm execute msgs) are decoded, replaced
+// and re-encoded. Substituted values are not traversed again, so they can not inject
+// placeholders. Errors name the JSON path of the failing value, e.g. $[0].wasm.execute.msg.
+pub fn substitute_placeholders(
+    json: Json,
+    values: &HashMap<String, Json>,
+    path: &str,
+) -> Result<Json, ContractError> {
+    match json {
+        Json::String(value) => substitute_string(value, values, path),
+        Json::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(idx, item)| substitute_placeholders(item, values, &format!("{}[{}]", path, idx)))
+            .collect::<Result<_, _>>()
+            .map(Json::Array),
+        Json::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| {
+                let value = substitute_placeholders(value, values, &format!("{}.{}", path, key))?;
+                Ok((key, value))
+            })
+            .collect::<Result<_, ContractError>>()
+            .map(Json::Object),
+        json => Ok(json),
+    }
+}
+
+fn substitute_string(
+    value: String,
+    values: &HashMap<String, Json>,
+    path: &str,
+) -> Result<Json, ContractError> {
+    if let Some(name) = value.strip_prefix(PLACEHOLDER) {
+        return values
+            .get(name)
+            .cloned()
+            .ok_or_else(|| placeholder_error(format!("Unknown variable {} at {}.", name, path)));
+    }
+
+    // only re-encoded when holding placeholders, leaving all other base64 values untouched
+    let decoded = match base64::decode(&value)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+    {
+        Some(decoded) if decoded.contains(PLACEHOLDER) => decoded,
+        _ => return Ok(Json::String(value)),
+    };
+    let inner = Decoder::default(decoded.chars())
+        .decode()
+        .map_err(|_| placeholder_error(format!("Invalid base64 encoded JSON at {}.", path)))?;
+    let inner = substitute_placeholders(inner, values, &format!("{}(base64)", path))?;
+
+    Ok(Json::String(base64::encode(encode_json(&inner)?)))
+}
+
+// Object keys are sorted, keeping encoded msgs deterministic
+pub fn encode_json(json: &Json) -> Result<String, ContractError> {
+    Ok(match json {
+        Json::Bool(value) => value.to_string(),
+        Json::I128(value) => value.to_string(),
+        Json::U128(value) => value.to_string(),
+        Json::String(value) => serde_json_wasm::to_string(value)?,
+        Json::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(encode_json)
+                .collect::<Result<Vec<_>, _>>()?
+                .join(",")
+        ),
+        Json::Object(object) => {
+            let mut keys: Vec<&String> = object.keys().collect();
+            keys.sort();
+            let entries = keys
+                .into_iter()
+                .map(|key| {
+                    Ok(format!(
+                        "{}:{}",
+                        serde_json_wasm::to_string(key)?,
+                        encode_json(&object[key])?
+                    ))
+                })
+                .collect::<Result<Vec<_>, ContractError>>()?;
+            format!("{{{}}}", entries.join(","))
+        }
+        Json::Null => "null".to_string(),
+    })
+}
+
+fn placeholder_error(msg: String) -> ContractError {
+    ContractError::HydrationError { msg }
+}
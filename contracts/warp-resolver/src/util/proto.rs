@@ -0,0 +1,224 @@
+use crate::ContractError;
+use cosmwasm_std::{Decimal256, Uint256};
+use json_codec_wasm::Json;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// Minimal protobuf wire format decoder turning Stargate responses into JSON, so query selectors
+// apply to them like to wasm query results. Messages are described by static field tables (see
+// stargate.rs); fields use their proto names and missing fields decode to their default value.
+pub enum FieldKind {
+    String,
+    Bytes,
+    // 64-bit integers are strings, as in the gRPC gateway JSON
+    Uint64,
+    Int64,
+    Uint32,
+    Int32,
+    Bool,
+    // cosmos sdk Dec, encoded as the string of its 18 decimal places atomics
+    Dec,
+    Enum(&'static [&'static str]),
+    Message(&'static [Field]),
+}
+
+pub struct Field {
+    pub number: u64,
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub repeated: bool,
+}
+
+pub const fn field(number: u64, name: &'static str, kind: FieldKind) -> Field {
+    Field {
+        number,
+        name,
+        kind,
+        repeated: false,
+    }
+}
+
+pub const fn repeated(number: u64, name: &'static str, kind: FieldKind) -> Field {
+    Field {
+        number,
+        name,
+        kind,
+        repeated: true,
+    }
+}
+
+enum WireValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+    Bytes(&'a [u8]),
+}
+
+pub fn decode_message(bytes: &[u8], fields: &[Field]) -> Result<Json, ContractError> {
+    let mut values: HashMap<u64, Vec<Json>> = HashMap::new();
+    let mut reader = Reader { bytes, pos: 0 };
+
+    while !reader.done() {
+        let key = reader.varint()?;
+        let value = match key & 7 {
+            0 => WireValue::Varint(reader.varint()?),
+            1 => WireValue::Fixed64(u64::from_le_bytes(reader.array()?)),
+            2 => {
+                let len = reader.varint()?;
+                WireValue::Bytes(reader.take(len)?)
+            }
+            5 => WireValue::Fixed32(u32::from_le_bytes(reader.array()?)),
+            wire_type => return Err(proto_error(format!("Unsupported wire type {}.", wire_type))),
+        };
+
+        // unknown fields are skipped, keeping decoding compatible with newer chain versions
+        let field = match fields.iter().find(|field| field.number == key >> 3) {
+            None => continue,
+            Some(field) => field,
+        };
+        let decoded = values.entry(field.number).or_default();
+        match value {
+            // repeated scalars are packed into a single length delimited value
+            WireValue::Bytes(packed) if field.repeated && is_packable(&field.kind) => {
+                let mut packed = Reader {
+                    bytes: packed,
+                    pos: 0,
+                };
+                while !packed.done() {
+                    decoded.push(decode_varint(packed.varint()?, field)?);
+                }
+            }
+            value => decoded.push(decode_value(value, field)?),
+        }
+    }
+
+    let mut object = HashMap::new();
+    for field in fields {
+        let mut decoded = values.remove(&field.number).unwrap_or_default();
+        let value = if field.repeated {
+            Json::Array(decoded)
+        } else {
+            // the last value wins for non repeated fields
+            decoded.pop().unwrap_or_else(|| default_value(&field.kind))
+        };
+        object.insert(field.name.to_string(), value);
+    }
+
+    Ok(Json::Object(object))
+}
+
+fn decode_value(value: WireValue, field: &Field) -> Result<Json, ContractError> {
+    match value {
+        WireValue::Varint(value) => decode_varint(value, field),
+        WireValue::Fixed64(value) => decode_varint(value, field),
+        WireValue::Fixed32(value) => decode_varint(value as u64, field),
+        WireValue::Bytes(bytes) => match &field.kind {
+            FieldKind::String => Ok(Json::String(utf8(bytes)?)),
+            FieldKind::Bytes => Ok(Json::String(base64::encode(bytes))),
+            FieldKind::Dec => {
+                let atomics = Uint256::from_str(&utf8(bytes)?)?;
+                Ok(Json::String(Decimal256::new(atomics).to_string()))
+            }
+            FieldKind::Message(fields) => decode_message(bytes, fields),
+            _ => Err(wire_type_error(field)),
+        },
+    }
+}
+
+fn decode_varint(value: u64, field: &Field) -> Result<Json, ContractError> {
+    match &field.kind {
+        FieldKind::Uint64 => Ok(Json::String(value.to_string())),
+        FieldKind::Int64 => Ok(Json::String((value as i64).to_string())),
+        FieldKind::Uint32 => Ok(Json::U128(value as u32 as u128)),
+        FieldKind::Int32 => Ok(Json::I128(value as i32 as i128)),
+        FieldKind::Bool => Ok(Json::Bool(value != 0)),
+        FieldKind::Enum(names) => Ok(match names.get(value as usize) {
+            Some(name) => Json::String(name.to_string()),
+            None => Json::U128(value as u128),
+        }),
+        _ => Err(wire_type_error(field)),
+    }
+}
+
+fn default_value(kind: &FieldKind) -> Json {
+    match kind {
+        FieldKind::String | FieldKind::Bytes => Json::String("".to_string()),
+        FieldKind::Uint64 | FieldKind::Int64 => Json::String("0".to_string()),
+        FieldKind::Uint32 => Json::U128(0),
+        FieldKind::Int32 => Json::I128(0),
+        FieldKind::Bool => Json::Bool(false),
+        FieldKind::Dec => Json::String(Decimal256::zero().to_string()),
+        FieldKind::Enum(names) => match names.first() {
+            Some(name) => Json::String(name.to_string()),
+            None => Json::U128(0),
+        },
+        FieldKind::Message(_) => Json::Null,
+    }
+}
+
+fn is_packable(kind: &FieldKind) -> bool {
+    !matches!(
+        kind,
+        FieldKind::String | FieldKind::Bytes | FieldKind::Dec | FieldKind::Message(_)
+    )
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn done(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn varint(&mut self) -> Result<u64, ContractError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .bytes
+                .get(self.pos)
+                .ok_or_else(|| proto_error("Truncated varint.".to_string()))?;
+            self.pos += 1;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(proto_error("Varint is too long.".to_string()))
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], ContractError> {
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.pos.checked_add(len))
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| proto_error("Truncated length delimited field.".to_string()))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ContractError> {
+        let bytes = self.take(N as u64)?;
+        let mut array = [0u8; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+}
+
+fn utf8(bytes: &[u8]) -> Result<String, ContractError> {
+    String::from_utf8(bytes.to_vec()).map_err(|_| proto_error("Invalid UTF8 string.".to_string()))
+}
+
+fn wire_type_error(field: &Field) -> ContractError {
+    proto_error(format!("Unexpected wire type for field {}.", field.name))
+}
+
+fn proto_error(msg: String) -> ContractError {
+    ContractError::HydrationError {
+        msg: format!("Protobuf decoding: {}", msg),
+    }
+}
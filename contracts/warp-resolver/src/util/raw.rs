@@ -0,0 +1,52 @@
+use crate::util::variable::replace_in_string;
+use crate::ContractError;
+use cosmwasm_std::{Binary, Empty, QueryRequest, WasmQuery};
+use cw_storage_plus::Path;
+use resolver::variable::{QueryExpr, RawKey, RawQueryExpr, Variable};
+
+// Builds the WasmQuery::Raw of a raw query var. References in the address and key parts are
+// replaced with vars first, as the encoded key can not be searched for them afterwards.
+pub fn raw_query_expr(expr: &RawQueryExpr, vars: &[Variable]) -> Result<QueryExpr, ContractError> {
+    let contract_addr = replace_in_string(expr.contract_addr.clone(), vars)?;
+    let keys = expr
+        .keys
+        .iter()
+        .map(|key| raw_key_bytes(key, vars))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(QueryExpr {
+        selector: expr.selector.clone(),
+        query: QueryRequest::Wasm(WasmQuery::Raw {
+            contract_addr,
+            key: storage_key(expr.namespace.as_bytes(), &keys),
+        }),
+    })
+}
+
+// Item keys are the namespace itself, Map keys follow cw-storage-plus: the namespace and all
+// key parts but the last are length prefixed
+pub fn storage_key(namespace: &[u8], keys: &[Vec<u8>]) -> Binary {
+    if keys.is_empty() {
+        return Binary::from(namespace);
+    }
+
+    let keys: Vec<&[u8]> = keys.iter().map(|key| key.as_slice()).collect();
+    Binary::from(Path::<Empty>::new(namespace, &keys).to_vec())
+}
+
+fn raw_key_bytes(key: &RawKey, vars: &[Variable]) -> Result<Vec<u8>, ContractError> {
+    match key {
+        RawKey::String(value) | RawKey::Addr(value) => {
+            Ok(replace_in_string(value.clone(), vars)?.into_bytes())
+        }
+        RawKey::U64(value) => {
+            let value = replace_in_string(value.clone(), vars)?;
+            let value = value
+                .parse::<u64>()
+                .map_err(|_| ContractError::HydrationError {
+                    msg: format!("Raw query key {} is not a u64.", value),
+                })?;
+            Ok(value.to_be_bytes().to_vec())
+        }
+    }
+}
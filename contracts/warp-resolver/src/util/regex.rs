@@ -0,0 +1,453 @@
+use crate::ContractError;
+
+// Bounded regex subset used by StringOp::Matches. Patterns are compiled to an NFA and
+// simulated in lockstep over the input, so matching is O(pattern * input) without backtracking.
+// - literals, escaped metacharacters - abc, \., \$
+// - any char - .
+// - classes - [a-z0-9_], [^/], \d, \w, \s
+// - anchors - ^, $
+// - groups and alternation - (ibc|factory)/
+// - quantifiers - *, +, ?, {n}, {n,}, {n,m}
+// Without anchors the pattern may match anywhere in the input.
+pub const MAX_PATTERN_LENGTH: usize = 256;
+pub const MAX_REPEAT: u32 = 100;
+pub const MAX_PROGRAM_SIZE: usize = 2048;
+
+pub fn is_match(pattern: &str, input: &str) -> Result<bool, ContractError> {
+    let program = compile(pattern)?;
+    let input: Vec<char> = input.chars().collect();
+
+    let mut current = Threads::new(program.len());
+    let mut next = Threads::new(program.len());
+
+    for pos in 0..=input.len() {
+        // unanchored search, a new thread starts at every position
+        current.add(&program, 0, pos, input.len());
+        if current.matched(&program) {
+            return Ok(true);
+        }
+
+        let c = match input.get(pos) {
+            Some(c) => *c,
+            None => break,
+        };
+
+        next.clear();
+        for pc in current.pcs.clone() {
+            if let Inst::Char(matcher) = &program[pc] {
+                if matcher.matches(c) {
+                    next.add(&program, pc + 1, pos + 1, input.len());
+                }
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+    }
+
+    Ok(current.matched(&program))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Matcher {
+    Any,
+    Literal(char),
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+}
+
+impl Matcher {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Literal(l) => *l == c,
+            Matcher::Class { ranges, negated } => {
+                ranges.iter().any(|(from, to)| *from <= c && c <= *to) != *negated
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Empty,
+    Char(Matcher),
+    Start,
+    End,
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+#[derive(Debug)]
+enum Inst {
+    Char(Matcher),
+    Split(usize, usize),
+    Jmp(usize),
+    Start,
+    End,
+    Match,
+}
+
+struct Threads {
+    pcs: Vec<usize>,
+    seen: Vec<bool>,
+}
+
+impl Threads {
+    fn new(size: usize) -> Threads {
+        Threads {
+            pcs: vec![],
+            seen: vec![false; size],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.pcs.clear();
+        self.seen.iter_mut().for_each(|seen| *seen = false);
+    }
+
+    // follows epsilon transitions, only Char and Match instructions are kept as threads
+    fn add(&mut self, program: &[Inst], pc: usize, pos: usize, len: usize) {
+        let mut stack = vec![pc];
+
+        while let Some(pc) = stack.pop() {
+            if self.seen[pc] {
+                continue;
+            }
+            self.seen[pc] = true;
+
+            match program[pc] {
+                Inst::Split(a, b) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                Inst::Jmp(to) => stack.push(to),
+                Inst::Start => {
+                    if pos == 0 {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::End => {
+                    if pos == len {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::Char(_) | Inst::Match => self.pcs.push(pc),
+            }
+        }
+    }
+
+    fn matched(&self, program: &[Inst]) -> bool {
+        self.pcs
+            .iter()
+            .any(|pc| matches!(program[*pc], Inst::Match))
+    }
+}
+
+fn compile(pattern: &str) -> Result<Vec<Inst>, ContractError> {
+    if pattern.chars().count() > MAX_PATTERN_LENGTH {
+        return Err(pattern_error(pattern, "pattern too long"));
+    }
+
+    let mut parser = Parser {
+        pattern,
+        chars: pattern.chars().collect(),
+        pos: 0,
+    };
+    let node = parser.alt()?;
+    if parser.pos < parser.chars.len() {
+        return Err(pattern_error(pattern, "unbalanced ')'"));
+    }
+
+    let mut program = vec![];
+    emit(&node, &mut program, pattern)?;
+    program.push(Inst::Match);
+
+    Ok(program)
+}
+
+fn emit(node: &Node, program: &mut Vec<Inst>, pattern: &str) -> Result<(), ContractError> {
+    if program.len() > MAX_PROGRAM_SIZE {
+        return Err(pattern_error(pattern, "pattern too complex"));
+    }
+
+    match node {
+        Node::Empty => {}
+        Node::Char(matcher) => program.push(Inst::Char(matcher.clone())),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::Concat(nodes) => {
+            for node in nodes {
+                emit(node, program, pattern)?;
+            }
+        }
+        Node::Alt(nodes) => {
+            // split to each branch in turn, every branch jumps to the end
+            let mut jumps = vec![];
+            for (idx, node) in nodes.iter().enumerate() {
+                if idx + 1 < nodes.len() {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    emit(node, program, pattern)?;
+                    jumps.push(program.len());
+                    program.push(Inst::Jmp(0));
+                    let next = program.len();
+                    program[split] = Inst::Split(split + 1, next);
+                } else {
+                    emit(node, program, pattern)?;
+                }
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jmp(end);
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            for _ in 0..*min {
+                emit(node, program, pattern)?;
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    emit(node, program, pattern)?;
+                    program.push(Inst::Jmp(split));
+                    let end = program.len();
+                    program[split] = Inst::Split(split + 1, end);
+                }
+                Some(max) => {
+                    let mut splits = vec![];
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Split(0, 0));
+                        emit(node, program, pattern)?;
+                    }
+                    let end = program.len();
+                    for split in splits {
+                        program[split] = Inst::Split(split + 1, end);
+                    }
+                }
+            }
+        }
+    }
+
+    if program.len() > MAX_PROGRAM_SIZE {
+        return Err(pattern_error(pattern, "pattern too complex"));
+    }
+
+    Ok(())
+}
+
+fn pattern_error(pattern: &str, msg: &str) -> ContractError {
+    ContractError::ConditionError {
+        msg: format!("Invalid pattern {}: {}.", pattern, msg),
+    }
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, msg: &str) -> ContractError {
+        pattern_error(self.pattern, msg)
+    }
+
+    fn alt(&mut self) -> Result<Node, ContractError> {
+        let mut branches = vec![self.concat()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            branches.push(self.concat()?);
+        }
+
+        Ok(match branches.len() {
+            1 => branches.remove(0),
+            _ => Node::Alt(branches),
+        })
+    }
+
+    fn concat(&mut self) -> Result<Node, ContractError> {
+        let mut nodes = vec![];
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.remove(0),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, ContractError> {
+        let c = self.peek().ok_or_else(|| self.error("unexpected end"))?;
+        self.pos += 1;
+
+        match c {
+            '(' => {
+                let node = self.alt()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error("unbalanced '('"));
+                }
+                self.pos += 1;
+                Ok(node)
+            }
+            '[' => self.class(),
+            '.' => Ok(Node::Char(Matcher::Any)),
+            '^' => Ok(Node::Start),
+            '$' => Ok(Node::End),
+            '\\' => Ok(Node::Char(self.escape()?)),
+            '*' | '+' | '?' | '{' => Err(self.error("nothing to repeat")),
+            ']' | '}' => Err(self.error(&format!("unescaped '{}'", c))),
+            c => Ok(Node::Char(Matcher::Literal(c))),
+        }
+    }
+
+    fn escape(&mut self) -> Result<Matcher, ContractError> {
+        let c = self.peek().ok_or_else(|| self.error("unfinished escape"))?;
+        self.pos += 1;
+
+        Ok(match c {
+            'd' => Matcher::Class {
+                ranges: vec![('0', '9')],
+                negated: false,
+            },
+            'w' => Matcher::Class {
+                ranges: vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')],
+                negated: false,
+            },
+            's' => Matcher::Class {
+                ranges: vec![(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r')],
+                negated: false,
+            },
+            c if c.is_ascii_alphanumeric() => {
+                return Err(self.error(&format!("unsupported escape '\\{}'", c)))
+            }
+            c => Matcher::Literal(c),
+        })
+    }
+
+    // contents of [...] following the opening bracket
+    fn class(&mut self) -> Result<Node, ContractError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+
+        let mut ranges = vec![];
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("unbalanced '['"))?;
+            self.pos += 1;
+
+            let from = match c {
+                ']' if !ranges.is_empty() => break,
+                '\\' => match self.escape()? {
+                    Matcher::Literal(c) => c,
+                    Matcher::Class { ranges: r, .. } => {
+                        ranges.extend(r);
+                        continue;
+                    }
+                    Matcher::Any => unreachable!(),
+                },
+                c => c,
+            };
+
+            // a '-' right before the closing bracket is a literal
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']') {
+                self.pos += 1;
+                let to = match self.peek() {
+                    Some('\\') => {
+                        self.pos += 1;
+                        match self.escape()? {
+                            Matcher::Literal(c) => c,
+                            _ => return Err(self.error("invalid class range")),
+                        }
+                    }
+                    Some(c) => {
+                        self.pos += 1;
+                        c
+                    }
+                    None => return Err(self.error("unbalanced '['")),
+                };
+                if to < from {
+                    return Err(self.error("invalid class range"));
+                }
+                ranges.push((from, to));
+            } else {
+                ranges.push((from, from));
+            }
+        }
+
+        Ok(Node::Char(Matcher::Class { ranges, negated }))
+    }
+
+    fn quantified(&mut self, atom: Node) -> Result<Node, ContractError> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+                let min = self.number()?;
+                let max = match self.peek() {
+                    Some(',') => {
+                        self.pos += 1;
+                        match self.peek() {
+                            Some('}') => None,
+                            _ => Some(self.number()?),
+                        }
+                    }
+                    _ => Some(min),
+                };
+                if self.peek() != Some('}') {
+                    return Err(self.error("unbalanced '{'"));
+                }
+                if max.is_some_and(|max| max < min) {
+                    return Err(self.error("invalid repetition range"));
+                }
+                (min, max)
+            }
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+
+        if matches!(self.peek(), Some('*') | Some('+') | Some('?') | Some('{')) {
+            return Err(self.error("nested quantifier"));
+        }
+
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        })
+    }
+
+    fn number(&mut self) -> Result<u32, ContractError> {
+        let mut s = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+            s.push(c);
+            self.pos += 1;
+        }
+
+        match s.parse::<u32>() {
+            Ok(n) if n <= MAX_REPEAT => Ok(n),
+            Ok(_) => Err(self.error("repetition too large")),
+            Err(_) => Err(self.error("expected number")),
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::util::proto::{decode_message, field, repeated, Field, FieldKind};
+use crate::ContractError;
+use cosmwasm_std::QueryRequest;
+use json_codec_wasm::Json;
+use resolver::variable::Variable;
+
+// Response messages of the Stargate queries query variables can use, by gRPC path.
+// Adding a query means describing its response message with the field tables below.
+const STARGATE_RESPONSES: &[(&str, &[Field])] = &[
+    // bank
+    (
+        "/cosmos.bank.v1beta1.Query/Balance",
+        &[field(1, "balance", FieldKind::Message(COIN))],
+    ),
+    ("/cosmos.bank.v1beta1.Query/AllBalances", BALANCES_RESPONSE),
+    (
+        "/cosmos.bank.v1beta1.Query/SpendableBalances",
+        BALANCES_RESPONSE,
+    ),
+    (
+        "/cosmos.bank.v1beta1.Query/TotalSupply",
+        &[
+            repeated(1, "supply", FieldKind::Message(COIN)),
+            field(2, "pagination", FieldKind::Message(PAGE_RESPONSE)),
+        ],
+    ),
+    (
+        "/cosmos.bank.v1beta1.Query/SupplyOf",
+        &[field(1, "amount", FieldKind::Message(COIN))],
+    ),
+    // staking
+    (
+        "/cosmos.staking.v1beta1.Query/Validator",
+        &[field(1, "validator", FieldKind::Message(VALIDATOR))],
+    ),
+    (
+        "/cosmos.staking.v1beta1.Query/Delegation",
+        &[field(
+            1,
+            "delegation_response",
+            FieldKind::Message(DELEGATION_RESPONSE),
+        )],
+    ),
+    (
+        "/cosmos.staking.v1beta1.Query/DelegatorDelegations",
+        &[
+            repeated(
+                1,
+                "delegation_responses",
+                FieldKind::Message(DELEGATION_RESPONSE),
+            ),
+            field(2, "pagination", FieldKind::Message(PAGE_RESPONSE)),
+        ],
+    ),
+    (
+        "/cosmos.staking.v1beta1.Query/UnbondingDelegation",
+        &[field(1, "unbond", FieldKind::Message(UNBONDING_DELEGATION))],
+    ),
+    (
+        "/cosmos.staking.v1beta1.Query/Pool",
+        &[field(1, "pool", FieldKind::Message(STAKING_POOL))],
+    ),
+    (
+        "/cosmos.staking.v1beta1.Query/Params",
+        &[field(1, "params", FieldKind::Message(STAKING_PARAMS))],
+    ),
+    // distribution
+    (
+        "/cosmos.distribution.v1beta1.Query/DelegationRewards",
+        &[repeated(1, "rewards", FieldKind::Message(DEC_COIN))],
+    ),
+    (
+        "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards",
+        &[
+            repeated(
+                1,
+                "rewards",
+                FieldKind::Message(DELEGATION_DELEGATOR_REWARD),
+            ),
+            repeated(2, "total", FieldKind::Message(DEC_COIN)),
+        ],
+    ),
+    (
+        "/cosmos.distribution.v1beta1.Query/ValidatorOutstandingRewards",
+        &[field(1, "rewards", FieldKind::Message(DEC_COINS_REWARDS))],
+    ),
+    (
+        "/cosmos.distribution.v1beta1.Query/ValidatorCommission",
+        &[field(
+            1,
+            "commission",
+            FieldKind::Message(DEC_COINS_COMMISSION),
+        )],
+    ),
+    (
+        "/cosmos.distribution.v1beta1.Query/CommunityPool",
+        &[repeated(1, "pool", FieldKind::Message(DEC_COIN))],
+    ),
+    (
+        "/cosmos.distribution.v1beta1.Query/DelegatorWithdrawAddress",
+        &[field(1, "withdraw_address", FieldKind::String)],
+    ),
+    // authz
+    (
+        "/cosmos.authz.v1beta1.Query/Grants",
+        &[
+            repeated(1, "grants", FieldKind::Message(GRANT)),
+            field(2, "pagination", FieldKind::Message(PAGE_RESPONSE)),
+        ],
+    ),
+    (
+        "/cosmos.authz.v1beta1.Query/GranterGrants",
+        GRANT_AUTHORIZATIONS,
+    ),
+    (
+        "/cosmos.authz.v1beta1.Query/GranteeGrants",
+        GRANT_AUTHORIZATIONS,
+    ),
+    // tokenfactory
+    (
+        "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata",
+        &[field(
+            1,
+            "authority_metadata",
+            FieldKind::Message(&[field(1, "admin", FieldKind::String)]),
+        )],
+    ),
+    (
+        "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator",
+        &[repeated(1, "denoms", FieldKind::String)],
+    ),
+    (
+        "/osmosis.tokenfactory.v1beta1.Query/Params",
+        &[field(1, "params", FieldKind::Message(TOKENFACTORY_PARAMS))],
+    ),
+];
+
+const COIN: &[Field] = &[
+    field(1, "denom", FieldKind::String),
+    field(2, "amount", FieldKind::String),
+];
+
+const DEC_COIN: &[Field] = &[
+    field(1, "denom", FieldKind::String),
+    field(2, "amount", FieldKind::Dec),
+];
+
+const PAGE_RESPONSE: &[Field] = &[
+    field(1, "next_key", FieldKind::Bytes),
+    field(2, "total", FieldKind::Uint64),
+];
+
+const TIMESTAMP: &[Field] = &[
+    field(1, "seconds", FieldKind::Int64),
+    field(2, "nanos", FieldKind::Int32),
+];
+
+const ANY: &[Field] = &[
+    field(1, "type_url", FieldKind::String),
+    field(2, "value", FieldKind::Bytes),
+];
+
+const BALANCES_RESPONSE: &[Field] = &[
+    repeated(1, "balances", FieldKind::Message(COIN)),
+    field(2, "pagination", FieldKind::Message(PAGE_RESPONSE)),
+];
+
+const VALIDATOR: &[Field] = &[
+    field(1, "operator_address", FieldKind::String),
+    field(2, "consensus_pubkey", FieldKind::Message(ANY)),
+    field(3, "jailed", FieldKind::Bool),
+    field(
+        4,
+        "status",
+        FieldKind::Enum(&[
+            "BOND_STATUS_UNSPECIFIED",
+            "BOND_STATUS_UNBONDED",
+            "BOND_STATUS_UNBONDING",
+            "BOND_STATUS_BONDED",
+        ]),
+    ),
+    field(5, "tokens", FieldKind::String),
+    field(6, "delegator_shares", FieldKind::Dec),
+    field(
+        7,
+        "description",
+        FieldKind::Message(&[
+            field(1, "moniker", FieldKind::String),
+            field(2, "identity", FieldKind::String),
+            field(3, "website", FieldKind::String),
+            field(4, "security_contact", FieldKind::String),
+            field(5, "details", FieldKind::String),
+        ]),
+    ),
+    field(8, "unbonding_height", FieldKind::Int64),
+    field(9, "unbonding_time", FieldKind::Message(TIMESTAMP)),
+    field(
+        10,
+        "commission",
+        FieldKind::Message(&[
+            field(
+                1,
+                "commission_rates",
+                FieldKind::Message(&[
+                    field(1, "rate", FieldKind::Dec),
+                    field(2, "max_rate", FieldKind::Dec),
+                    field(3, "max_change_rate", FieldKind::Dec),
+                ]),
+            ),
+            field(2, "update_time", FieldKind::Message(TIMESTAMP)),
+        ]),
+    ),
+    field(11, "min_self_delegation", FieldKind::String),
+];
+
+const DELEGATION_RESPONSE: &[Field] = &[
+    field(
+        1,
+        "delegation",
+        FieldKind::Message(&[
+            field(1, "delegator_address", FieldKind::String),
+            field(2, "validator_address", FieldKind::String),
+            field(3, "shares", FieldKind::Dec),
+        ]),
+    ),
+    field(2, "balance", FieldKind::Message(COIN)),
+];
+
+const UNBONDING_DELEGATION: &[Field] = &[
+    field(1, "delegator_address", FieldKind::String),
+    field(2, "validator_address", FieldKind::String),
+    repeated(
+        3,
+        "entries",
+        FieldKind::Message(&[
+            field(1, "creation_height", FieldKind::Int64),
+            field(2, "completion_time", FieldKind::Message(TIMESTAMP)),
+            field(3, "initial_balance", FieldKind::String),
+            field(4, "balance", FieldKind::String),
+        ]),
+    ),
+];
+
+const STAKING_POOL: &[Field] = &[
+    field(1, "not_bonded_tokens", FieldKind::String),
+    field(2, "bonded_tokens", FieldKind::String),
+];
+
+const STAKING_PARAMS: &[Field] = &[
+    field(
+        1,
+        "unbonding_time",
+        FieldKind::Message(&[
+            field(1, "seconds", FieldKind::Int64),
+            field(2, "nanos", FieldKind::Int32),
+        ]),
+    ),
+    field(2, "max_validators", FieldKind::Uint32),
+    field(3, "max_entries", FieldKind::Uint32),
+    field(4, "historical_entries", FieldKind::Uint32),
+    field(5, "bond_denom", FieldKind::String),
+    field(6, "min_commission_rate", FieldKind::Dec),
+];
+
+const DELEGATION_DELEGATOR_REWARD: &[Field] = &[
+    field(1, "validator_address", FieldKind::String),
+    repeated(2, "reward", FieldKind::Message(DEC_COIN)),
+];
+
+const DEC_COINS_REWARDS: &[Field] = &[repeated(1, "rewards", FieldKind::Message(DEC_COIN))];
+
+const DEC_COINS_COMMISSION: &[Field] = &[repeated(1, "commission", FieldKind::Message(DEC_COIN))];
+
+const GRANT: &[Field] = &[
+    field(1, "authorization", FieldKind::Message(ANY)),
+    field(2, "expiration", FieldKind::Message(TIMESTAMP)),
+];
+
+const GRANT_AUTHORIZATIONS: &[Field] = &[
+    repeated(
+        1,
+        "grants",
+        FieldKind::Message(&[
+            field(1, "granter", FieldKind::String),
+            field(2, "grantee", FieldKind::String),
+            field(3, "authorization", FieldKind::Message(ANY)),
+            field(4, "expiration", FieldKind::Message(TIMESTAMP)),
+        ]),
+    ),
+    field(2, "pagination", FieldKind::Message(PAGE_RESPONSE)),
+];
+
+const TOKENFACTORY_PARAMS: &[Field] = &[
+    repeated(1, "denom_creation_fee", FieldKind::Message(COIN)),
+    field(2, "denom_creation_gas_consume", FieldKind::Uint64),
+];
+
+// Rejects query vars using Stargate paths without a registered response decoder
+pub fn validate_stargate_vars(vars: &[Variable]) -> Result<(), ContractError> {
+    for var in vars {
+        if let Variable::Query(v) = var {
+            if let QueryRequest::Stargate { path, .. } = &v.init_fn.query {
+                response_fields(path).ok_or_else(|| unsupported_path(path))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn decode_stargate_response(path: &str, bytes: &[u8]) -> Result<Json, ContractError> {
+    let fields = response_fields(path).ok_or_else(|| unsupported_path(path))?;
+
+    decode_message(bytes, fields)
+}
+
+fn response_fields(path: &str) -> Option<&'static [Field]> {
+    STARGATE_RESPONSES
+        .iter()
+        .find(|(response_path, _)| *response_path == path)
+        .map(|(_, fields)| *fields)
+}
+
+fn unsupported_path(path: &str) -> ContractError {
+    ContractError::HydrationError {
+        msg: format!("Unsupported Stargate query path: {}.", path),
+    }
+}
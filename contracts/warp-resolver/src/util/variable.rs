@@ -10,362 +10,616 @@ use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
     Binary, CosmosMsg, Decimal256, Deps, Env, QueryRequest, Uint128, Uint256, WasmQuery,
 };
+use json_codec_wasm::{Decoder, Json};
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use controller::job::{ExternalInput, JobStatus};
-use resolver::variable::{FnValue, QueryExpr, Variable, VariableKind};
+use resolver::int256::Int256;
+use resolver::variable::{
+    ExternalVariable, FnValue, HistoryVariable, OnErrorPolicy, QueryExpr, QueryVariable, RawKey,
+    RawQueryVariable, StaticVariable, Variable, VariableKind,
+};
+use resolver::JobEnv;
 
 use super::condition::resolve_string_value;
+use super::external::{trusted_signers_valid, verify_external_input};
+use super::history::{record_history, MAX_HISTORY_CAPACITY};
+use super::placeholder::{encode_json, substitute_placeholders};
+use super::raw::raw_query_expr;
 
 pub fn hydrate_vars(
     deps: Deps,
     env: Env,
     vars: Vec<Variable>,
     external_inputs: Option<Vec<ExternalInput>>,
-    warp_account_addr: Option<String>,
+    job_env: &JobEnv,
 ) -> Result<Vec<Variable>, ContractError> {
+    let order = hydration_order(&vars)?;
     let mut hydrated_vars = vec![];
 
-    for var in vars {
-        let hydrated_var = match var {
-            Variable::Static(mut v) => {
-                if v.reinitialize || v.value.is_none() {
-                    match v.kind {
-                        VariableKind::Uint => match v.init_fn.clone() {
-                            FnValue::Uint(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_num_value_uint(deps, env.clone(), val, &hydrated_vars)?
-                                        .to_string(),
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::Uint."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::Int => match v.init_fn.clone() {
-                            FnValue::Int(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_num_value_int(deps, env.clone(), val, &hydrated_vars)?
-                                        .to_string(),
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::Int."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::Decimal => match v.init_fn.clone() {
-                            FnValue::Decimal(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_num_value_decimal(
-                                        deps,
-                                        env.clone(),
-                                        val,
-                                        &hydrated_vars,
-                                    )?
-                                    .to_string(),
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::Decimal."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::Timestamp => match v.init_fn.clone() {
-                            FnValue::Timestamp(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_num_value_int(deps, env.clone(), val, &hydrated_vars)?
-                                        .to_string(),
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::Timestamp."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::Bool => match v.init_fn.clone() {
-                            FnValue::Bool(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_ref_bool(deps, env.clone(), val, &hydrated_vars)?
-                                        .to_string(),
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::Bool."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::Amount => match v.init_fn.clone() {
-                            FnValue::Uint(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_num_value_uint(deps, env.clone(), val, &hydrated_vars)?
-                                        .to_string(),
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::Uint."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::String => match v.init_fn.clone() {
-                            FnValue::String(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_string_value(
-                                        deps,
-                                        env.clone(),
-                                        val,
-                                        &hydrated_vars,
-                                        warp_account_addr.clone(),
-                                    )?,
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "1Variable init_fn is not of type FnValue::String."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::Asset => match v.init_fn.clone() {
-                            FnValue::String(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_string_value(
-                                        deps,
-                                        env.clone(),
-                                        val,
-                                        &hydrated_vars,
-                                        warp_account_addr.clone(),
-                                    )?,
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::String."
-                                        .to_string(),
-                                })
-                            }
-                        },
-                        VariableKind::Json => match v.init_fn.clone() {
-                            FnValue::String(val) => {
-                                v.value = Some(replace_in_string(
-                                    resolve_string_value(
-                                        deps,
-                                        env.clone(),
-                                        val,
-                                        &hydrated_vars,
-                                        warp_account_addr.clone(),
-                                    )?,
-                                    &hydrated_vars,
-                                )?)
-                            }
-                            _ => {
-                                return Err(ContractError::HydrationError {
-                                    msg: "Variable init_fn is not of type FnValue::String."
-                                        .to_string(),
-                                });
-                            }
-                        },
-                    }
-                }
-                if v.value.is_none() {
-                    return Err(ContractError::Unauthorized {});
-                }
-                Variable::Static(v)
-            }
-            Variable::External(mut v) => {
-                if v.reinitialize || v.value.is_none() {
-                    match external_inputs {
-                        None => {
-                            if v.value.is_none() {
-                                return Err(ContractError::HydrationError {
-                                    msg: "External input value is none.".to_string(),
-                                });
-                            }
-                            Variable::External(v)
-                        }
-                        Some(ref input) => {
-                            let idx = input.iter().position(|i| i.name == v.name);
-                            v.value = match idx {
-                                None => {
-                                    return Err(ContractError::HydrationError {
-                                        msg: "External input variable not found.".to_string(),
-                                    })
-                                }
-                                Some(i) => Some(input[i].input.clone()),
-                            };
-                            Variable::External(v)
-                        }
-                    }
-                } else {
-                    if v.value.is_none() {
-                        return Err(ContractError::HydrationError {
-                            msg: "External value is none.".to_string(),
-                        });
-                    }
-                    Variable::External(v)
-                }
-            }
-            Variable::Query(mut v) => {
-                if v.reinitialize || v.value.is_none() {
-                    let replaced_init_fn = replace_references(v.init_fn.clone(), &hydrated_vars)?;
+    for idx in order.iter() {
+        let (var, original) = split_var(vars[*idx].clone(), Some(&hydrated_vars))?;
+        let hydrated_var = match hydrate_var(
+            deps,
+            env.clone(),
+            var.clone(),
+            &external_inputs,
+            &hydrated_vars,
+            job_env,
+        ) {
+            Ok(hydrated_var) => hydrated_var,
+            Err(e) => hydrate_fallback(var, e)?,
+        };
+        hydrated_vars.push(join_var(hydrated_var, original));
+    }
 
-                    match v.kind {
-                        VariableKind::String => {
-                            v.value = Some(
-                                // \"$warp.variable\" => \"VALUE"\
-                                resolve_query_expr_string(
+    // hydrated in dependency order, returned in declaration order
+    let mut hydrated: Vec<(usize, Variable)> = order.into_iter().zip(hydrated_vars).collect();
+    hydrated.sort_by_key(|(idx, _)| *idx);
+
+    Ok(hydrated.into_iter().map(|(_, var)| var).collect())
+}
+
+fn hydrate_var(
+    deps: Deps,
+    env: Env,
+    var: Variable,
+    external_inputs: &Option<Vec<ExternalInput>>,
+    hydrated_vars: &Vec<Variable>,
+    job_env: &JobEnv,
+) -> Result<Variable, ContractError> {
+    let hydrated_var = match var {
+        Variable::Static(mut v) => {
+            if v.reinitialize || v.value.is_none() {
+                match v.kind {
+                    VariableKind::Uint => match v.init_fn.clone() {
+                        FnValue::Uint(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_num_value_uint(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
                                 )?
                                 .to_string(),
-                            )
+                                hydrated_vars,
+                            )?)
                         }
-                        VariableKind::Uint => {
-                            v.value = Some(
-                                resolve_query_expr_uint(
-                                    deps,
-                                    env.clone(),
-                                    replaced_init_fn.clone(),
-                                )?
-                                .to_string(),
-                            )
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::Uint.".to_string(),
+                            })
                         }
-                        VariableKind::Int => {
-                            v.value = Some(
-                                resolve_query_expr_int(
+                    },
+                    VariableKind::Int => match v.init_fn.clone() {
+                        FnValue::Int(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_num_value_int(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
                                 )?
                                 .to_string(),
-                            )
+                                hydrated_vars,
+                            )?)
                         }
-                        VariableKind::Decimal => {
-                            v.value = Some(
-                                resolve_query_expr_decimal(
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::Int.".to_string(),
+                            })
+                        }
+                    },
+                    VariableKind::Decimal => match v.init_fn.clone() {
+                        FnValue::Decimal(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_num_value_decimal(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
                                 )?
                                 .to_string(),
-                            )
+                                hydrated_vars,
+                            )?)
                         }
-                        VariableKind::Timestamp => {
-                            v.value = Some(
-                                resolve_query_expr_int(
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::Decimal."
+                                    .to_string(),
+                            })
+                        }
+                    },
+                    VariableKind::Timestamp => match v.init_fn.clone() {
+                        FnValue::Timestamp(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_num_value_int(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
                                 )?
                                 .to_string(),
-                            )
+                                hydrated_vars,
+                            )?)
                         }
-                        VariableKind::Bool => {
-                            v.value = Some(
-                                resolve_query_expr_bool(
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::Timestamp."
+                                    .to_string(),
+                            })
+                        }
+                    },
+                    VariableKind::Bool => match v.init_fn.clone() {
+                        FnValue::Bool(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_ref_bool(deps, env.clone(), val, hydrated_vars)?
+                                    .to_string(),
+                                hydrated_vars,
+                            )?)
+                        }
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::Bool.".to_string(),
+                            })
+                        }
+                    },
+                    VariableKind::Amount => match v.init_fn.clone() {
+                        FnValue::Uint(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_num_value_uint(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
                                 )?
                                 .to_string(),
-                            )
+                                hydrated_vars,
+                            )?)
                         }
-                        VariableKind::Amount => {
-                            v.value = Some(
-                                resolve_query_expr_uint(
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::Uint.".to_string(),
+                            })
+                        }
+                    },
+                    VariableKind::String => match v.init_fn.clone() {
+                        FnValue::String(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_string_value(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
-                                )?
-                                .to_string(),
-                            )
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
+                                )?,
+                                hydrated_vars,
+                            )?)
                         }
-                        VariableKind::Asset => {
-                            v.value = Some(
-                                resolve_query_expr_string(
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "1Variable init_fn is not of type FnValue::String."
+                                    .to_string(),
+                            })
+                        }
+                    },
+                    VariableKind::Asset => match v.init_fn.clone() {
+                        FnValue::String(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_string_value(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
-                                )?
-                                .to_string(),
-                            )
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
+                                )?,
+                                hydrated_vars,
+                            )?)
+                        }
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::String.".to_string(),
+                            })
                         }
-                        VariableKind::Json => {
-                            v.value = Some(
-                                resolve_query_expr_string(
+                    },
+                    VariableKind::Json => match v.init_fn.clone() {
+                        FnValue::String(val) => {
+                            v.value = Some(replace_in_string(
+                                resolve_string_value(
                                     deps,
                                     env.clone(),
-                                    replaced_init_fn.clone(),
-                                )?
-                                .to_string(),
-                            )
+                                    val,
+                                    hydrated_vars,
+                                    job_env,
+                                )?,
+                                hydrated_vars,
+                            )?)
                         }
+                        _ => {
+                            return Err(ContractError::HydrationError {
+                                msg: "Variable init_fn is not of type FnValue::String.".to_string(),
+                            });
+                        }
+                    },
+                }
+            }
+            if v.value.is_none() {
+                return Err(ContractError::Unauthorized {});
+            }
+            Variable::Static(v)
+        }
+        Variable::External(mut v) => {
+            if v.reinitialize || v.value.is_none() {
+                match external_inputs {
+                    None => {
+                        if v.value.is_none() {
+                            return Err(ContractError::HydrationError {
+                                msg: "External input value is none.".to_string(),
+                            });
+                        }
+                        Variable::External(v)
+                    }
+                    Some(ref input) => {
+                        let idx = input.iter().position(|i| i.name == v.name);
+                        v.value = match idx {
+                            None => {
+                                return Err(ContractError::HydrationError {
+                                    msg: "External input variable not found.".to_string(),
+                                })
+                            }
+                            Some(i) => {
+                                verify_external_input(
+                                    deps.api, &env, &v.init_fn, &input[i], job_env,
+                                )?;
+                                Some(input[i].input.clone())
+                            }
+                        };
+                        Variable::External(v)
                     }
                 }
+            } else {
                 if v.value.is_none() {
-                    return Err(ContractError::Unauthorized {});
+                    return Err(ContractError::HydrationError {
+                        msg: "External value is none.".to_string(),
+                    });
                 }
-                Variable::Query(v)
+                Variable::External(v)
             }
-        };
-        hydrated_vars.push(hydrated_var);
+        }
+        Variable::Query(mut v) => {
+            if v.reinitialize || v.value.is_none() {
+                let replaced_init_fn = replace_references(v.init_fn.clone(), hydrated_vars)?;
+
+                match v.kind {
+                    VariableKind::String => {
+                        v.value = Some(
+                            // \"$warp.variable\" => \"VALUE"\
+                            resolve_query_expr_string(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                    VariableKind::Uint => {
+                        v.value = Some(
+                            resolve_query_expr_uint(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                    VariableKind::Int => {
+                        v.value = Some(
+                            resolve_query_expr_int(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                    VariableKind::Decimal => {
+                        v.value = Some(
+                            resolve_query_expr_decimal(
+                                deps,
+                                env.clone(),
+                                replaced_init_fn.clone(),
+                            )?
+                            .to_string(),
+                        )
+                    }
+                    VariableKind::Timestamp => {
+                        v.value = Some(
+                            resolve_query_expr_int(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                    VariableKind::Bool => {
+                        v.value = Some(
+                            resolve_query_expr_bool(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                    VariableKind::Amount => {
+                        v.value = Some(
+                            resolve_query_expr_uint(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                    VariableKind::Asset => {
+                        v.value = Some(
+                            resolve_query_expr_string(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                    VariableKind::Json => {
+                        v.value = Some(
+                            resolve_query_expr_string(deps, env.clone(), replaced_init_fn.clone())?
+                                .to_string(),
+                        )
+                    }
+                }
+            }
+            if v.value.is_none() {
+                return Err(ContractError::Unauthorized {});
+            }
+            Variable::Query(v)
+        }
+        var => var,
+    };
+
+    Ok(hydrated_var)
+}
+
+// Applies the on_error policy of a var failing to hydrate, raw query vars arriving as query vars
+fn hydrate_fallback(var: Variable, e: ContractError) -> Result<Variable, ContractError> {
+    match var {
+        Variable::Static(mut v) => {
+            v.value = Some(fallback_value(&v.on_error, &v.default, &v.value).ok_or(e)?);
+            Ok(Variable::Static(v))
+        }
+        Variable::External(mut v) => {
+            v.value = Some(fallback_value(&v.on_error, &v.default, &v.value).ok_or(e)?);
+            Ok(Variable::External(v))
+        }
+        Variable::Query(mut v) => {
+            v.value = Some(fallback_value(&v.on_error, &v.default, &v.value).ok_or(e)?);
+            Ok(Variable::Query(v))
+        }
+        _ => Err(e),
     }
-    Ok(hydrated_vars)
 }
 
-pub fn hydrate_msgs(msgs: String, vars: Vec<Variable>) -> Result<Vec<WarpMsg>, ContractError> {
-    let mut replaced_msgs = msgs;
-    for var in &vars {
-        let (name, replacement) = get_replacement_in_struct(var)?;
-        replaced_msgs =
-            replaced_msgs.replace(&format!("\"$warp.variable.{}\"", name), &replacement);
-        if replacement.contains("$warp.variable") {
-            return Err(ContractError::HydrationError {
-                msg: "Attempt to inject warp variable.".to_string(),
+fn fallback_value(
+    on_error: &Option<OnErrorPolicy>,
+    default: &Option<String>,
+    last_value: &Option<String>,
+) -> Option<String> {
+    match on_error {
+        None | Some(OnErrorPolicy::Fail) => None,
+        Some(OnErrorPolicy::UseDefault) => default.clone(),
+        Some(OnErrorPolicy::UseLastValue) => last_value.clone().or_else(|| default.clone()),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum Visit {
+    Pending,
+    InProgress,
+    Done,
+}
+
+// Indices of vars ordered so that every var comes after the vars its init_fn references,
+// keeping declaration order otherwise. Errors with the cycle path when vars reference each other.
+pub fn hydration_order(vars: &[Variable]) -> Result<Vec<usize>, ContractError> {
+    let names: Vec<String> = vars.iter().map(get_var_name).collect();
+    let references = vars
+        .iter()
+        .map(|var| init_fn_references(var, &names))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut visits = vec![Visit::Pending; vars.len()];
+    let mut path = vec![];
+    let mut order = vec![];
+    for idx in 0..vars.len() {
+        visit_var(idx, &references, &names, &mut visits, &mut path, &mut order)?;
+    }
+
+    Ok(order)
+}
+
+fn visit_var(
+    idx: usize,
+    references: &[Vec<usize>],
+    names: &[String],
+    visits: &mut [Visit],
+    path: &mut Vec<usize>,
+    order: &mut Vec<usize>,
+) -> Result<(), ContractError> {
+    match visits[idx] {
+        Visit::Done => return Ok(()),
+        Visit::InProgress => {
+            let start = path.iter().position(|i| *i == idx).unwrap_or(0);
+            let cycle: Vec<String> = path[start..]
+                .iter()
+                .chain([idx].iter())
+                .map(|i| names[*i].clone())
+                .collect();
+            return Err(ContractError::VariableCycle {
+                path: cycle.join(" -> "),
             });
         }
+        Visit::Pending => {}
     }
 
-    match serde_json_wasm::from_str::<Vec<WarpMsg>>(&replaced_msgs) {
-        Ok(msgs) => Ok(msgs),
+    visits[idx] = Visit::InProgress;
+    path.push(idx);
+    for reference in references[idx].iter() {
+        visit_var(*reference, references, names, visits, path, order)?;
+    }
+    path.pop();
+    visits[idx] = Visit::Done;
+    order.push(idx);
 
-        // fallback to legacy flow
-        Err(_) => {
-            let msgs = serde_json_wasm::from_str::<Vec<CosmosMsg>>(&replaced_msgs)
-                .unwrap()
-                .into_iter()
-                .map(WarpMsg::Generic)
-                .collect();
+    Ok(())
+}
+
+// External init_fns are resolved off chain, so only static and query vars depend on other vars
+fn init_fn_references(var: &Variable, names: &[String]) -> Result<Vec<usize>, ContractError> {
+    let init_fn = match var {
+        Variable::Static(v) => serde_json_wasm::to_string(&v.init_fn)?,
+        Variable::History(v) => serde_json_wasm::to_string(&v.init_fn)?,
+        Variable::RawQuery(v) => serde_json_wasm::to_string(&v.init_fn)?,
+        Variable::External(_) => return Ok(vec![]),
+        Variable::Query(v) => {
+            let mut query = serde_json_wasm::to_string(&v.init_fn.query)?;
+            if let QueryRequest::Wasm(WasmQuery::Smart { msg: binary, .. })
+            | QueryRequest::Wasm(WasmQuery::Raw { key: binary, .. })
+            | QueryRequest::Stargate { data: binary, .. } = &v.init_fn.query
+            {
+                query.push_str(&String::from_utf8_lossy(binary.as_slice()));
+            }
+            query
+        }
+    };
+
+    let mut references = vec![];
+    for (pos, _) in init_fn.match_indices("$warp.variable.") {
+        let rest = &init_fn[pos + "$warp.variable.".len()..];
+        // a name only matches up to a boundary, so $warp.variable.ab does not reference a,
+        // and the longest matching name wins for names containing dots
+        let referenced = names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| {
+                rest.strip_prefix(name.as_str())
+                    .is_some_and(|after| !after.starts_with(is_name_char))
+            })
+            .max_by_key(|(_, name)| name.len());
+        if let Some((idx, _)) = referenced {
+            if !references.contains(&idx) {
+                references.push(idx);
+            }
+        }
+    }
+
+    Ok(references)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+pub fn hydrate_msgs(msgs: String, vars: Vec<Variable>) -> Result<Vec<WarpMsg>, ContractError> {
+    let values = vars
+        .iter()
+        .map(placeholder_value)
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    substitute_msgs(&msgs, &values)
+}
+
+fn substitute_msgs(
+    msgs: &str,
+    values: &HashMap<String, Json>,
+) -> Result<Vec<WarpMsg>, ContractError> {
+    let msgs =
+        Decoder::default(msgs.chars())
+            .decode()
+            .map_err(|_| ContractError::HydrationError {
+                msg: "Msgs are not valid JSON.".to_string(),
+            })?;
+
+    let msgs = match substitute_placeholders(msgs, values, "$")? {
+        Json::Array(msgs) => msgs,
+        _ => {
+            return Err(ContractError::HydrationError {
+                msg: "Msgs are not a JSON array.".to_string(),
+            })
+        }
+    };
+
+    msgs.iter()
+        .enumerate()
+        .map(|(idx, msg)| {
+            let msg = encode_json(msg)?;
+            match serde_json_wasm::from_str::<WarpMsg>(&msg) {
+                Ok(msg) => Ok(msg),
+                // fallback to legacy flow
+                Err(e) => serde_json_wasm::from_str::<CosmosMsg>(&msg)
+                    .map(WarpMsg::Generic)
+                    .map_err(|_| ContractError::HydrationError {
+                        msg: format!("Invalid msg at $[{}]: {}.", idx, e),
+                    }),
+            }
+        })
+        .collect()
+}
+
+// The JSON value replacing the var's placeholders in msgs: numbers, bools and JSON are inserted
+// as is, other kinds and encoded values as strings
+fn placeholder_value(var: &Variable) -> Result<(String, Json), ContractError> {
+    let (kind, name, encode, value, var_type) = match var {
+        Variable::History(_) | Variable::RawQuery(_) => {
+            return placeholder_value(&split_var(var.clone(), None)?.0)
+        }
+        Variable::Static(v) => (&v.kind, &v.name, v.encode, &v.value, "Static"),
+        Variable::External(v) => (&v.kind, &v.name, v.encode, &v.value, "External"),
+        Variable::Query(v) => (&v.kind, &v.name, v.encode, &v.value, "Query"),
+    };
+    let value = value
+        .as_ref()
+        .ok_or_else(|| ContractError::HydrationError {
+            msg: format!("{} msg value is none.", var_type),
+        })?;
 
-            Ok(msgs)
+    let json = match (encode, kind) {
+        (true, _) => Json::String(base64::encode(value)),
+        (false, VariableKind::Int) => int_json(parse_int(name, value)?),
+        (false, VariableKind::Timestamp | VariableKind::Bool | VariableKind::Json) => {
+            Decoder::default(value.chars())
+                .decode()
+                .map_err(|_| ContractError::HydrationError {
+                    msg: format!("Variable {} value is not valid JSON.", name),
+                })?
         }
+        (false, _) => Json::String(value.clone()),
+    };
+
+    Ok((name.clone(), json))
+}
+
+// Json numbers are limited to 128 bits, larger values are emitted as a string like Int256
+// serializes itself
+fn int_json(value: Int256) -> Json {
+    let value = value.to_string();
+
+    match (value.parse::<i128>(), value.parse::<u128>()) {
+        (Ok(value), _) => Json::I128(value),
+        (_, Ok(value)) => Json::U128(value),
+        _ => Json::String(value),
     }
 }
 
+// Query structs get the same number or string choice as msgs, as raw json text
+fn int_replacement(name: &str, value: &str) -> Result<String, ContractError> {
+    let value = parse_int(name, value)?;
+
+    Ok(match int_json(value) {
+        Json::String(value) => format!("\"{}\"", value),
+        _ => value.to_string(),
+    })
+}
+
+fn parse_int(name: &str, value: &str) -> Result<Int256, ContractError> {
+    Int256::from_str(value).map_err(|_| ContractError::HydrationError {
+        msg: format!("Variable {} value is not a valid Int.", name),
+    })
+}
+
 fn get_replacement_in_struct(var: &Variable) -> Result<(String, String), ContractError> {
     let (name, replacement) = match var {
+        Variable::History(_) | Variable::RawQuery(_) => {
+            return get_replacement_in_struct(&split_var(var.clone(), None)?.0)
+        }
         Variable::Static(v) => match v.value.clone() {
             None => {
                 return Err(ContractError::HydrationError {
@@ -387,7 +641,7 @@ fn get_replacement_in_struct(var: &Variable) -> Result<(String, String), Contrac
                         true => {
                             format!("\"{}\"", base64::encode(val))
                         }
-                        false => val,
+                        false => int_replacement(&v.name, &val)?,
                     },
                     VariableKind::Decimal => format!(
                         "\"{}\"",
@@ -476,7 +730,7 @@ fn get_replacement_in_struct(var: &Variable) -> Result<(String, String), Contrac
                         true => {
                             format!("\"{}\"", base64::encode(val))
                         }
-                        false => val,
+                        false => int_replacement(&v.name, &val)?,
                     },
                     VariableKind::Decimal => format!(
                         "\"{}\"",
@@ -547,7 +801,7 @@ fn get_replacement_in_struct(var: &Variable) -> Result<(String, String), Contrac
                         true => {
                             format!("\"{}\"", base64::encode(val))
                         }
-                        false => val,
+                        false => int_replacement(&v.name, &val)?,
                     },
                     VariableKind::Decimal => format!(
                         "\"{}\"",
@@ -613,6 +867,9 @@ fn get_replacement_in_struct(var: &Variable) -> Result<(String, String), Contrac
 
 fn get_replacement_in_string(var: &Variable) -> Result<(String, String), ContractError> {
     let (name, replacement) = match var {
+        Variable::History(_) | Variable::RawQuery(_) => {
+            return get_replacement_in_string(&split_var(var.clone(), None)?.0)
+        }
         Variable::Static(v) => match v.value.clone() {
             None => {
                 return Err(ContractError::HydrationError {
@@ -667,7 +924,10 @@ fn replace_references(mut expr: QueryExpr, vars: &[Variable]) -> Result<QueryExp
             *contract_addr = replace_in_string(contract_addr.to_string(), vars)?;
         }
         QueryRequest::Wasm(WasmQuery::Raw { key, contract_addr }) => {
-            *key = replace_in_binary(key, vars)?;
+            // keys built from raw query vars may be binary and have their references replaced
+            if std::str::from_utf8(key.as_slice()).is_ok() {
+                *key = replace_in_binary(key, vars)?;
+            }
             *contract_addr = replace_in_string(contract_addr.to_string(), vars)?;
         }
         _ => expr.query = replace_in_struct(&expr.query, vars)?,
@@ -721,7 +981,7 @@ fn replace_in_struct_string(value: String, vars: &[Variable]) -> Result<String,
     Ok(replaced_value)
 }
 
-fn replace_in_string(value: String, vars: &[Variable]) -> Result<String, ContractError> {
+pub fn replace_in_string(value: String, vars: &[Variable]) -> Result<String, ContractError> {
     let mut replaced_value = value;
 
     for var in vars {
@@ -732,92 +992,57 @@ fn replace_in_string(value: String, vars: &[Variable]) -> Result<String, Contrac
     Ok(replaced_value)
 }
 
-pub fn msgs_valid(msgs: &str, vars: &Vec<Variable>) -> Result<bool, ContractError> {
-    let mut replaced_msgs = msgs.to_owned();
-    for var in vars {
-        let (name, replacement) = match var {
-            Variable::Static(v) => (
-                v.name.clone(),
-                match v.kind {
-                    VariableKind::String => "\"test\"",
-                    VariableKind::Uint => "\"0\"",
-                    VariableKind::Int => "0",
-                    VariableKind::Decimal => "\"0.0\"",
-                    VariableKind::Timestamp => "0",
-                    VariableKind::Bool => "true",
-                    VariableKind::Amount => "\"0\"",
-                    VariableKind::Asset => "\"test\"",
-                    VariableKind::Json => "true",
-                },
-            ),
-            Variable::External(v) => (
-                v.name.clone(),
-                match v.kind {
-                    VariableKind::String => "\"test\"",
-                    VariableKind::Uint => "\"0\"",
-                    VariableKind::Int => "0",
-                    VariableKind::Decimal => "\"0.0\"",
-                    VariableKind::Timestamp => "0",
-                    VariableKind::Bool => "true",
-                    VariableKind::Amount => "\"0\"",
-                    VariableKind::Asset => "\"test\"",
-                    VariableKind::Json => "true",
-                },
-            ),
-            Variable::Query(v) => (
-                v.name.clone(),
-                match v.kind {
-                    VariableKind::String => "\"test\"",
-                    VariableKind::Uint => "\"0\"",
-                    VariableKind::Int => "0",
-                    VariableKind::Decimal => "\"0.0\"",
-                    VariableKind::Timestamp => "0",
-                    VariableKind::Bool => "true",
-                    VariableKind::Amount => "\"0\"",
-                    VariableKind::Asset => "\"test\"",
-                    VariableKind::Json => "true",
-                },
-            ),
-        };
-        if replacement.contains("$warp.variable") {
-            return Err(ContractError::HydrationError {
-                msg: "Attempt to inject warp variable.".to_string(),
-            });
-        }
-        let replacement_with_encode = match var {
-            Variable::Static(v) => match v.encode {
-                true => format!("\"{}\"", base64::encode(replacement)),
-                false => replacement.to_string(),
-            },
-            Variable::External(v) => match v.encode {
-                true => format!("\"{}\"", base64::encode(replacement)),
-                false => replacement.to_string(),
-            },
-            Variable::Query(v) => match v.encode {
-                true => format!("\"{}\"", base64::encode(replacement)),
-                false => replacement.to_string(),
-            },
-        };
-        replaced_msgs = replaced_msgs.replace(
-            &format!("\"$warp.variable.{}\"", name),
-            &replacement_with_encode,
-        );
-    }
+// Msgs go through the same substitution as hydrate_msgs, with dummy values for the vars
+pub fn msgs_valid(msgs: &str, vars: &[Variable]) -> Result<bool, ContractError> {
+    let values = vars
+        .iter()
+        .map(|var| {
+            let (name, kind, encode) = match var {
+                Variable::Static(v) => (&v.name, &v.kind, v.encode),
+                Variable::History(v) => (&v.name, &v.kind, v.encode),
+                Variable::External(v) => (&v.name, &v.kind, v.encode),
+                Variable::Query(v) => (&v.name, &v.kind, v.encode),
+                Variable::RawQuery(v) => (&v.name, &v.kind, v.encode),
+            };
+            let replacement = dummy_replacement(kind);
+            let replacement = match encode {
+                true => Json::String(base64::encode(encode_json(&replacement)?)),
+                false => replacement,
+            };
+            Ok((name.clone(), replacement))
+        })
+        .collect::<Result<HashMap<_, _>, ContractError>>()?;
 
-    let _msgs = serde_json_wasm::from_str::<Vec<WarpMsg>>(&replaced_msgs)?;
+    substitute_msgs(msgs, &values)?;
 
     Ok(true)
 }
 
+// JSON value of the kind's type, standing in for a var when checking that msgs deserialize
+fn dummy_replacement(kind: &VariableKind) -> Json {
+    match kind {
+        VariableKind::String => Json::String("test".to_string()),
+        VariableKind::Uint => Json::String("0".to_string()),
+        VariableKind::Int => Json::I128(0),
+        VariableKind::Decimal => Json::String("0.0".to_string()),
+        VariableKind::Timestamp => Json::I128(0),
+        VariableKind::Bool => Json::Bool(true),
+        VariableKind::Amount => Json::String("0".to_string()),
+        VariableKind::Asset => Json::String("test".to_string()),
+        VariableKind::Json => Json::Bool(true),
+    }
+}
+
 pub fn apply_var_fn(
     deps: Deps,
     env: Env,
     vars: Vec<Variable>,
     status: JobStatus,
-    warp_account_addr: Option<String>,
+    job_env: &JobEnv,
 ) -> Result<String, ContractError> {
     let mut res = vec![];
     for var in vars.clone() {
+        let (var, original) = split_var(var, None)?;
         match var {
             Variable::Static(mut v) => {
                 match v.update_fn.clone() {
@@ -838,8 +1063,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_uint(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_uint(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Int(nv) => {
@@ -849,8 +1080,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Decimal(nv) => {
@@ -860,8 +1097,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_decimal(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_decimal(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Timestamp(nv) => {
@@ -871,8 +1114,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::BlockHeight(nv) => {
@@ -883,8 +1132,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Bool(val) => {
@@ -910,7 +1165,7 @@ pub fn apply_var_fn(
                                             env.clone(),
                                             val,
                                             &vars,
-                                            warp_account_addr.clone(),
+                                            job_env,
                                         )?
                                         .to_string(),
                                     );
@@ -927,8 +1182,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_uint(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_uint(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Int(nv) => {
@@ -938,8 +1199,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Decimal(nv) => {
@@ -949,8 +1216,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_decimal(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_decimal(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Timestamp(nv) => {
@@ -960,8 +1233,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::BlockHeight(nv) => {
@@ -972,8 +1251,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     );
                                 }
                                 FnValue::Bool(val) => {
@@ -999,7 +1284,7 @@ pub fn apply_var_fn(
                                             env.clone(),
                                             val,
                                             &vars,
-                                            warp_account_addr.clone(),
+                                            job_env,
                                         )?
                                         .to_string(),
                                     );
@@ -1034,8 +1319,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_uint(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_uint(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Int(nv) => {
@@ -1045,8 +1336,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Decimal(nv) => {
@@ -1056,8 +1353,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_decimal(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_decimal(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Timestamp(nv) => {
@@ -1068,8 +1371,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::BlockHeight(nv) => {
@@ -1080,8 +1389,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Bool(val) => {
@@ -1107,7 +1422,7 @@ pub fn apply_var_fn(
                                             env.clone(),
                                             val,
                                             &vars,
-                                            warp_account_addr.clone(),
+                                            job_env,
                                         )?
                                         .to_string(),
                                     )
@@ -1124,8 +1439,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_uint(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_uint(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Int(nv) => {
@@ -1135,8 +1456,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Decimal(nv) => {
@@ -1146,8 +1473,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_decimal(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_decimal(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Timestamp(nv) => {
@@ -1158,8 +1491,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::BlockHeight(nv) => {
@@ -1170,8 +1509,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Bool(val) => {
@@ -1197,7 +1542,7 @@ pub fn apply_var_fn(
                                             env.clone(),
                                             val,
                                             &vars,
-                                            warp_account_addr.clone(),
+                                            job_env,
                                         )?
                                         .to_string(),
                                     )
@@ -1232,8 +1577,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_uint(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_uint(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Int(nv) => {
@@ -1243,8 +1594,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Decimal(nv) => {
@@ -1254,8 +1611,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_decimal(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_decimal(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Timestamp(nv) => {
@@ -1265,8 +1628,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::BlockHeight(nv) => {
@@ -1277,8 +1646,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Bool(val) => {
@@ -1304,7 +1679,7 @@ pub fn apply_var_fn(
                                             env.clone(),
                                             val,
                                             &vars,
-                                            warp_account_addr.clone(),
+                                            job_env,
                                         )?
                                         .to_string(),
                                     )
@@ -1321,8 +1696,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_uint(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_uint(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Int(nv) => {
@@ -1332,8 +1713,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Decimal(nv) => {
@@ -1343,8 +1730,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_decimal(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_decimal(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Timestamp(nv) => {
@@ -1354,8 +1747,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::BlockHeight(nv) => {
@@ -1365,8 +1764,14 @@ pub fn apply_var_fn(
                                         });
                                     }
                                     v.value = Some(
-                                        resolve_num_value_int(deps, env.clone(), nv, &vars)?
-                                            .to_string(),
+                                        resolve_num_value_int(
+                                            deps,
+                                            env.clone(),
+                                            nv,
+                                            &vars,
+                                            job_env,
+                                        )?
+                                        .to_string(),
                                     )
                                 }
                                 FnValue::Bool(val) => {
@@ -1392,7 +1797,7 @@ pub fn apply_var_fn(
                                             env.clone(),
                                             val,
                                             &vars,
-                                            warp_account_addr.clone(),
+                                            job_env,
                                         )?
                                         .to_string(),
                                     )
@@ -1408,6 +1813,19 @@ pub fn apply_var_fn(
                 }
                 res.push(Variable::Query(v));
             }
+            var => res.push(var),
+        }
+
+        if original.is_some() {
+            match res.pop().map(|var| join_var(var, original)) {
+                // history vars record the value they end the execution with
+                Some(Variable::History(mut v)) => {
+                    record_history(&mut v);
+                    res.push(Variable::History(v));
+                }
+                Some(var) => res.push(var),
+                None => (),
+            }
         }
     }
     Ok(serde_json_wasm::to_string(&res)?)
@@ -1419,6 +1837,8 @@ pub fn get_var(name: String, vars: &Vec<Variable>) -> Result<&Variable, Contract
             Variable::Static(v) => v.name.clone(),
             Variable::External(v) => v.name.clone(),
             Variable::Query(v) => v.name.clone(),
+            Variable::History(v) => v.name.clone(),
+            Variable::RawQuery(v) => v.name.clone(),
         };
         if format!("$warp.variable.{}", n) == name {
             return Ok(var);
@@ -1427,64 +1847,11 @@ pub fn get_var(name: String, vars: &Vec<Variable>) -> Result<&Variable, Contract
     Err(ContractError::VariableNotFound { name })
 }
 
-pub fn has_duplicates(vars: &Vec<Variable>) -> bool {
-    for i in 0..vars.len() {
-        for j in i..vars.len() {
-            if i != j {
-                match vars[i].clone() {
-                    Variable::Static(vari) => match vars[j].clone() {
-                        Variable::Static(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                        Variable::External(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                        Variable::Query(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                    },
-                    Variable::External(vari) => match vars[j].clone() {
-                        Variable::Static(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                        Variable::External(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                        Variable::Query(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                    },
-                    Variable::Query(vari) => match vars[j].clone() {
-                        Variable::Static(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                        Variable::External(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                        Variable::Query(varj) => {
-                            if vari.name == varj.name {
-                                return true;
-                            }
-                        }
-                    },
-                }
-            }
+pub fn has_duplicates(vars: &[Variable]) -> bool {
+    let names: Vec<String> = vars.iter().map(get_var_name).collect();
+    for i in 0..names.len() {
+        if names[i + 1..].contains(&names[i]) {
+            return true;
         }
     }
     false
@@ -1507,12 +1874,154 @@ fn get_var_name(var: &Variable) -> String {
         Variable::Static(v) => v.name,
         Variable::External(v) => v.name,
         Variable::Query(v) => v.name,
+        Variable::History(v) => v.name,
+        Variable::RawQuery(v) => v.name,
+    }
+}
+
+// History and raw query vars hydrate and update like static and query vars, so they are split
+// into those around that logic and joined back afterwards. Raw query keys are only built when
+// vars are given, other uses need no init_fn.
+fn split_var(
+    var: Variable,
+    vars: Option<&[Variable]>,
+) -> Result<(Variable, Option<Variable>), ContractError> {
+    match var {
+        Variable::History(v) => Ok((Variable::Static(v.to_static()), Some(Variable::History(v)))),
+        Variable::RawQuery(v) => {
+            let init_fn = match vars {
+                Some(vars) => raw_query_expr(&v.init_fn, vars)?,
+                None => QueryExpr {
+                    selector: v.init_fn.selector.clone(),
+                    query: QueryRequest::Wasm(WasmQuery::Raw {
+                        contract_addr: v.init_fn.contract_addr.clone(),
+                        key: Binary::default(),
+                    }),
+                },
+            };
+            Ok((
+                Variable::Query(v.to_query(init_fn)),
+                Some(Variable::RawQuery(v)),
+            ))
+        }
+        var => Ok((var, None)),
+    }
+}
+
+fn join_var(var: Variable, original: Option<Variable>) -> Variable {
+    match (var, original) {
+        (Variable::Static(v), Some(Variable::History(history))) => {
+            Variable::History(HistoryVariable {
+                value: v.value,
+                ..history
+            })
+        }
+        (Variable::Query(v), Some(Variable::RawQuery(raw))) => {
+            Variable::RawQuery(RawQueryVariable {
+                value: v.value,
+                ..raw
+            })
+        }
+        (var, _) => var,
+    }
+}
+
+// UseDefault needs a default, and defaults must be valid values of their var
+fn fallback_valid(var: &Variable) -> bool {
+    let (on_error, default_var) = match var {
+        Variable::Static(v) => (
+            &v.on_error,
+            v.default.clone().map(|default| {
+                Variable::Static(StaticVariable {
+                    value: Some(default),
+                    default: None,
+                    on_error: None,
+                    ..v.clone()
+                })
+            }),
+        ),
+        Variable::External(v) => (
+            &v.on_error,
+            v.default.clone().map(|default| {
+                Variable::External(ExternalVariable {
+                    value: Some(default),
+                    default: None,
+                    on_error: None,
+                    ..v.clone()
+                })
+            }),
+        ),
+        Variable::Query(v) => (
+            &v.on_error,
+            v.default.clone().map(|default| {
+                Variable::Query(QueryVariable {
+                    value: Some(default),
+                    default: None,
+                    on_error: None,
+                    ..v.clone()
+                })
+            }),
+        ),
+        _ => return true,
+    };
+
+    match default_var {
+        None => on_error != &Some(OnErrorPolicy::UseDefault),
+        Some(default_var) => vars_valid(&vec![default_var]),
     }
 }
 
 pub fn vars_valid(vars: &Vec<Variable>) -> bool {
     for var in vars {
+        if !fallback_valid(var) {
+            return false;
+        }
+
         match var {
+            Variable::RawQuery(v) => {
+                if !vars_valid(&vec![Variable::Query(v.to_query(QueryExpr {
+                    selector: v.init_fn.selector.clone(),
+                    query: QueryRequest::Wasm(WasmQuery::Raw {
+                        contract_addr: v.init_fn.contract_addr.clone(),
+                        key: Binary::default(),
+                    }),
+                }))]) {
+                    return false;
+                }
+                // u64 key parts can only be checked once they hold no references
+                if v.init_fn.keys.iter().any(|key| match key {
+                    RawKey::U64(val) => {
+                        !val.contains("$warp.variable.") && val.parse::<u64>().is_err()
+                    }
+                    _ => false,
+                }) {
+                    return false;
+                }
+            }
+            Variable::History(v) => {
+                if !vars_valid(&vec![Variable::Static(v.to_static())]) {
+                    return false;
+                }
+                // recorded values are resolved as Decimal
+                if !matches!(
+                    v.kind,
+                    VariableKind::Uint | VariableKind::Decimal | VariableKind::Amount
+                ) {
+                    return false;
+                }
+                if v.capacity == 0
+                    || v.capacity > MAX_HISTORY_CAPACITY
+                    || v.history.len() > v.capacity as usize
+                {
+                    return false;
+                }
+                if v.history
+                    .iter()
+                    .any(|val| Decimal256::from_str(val).is_err())
+                {
+                    return false;
+                }
+            }
             Variable::Static(v) => {
                 if v.reinitialize && v.update_fn.is_some() {
                     return false;
@@ -1526,7 +2035,7 @@ pub fn vars_valid(vars: &Vec<Variable>) -> bool {
                             }
                         }
                         VariableKind::Int => {
-                            if i128::from_str(&val).is_err() {
+                            if Int256::from_str(&val).is_err() {
                                 return false;
                             }
                         }
@@ -1536,7 +2045,7 @@ pub fn vars_valid(vars: &Vec<Variable>) -> bool {
                             }
                         }
                         VariableKind::Timestamp => {
-                            if i128::from_str(&val).is_err() {
+                            if Int256::from_str(&val).is_err() {
                                 return false;
                             }
                         }
@@ -1564,6 +2073,10 @@ pub fn vars_valid(vars: &Vec<Variable>) -> bool {
                     return false;
                 }
 
+                if !trusted_signers_valid(&v.init_fn) {
+                    return false;
+                }
+
                 if let Some(val) = v.value.clone() {
                     match v.kind {
                         VariableKind::String => {}
@@ -1573,7 +2086,7 @@ pub fn vars_valid(vars: &Vec<Variable>) -> bool {
                             }
                         }
                         VariableKind::Int => {
-                            if i128::from_str(&val).is_err() {
+                            if Int256::from_str(&val).is_err() {
                                 return false;
                             }
                         }
@@ -1583,7 +2096,7 @@ pub fn vars_valid(vars: &Vec<Variable>) -> bool {
                             }
                         }
                         VariableKind::Timestamp => {
-                            if i128::from_str(&val).is_err() {
+                            if Int256::from_str(&val).is_err() {
                                 return false;
                             }
                         }
@@ -1619,7 +2132,7 @@ pub fn vars_valid(vars: &Vec<Variable>) -> bool {
                             }
                         }
                         VariableKind::Int => {
-                            if i128::from_str(&val).is_err() {
+                            if Int256::from_str(&val).is_err() {
                                 return false;
                             }
                         }
@@ -1629,7 +2142,7 @@ pub fn vars_valid(vars: &Vec<Variable>) -> bool {
                             }
                         }
                         VariableKind::Timestamp => {
-                            if i128::from_str(&val).is_err() {
+                            if Int256::from_str(&val).is_err() {
                                 return false;
                             }
                         }
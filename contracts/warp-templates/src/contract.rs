@@ -1,9 +1,11 @@
-use crate::state::{CONFIG, QUERY_PAGE_SIZE, STATE, TEMPLATES};
+use crate::state::{CONFIG, OWNERSHIP, QUERY_PAGE_SIZE, STATE, TEMPLATES};
 use crate::ContractError;
+use common::migrate::ensure_migratable;
 use cosmwasm_std::{
     entry_point, to_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
     Order, Response, StdError, StdResult, Uint64,
 };
+use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 
 use templates::template::{
@@ -11,10 +13,14 @@ use templates::template::{
     Template, TemplateResponse, TemplatesResponse,
 };
 use templates::{
-    Config, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryConfigMsg, QueryMsg,
-    State, UpdateConfigMsg,
+    AcceptOwnershipMsg, CancelOwnershipProposalMsg, Config, ConfigResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, PendingOwnerResponse, ProposeOwnerMsg, QueryConfigMsg, QueryMsg,
+    QueryPendingOwnerMsg, State, UpdateConfigMsg,
 };
 
+pub const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 const MAX_TEXT_LENGTH: usize = 280;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -43,6 +49,7 @@ pub fn instantiate(
     }
 
     STATE.save(deps.storage, &state)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
@@ -65,6 +72,11 @@ pub fn execute(
         ExecuteMsg::DeleteTemplate(data) => delete_template(deps, env, info, data),
 
         ExecuteMsg::UpdateConfig(data) => update_config(deps, env, info, data),
+        ExecuteMsg::ProposeOwner(data) => propose_owner(deps, env, info, data),
+        ExecuteMsg::AcceptOwnership(data) => accept_ownership(deps, env, info, data),
+        ExecuteMsg::CancelOwnershipProposal(data) => {
+            cancel_ownership_proposal(deps, env, info, data)
+        }
     }
 }
 
@@ -74,12 +86,19 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::QueryTemplate(data) => to_binary(&query_template(deps, env, data)?),
         QueryMsg::QueryTemplates(data) => to_binary(&query_templates(deps, env, data)?),
         QueryMsg::QueryConfig(data) => to_binary(&query_config(deps, env, data)?),
+        QueryMsg::QueryPendingOwner(data) => to_binary(&query_pending_owner(deps, env, data)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    Ok(Response::new())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_migratable(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, &msg.version)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("version", CONTRACT_VERSION))
 }
 
 pub fn submit_template(
@@ -213,7 +232,7 @@ pub fn delete_template(
 
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     data: UpdateConfigMsg,
 ) -> Result<Response, ContractError> {
@@ -222,10 +241,19 @@ pub fn update_config(
         return Err(ContractError::Unauthorized {});
     }
 
-    config.owner = match data.owner {
-        None => config.owner,
-        Some(data) => deps.api.addr_validate(data.as_str())?,
-    };
+    if let Some(owner) = &data.owner {
+        OWNERSHIP.propose(
+            deps.storage,
+            deps.api,
+            &env,
+            &info.sender,
+            &config.owner,
+            ProposeOwnerMsg {
+                owner: owner.clone(),
+                expires_at_time: None,
+            },
+        )?;
+    }
 
     config.fee_denom = match data.fee_denom {
         None => config.fee_denom,
@@ -244,7 +272,50 @@ pub fn update_config(
         .add_attribute("action", "update_config")
         .add_attribute("owner", config.owner)
         .add_attribute("template_fee", config.template_fee)
-        .add_attribute("fee_collector", config.fee_collector))
+        .add_attribute("fee_collector", config.fee_collector)
+        .add_attributes(data.owner.map(|owner| ("pending_owner", owner))))
+}
+
+pub fn propose_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data: ProposeOwnerMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(OWNERSHIP.propose(
+        deps.storage,
+        deps.api,
+        &env,
+        &info.sender,
+        &config.owner,
+        data,
+    )?)
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    _data: AcceptOwnershipMsg,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let (owner, response) = OWNERSHIP.accept(deps.storage, &env, &info.sender, &config.owner)?;
+
+    config.owner = owner;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+pub fn cancel_ownership_proposal(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _data: CancelOwnershipProposalMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(OWNERSHIP.cancel(deps.storage, &info.sender, &config.owner)?)
 }
 
 pub fn query_template(
@@ -327,3 +398,11 @@ pub fn query_config(deps: Deps, _env: Env, _data: QueryConfigMsg) -> StdResult<C
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse { config })
 }
+
+pub fn query_pending_owner(
+    deps: Deps,
+    _env: Env,
+    _data: QueryPendingOwnerMsg,
+) -> StdResult<PendingOwnerResponse> {
+    OWNERSHIP.query(deps.storage)
+}
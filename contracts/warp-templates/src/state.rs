@@ -1,3 +1,4 @@
+use common::ownership::Ownership;
 use cw_storage_plus::{Item, Map};
 use templates::template::Template;
 use templates::{Config, State};
@@ -5,5 +6,6 @@ use templates::{Config, State};
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const TEMPLATES: Map<u64, Template> = Map::new("templates");
 pub const STATE: Item<State> = Item::new("state");
+pub const OWNERSHIP: Ownership = Ownership::new("pending_owner");
 
 pub const QUERY_PAGE_SIZE: u32 = 50;
@@ -1,3 +1,7 @@
+pub use common::ownership::{
+    AcceptOwnershipMsg, CancelOwnershipProposalMsg, PendingOwner, PendingOwnerResponse,
+    ProposeOwnerMsg, QueryPendingOwnerMsg,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint64};
 
@@ -34,12 +38,18 @@ pub enum ExecuteMsg {
     FreeJobAccount(FreeJobAccountMsg),
     TakeFundingAccount(TakeFundingAccountMsg),
     FreeFundingAccount(FreeFundingAccountMsg),
+
     UpdateConfig(UpdateConfigMsg),
+    ProposeOwner(ProposeOwnerMsg),
+    AcceptOwnership(AcceptOwnershipMsg),
+    CancelOwnershipProposal(CancelOwnershipProposalMsg),
 }
 
 #[cw_serde]
 pub struct UpdateConfigMsg {
+    // Only proposes the new admin, who still has to send AcceptOwnership
     pub admin: Option<String>,
+    pub warp_addr: Option<String>,
 }
 
 #[cw_serde]
@@ -81,6 +91,8 @@ pub struct AddFundingAccountMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     QueryConfig(QueryConfigMsg),
+    #[returns(PendingOwnerResponse)]
+    QueryPendingOwner(QueryPendingOwnerMsg),
     #[returns(AccountsResponse)]
     QueryAccounts(QueryAccountsMsg),
     #[returns(JobAccountsResponse)]
@@ -198,4 +210,7 @@ pub struct FundingAccountResponse {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // Version of the code being migrated to, must match the new contract version
+    pub version: String,
+}
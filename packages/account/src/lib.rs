@@ -45,4 +45,7 @@ pub struct ConfigResponse {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // Version of the code being migrated to, must match the new contract version
+    pub version: String,
+}
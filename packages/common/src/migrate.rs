@@ -0,0 +1,61 @@
+use cosmwasm_std::{StdError, Storage};
+use semver::Version;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MigrationError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Migration target version {target} does not match contract version {version}.")]
+    VersionMismatch { target: String, version: String },
+
+    #[error("Cannot migrate from contract {contract}.")]
+    ContractMismatch { contract: String },
+
+    #[error("Cannot migrate from version {stored} to older version {version}.")]
+    Downgrade { stored: String, version: String },
+
+    #[error("Invalid contract version.")]
+    InvalidContractVersion {},
+}
+
+impl From<semver::Error> for MigrationError {
+    fn from(_: semver::Error) -> Self {
+        MigrationError::InvalidContractVersion {}
+    }
+}
+
+// Checks that the contract stored under cw2 can be migrated to `name` at `version`,
+// `target` being the version requested in the MigrateMsg
+pub fn ensure_migratable(
+    storage: &dyn Storage,
+    name: &str,
+    version: &str,
+    target: &str,
+) -> Result<(), MigrationError> {
+    if target != version {
+        return Err(MigrationError::VersionMismatch {
+            target: target.to_string(),
+            version: version.to_string(),
+        });
+    }
+
+    // contracts deployed before version tracking have no cw2 info stored
+    if let Some(stored) = cw2::CONTRACT.may_load(storage)? {
+        if stored.contract != name {
+            return Err(MigrationError::ContractMismatch {
+                contract: stored.contract,
+            });
+        }
+
+        if stored.version.parse::<Version>()? > version.parse::<Version>()? {
+            return Err(MigrationError::Downgrade {
+                stored: stored.version,
+                version: version.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, Env, Response, StdError, StdResult, Storage, Uint64};
+use cw_storage_plus::Item;
+use thiserror::Error;
+
+#[cw_serde]
+pub struct PendingOwner {
+    pub owner: Addr,
+    // Timestamp (seconds) after which the proposal can no longer be accepted
+    pub expires_at_time: Option<Uint64>,
+}
+
+#[cw_serde]
+pub struct ProposeOwnerMsg {
+    pub owner: String,
+    pub expires_at_time: Option<Uint64>,
+}
+
+#[cw_serde]
+pub struct AcceptOwnershipMsg {}
+
+#[cw_serde]
+pub struct CancelOwnershipProposalMsg {}
+
+#[cw_serde]
+pub struct QueryPendingOwnerMsg {}
+
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<PendingOwner>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OwnershipError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("No pending ownership proposal.")]
+    NoPendingOwner {},
+
+    #[error("Ownership proposal expired.")]
+    OwnershipProposalExpired {},
+
+    #[error("Ownership proposal expiry must be in the future.")]
+    InvalidOwnershipProposalExpiry {},
+}
+
+// Two-step ownership transfer: the owner proposes, the proposed address accepts.
+// The owner itself stays in each contract's config, only the proposal is stored here.
+pub struct Ownership<'a> {
+    pending_owner: Item<'a, PendingOwner>,
+}
+
+impl<'a> Ownership<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        Ownership {
+            pending_owner: Item::new(namespace),
+        }
+    }
+
+    pub fn propose(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        env: &Env,
+        sender: &Addr,
+        owner: &Addr,
+        data: ProposeOwnerMsg,
+    ) -> Result<Response, OwnershipError> {
+        if sender != owner {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        if let Some(expires_at_time) = data.expires_at_time {
+            if expires_at_time.u64() <= env.block.time.seconds() {
+                return Err(OwnershipError::InvalidOwnershipProposalExpiry {});
+            }
+        }
+
+        let pending_owner = PendingOwner {
+            owner: api.addr_validate(&data.owner)?,
+            expires_at_time: data.expires_at_time,
+        };
+
+        self.pending_owner.save(storage, &pending_owner)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "propose_owner")
+            .add_attribute("pending_owner", pending_owner.owner)
+            .add_attribute(
+                "pending_owner_expires_at_time",
+                serde_json_wasm::to_string(&pending_owner.expires_at_time)
+                    .map_err(|e| StdError::serialize_err("Option<Uint64>", e))?,
+            ))
+    }
+
+    // Returns the new owner, the caller is responsible for persisting it in its config
+    pub fn accept(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        owner: &Addr,
+    ) -> Result<(Addr, Response), OwnershipError> {
+        let pending_owner = self
+            .pending_owner
+            .may_load(storage)?
+            .ok_or(OwnershipError::NoPendingOwner {})?;
+
+        if *sender != pending_owner.owner {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        if let Some(expires_at_time) = pending_owner.expires_at_time {
+            if env.block.time.seconds() >= expires_at_time.u64() {
+                return Err(OwnershipError::OwnershipProposalExpired {});
+            }
+        }
+
+        self.pending_owner.remove(storage);
+
+        let response = Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("previous_owner", owner)
+            .add_attribute("owner", &pending_owner.owner);
+
+        Ok((pending_owner.owner, response))
+    }
+
+    pub fn cancel(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        owner: &Addr,
+    ) -> Result<Response, OwnershipError> {
+        if sender != owner {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        if self.pending_owner.may_load(storage)?.is_none() {
+            return Err(OwnershipError::NoPendingOwner {});
+        }
+
+        self.pending_owner.remove(storage);
+
+        Ok(Response::new().add_attribute("action", "cancel_ownership_proposal"))
+    }
+
+    pub fn query(&self, storage: &dyn Storage) -> StdResult<PendingOwnerResponse> {
+        Ok(PendingOwnerResponse {
+            pending_owner: self.pending_owner.may_load(storage)?,
+        })
+    }
+}
@@ -302,21 +302,20 @@ fn withdraw_asset_cw721(
 pub fn ibc_transfer(env: Env, data: IbcTransferMsg) -> Result<Response, StdError> {
     let mut transfer_msg = data.transfer_msg.clone();
 
-    if data.timeout_block_delta.is_some() && data.transfer_msg.timeout_block.is_some() {
-        let block = transfer_msg.timeout_block.unwrap();
+    if let (Some(timeout_block_delta), Some(block)) =
+        (data.timeout_block_delta, transfer_msg.timeout_block.clone())
+    {
         transfer_msg.timeout_block = Some(TimeoutBlock {
             revision_number: Some(block.revision_number()),
-            revision_height: Some(env.block.height + data.timeout_block_delta.unwrap()),
+            revision_height: Some(env.block.height + timeout_block_delta),
         })
     }
 
-    if data.timeout_timestamp_seconds_delta.is_some() {
+    if let Some(timeout_timestamp_seconds_delta) = data.timeout_timestamp_seconds_delta {
         transfer_msg.timeout_timestamp = Some(
             env.block
                 .time
-                .plus_seconds(
-                    env.block.time.seconds() + data.timeout_timestamp_seconds_delta.unwrap(),
-                )
+                .plus_seconds(env.block.time.seconds() + timeout_timestamp_seconds_delta)
                 .nanos(),
         );
     }
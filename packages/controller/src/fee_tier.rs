@@ -0,0 +1,62 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Uint128, Uint64};
+
+#[cw_serde]
+pub struct FeeTier {
+    pub name: String,
+    // Percentage (0-100) taken off each fee component, 100 waives the fee entirely
+    pub creation_fee_discount: Uint64,
+    pub maintenance_fee_discount: Uint64,
+    pub burn_fee_discount: Uint64,
+    // Minimum balance held in config.fee_tier_stake_contract to qualify for this tier.
+    // None means the tier can only be assigned to addresses explicitly by the owner
+    pub stake_threshold: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct SetFeeTierMsg {
+    pub tier: FeeTier,
+}
+
+#[cw_serde]
+pub struct RemoveFeeTierMsg {
+    pub name: String,
+}
+
+#[cw_serde]
+pub struct AssignFeeTierMsg {
+    pub addresses: Vec<String>,
+    // None removes the explicit assignment, falling back to stake based tiers
+    pub tier: Option<String>,
+}
+
+#[cw_serde]
+pub struct QueryFeeTierMsg {
+    pub name: String,
+}
+
+#[cw_serde]
+pub struct QueryFeeTiersMsg {
+    pub start_after: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[cw_serde]
+pub struct QueryAddressFeeTierMsg {
+    pub address: String,
+}
+
+#[cw_serde]
+pub struct FeeTierResponse {
+    pub tier: FeeTier,
+}
+
+#[cw_serde]
+pub struct FeeTiersResponse {
+    pub tiers: Vec<FeeTier>,
+}
+
+#[cw_serde]
+pub struct AddressFeeTierResponse {
+    pub tier: Option<FeeTier>,
+}
@@ -1,6 +1,6 @@
 use crate::account::{AssetInfo, CwFund, WarpMsg};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128, Uint64};
+use cosmwasm_std::{Addr, Binary, Uint128, Uint64};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
@@ -35,6 +35,11 @@ pub struct Job {
     // For withdrawing assets on each job execution (recurring jobs),
     // use WithdrawAssets warp msg
     pub assets_to_withdraw: Vec<AssetInfo>,
+    // Executions across this job and the recurring jobs it follows up on
+    #[serde(default)]
+    pub execution_count: Uint64,
+    #[serde(default)]
+    pub last_execution_time: Option<Uint64>,
 }
 
 #[cw_serde]
@@ -114,6 +119,22 @@ pub struct EvictJobMsg {
 pub struct ExternalInput {
     pub name: String,
     pub input: String,
+    // required for external vars with trusted signers
+    pub signature: Option<Binary>,
+    pub timestamp: Option<Uint64>,
+}
+
+// Signed by the trusted signers of external vars: secp256k1 signers sign the sha256 of its JSON,
+// ed25519 signers the JSON itself. Chain and controller keep signatures from being replayed on
+// other deployments, where the same job id can exist.
+#[cw_serde]
+pub struct ExternalInputSignDoc {
+    pub chain_id: String,
+    pub controller_addr: String,
+    pub job_id: Uint64,
+    pub name: String,
+    pub value: String,
+    pub timestamp: Uint64,
 }
 
 #[cw_serde]
@@ -1,11 +1,20 @@
+use crate::fee_tier::{
+    AddressFeeTierResponse, AssignFeeTierMsg, FeeTierResponse, FeeTiersResponse,
+    QueryAddressFeeTierMsg, QueryFeeTierMsg, QueryFeeTiersMsg, RemoveFeeTierMsg, SetFeeTierMsg,
+};
 use crate::job::{
     CreateJobMsg, DeleteJobMsg, EvictJobMsg, ExecuteJobMsg, JobResponse, JobsResponse, QueryJobMsg,
     QueryJobsMsg, UpdateJobMsg,
 };
+pub use common::ownership::{
+    AcceptOwnershipMsg, CancelOwnershipProposalMsg, PendingOwner, PendingOwnerResponse,
+    ProposeOwnerMsg, QueryPendingOwnerMsg,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint64};
 
 pub mod account;
+pub mod fee_tier;
 pub mod job;
 
 //objects
@@ -35,6 +44,30 @@ pub struct Config {
     pub queue_size_left: Uint64,
     pub queue_size_right: Uint64,
     pub burn_fee_rate: Uint128,
+    // Contract queried (cw20 balance) to resolve stake based fee tiers
+    pub fee_tier_stake_contract: Option<Addr>,
+    // Address allowed to pause and unpause actions alongside the owner
+    pub guardian: Option<Addr>,
+    // Actions currently halted by the circuit breaker, DeleteJob can never be paused
+    #[serde(default)]
+    pub paused: Vec<PausableAction>,
+    // Delay between scheduling and executing governance sensitive config updates,
+    // 0 disables the timelock
+    #[serde(default)]
+    pub config_update_delay_seconds: Uint64,
+}
+
+impl Config {
+    pub fn is_paused(&self, action: &PausableAction) -> bool {
+        self.paused.contains(action)
+    }
+}
+
+#[cw_serde]
+pub enum PausableAction {
+    CreateJob,
+    ExecuteJob,
+    EvictJob,
 }
 
 #[cw_serde]
@@ -68,6 +101,9 @@ pub struct InstantiateMsg {
     pub queue_size_left: Uint64,
     pub queue_size_right: Uint64,
     pub burn_fee_rate: Uint128,
+    pub fee_tier_stake_contract: Option<String>,
+    pub guardian: Option<String>,
+    pub config_update_delay_seconds: Uint64,
 }
 
 //execute
@@ -81,20 +117,44 @@ pub enum ExecuteMsg {
     EvictJob(EvictJobMsg),
 
     UpdateConfig(UpdateConfigMsg),
+    ScheduleConfigUpdate(ScheduleConfigUpdateMsg),
+    ExecuteConfigUpdate(ExecuteConfigUpdateMsg),
+    CancelConfigUpdate(CancelConfigUpdateMsg),
 
     MigrateAccounts(MigrateAccountsMsg),
 
-    MigratePendingJobs(MigrateJobsMsg),
-    MigrateFinishedJobs(MigrateJobsMsg),
+    MigrateJobs(MigrateJobsMsg),
 
     CreateFundingAccount(CreateFundingAccountMsg),
+
+    // Fee tiers only discount the fees computed from the timelocked config,
+    // they can never charge more than it, so they take effect immediately
+    SetFeeTier(SetFeeTierMsg),
+    RemoveFeeTier(RemoveFeeTierMsg),
+    AssignFeeTier(AssignFeeTierMsg),
+
+    SetPaused(SetPausedMsg),
+    RemoveGuardian(RemoveGuardianMsg),
+
+    ProposeOwner(ProposeOwnerMsg),
+    AcceptOwnership(AcceptOwnershipMsg),
+    CancelOwnershipProposal(CancelOwnershipProposalMsg),
 }
 
 #[cw_serde]
 pub struct UpdateConfigMsg {
+    // Only proposes the new owner, who still has to send AcceptOwnership
     pub owner: Option<String>,
     pub fee_collector: Option<String>,
     pub minimum_reward: Option<Uint128>,
+    pub guardian: Option<String>,
+}
+
+// Governance sensitive config fields, only updatable after config.config_update_delay_seconds
+#[cw_serde]
+pub struct ScheduleConfigUpdateMsg {
+    pub resolver_address: Option<String>,
+    pub warp_account_code_id: Option<Uint64>,
     pub cancellation_fee_rate: Option<Uint64>,
     pub creation_fee_min: Option<Uint128>,
     pub creation_fee_max: Option<Uint128>,
@@ -109,19 +169,51 @@ pub struct UpdateConfigMsg {
     pub queue_size_left: Option<Uint64>,
     pub queue_size_right: Option<Uint64>,
     pub burn_fee_rate: Option<Uint128>,
+    pub fee_tier_stake_contract: Option<String>,
+    pub config_update_delay_seconds: Option<Uint64>,
+}
+
+#[cw_serde]
+pub struct ExecuteConfigUpdateMsg {
+    pub id: Uint64,
+}
+
+#[cw_serde]
+pub struct CancelConfigUpdateMsg {
+    pub id: Uint64,
 }
 
+#[cw_serde]
+pub struct PendingConfigUpdate {
+    pub id: Uint64,
+    pub update: ScheduleConfigUpdateMsg,
+    pub scheduled_at_time: Uint64,
+    pub executable_at_time: Uint64,
+}
+
+#[cw_serde]
+pub struct SetPausedMsg {
+    // Full set of paused actions, replaces the current one.
+    // The guardian can only add actions, lifting a pause is left to the owner.
+    pub paused: Vec<PausableAction>,
+}
+
+#[cw_serde]
+pub struct RemoveGuardianMsg {}
+
 #[cw_serde]
 pub struct MigrateAccountsMsg {
     pub account_owner_addr: String,
     pub warp_account_code_id: Uint64,
+    // Contract version of the new warp account code
+    pub warp_account_version: String,
     pub start_after: Option<String>,
     pub limit: u8,
 }
 
 #[cw_serde]
 pub struct MigrateJobsMsg {
-    pub start_after: Option<Uint64>,
+    // Maximum number of jobs moved to the current storage layout in this call
     pub limit: u8,
 }
 
@@ -142,6 +234,17 @@ pub enum QueryMsg {
 
     #[returns(StateResponse)]
     QueryState(QueryStateMsg),
+    #[returns(PendingOwnerResponse)]
+    QueryPendingOwner(QueryPendingOwnerMsg),
+    #[returns(PendingConfigUpdatesResponse)]
+    QueryPendingConfigUpdates(QueryPendingConfigUpdatesMsg),
+
+    #[returns(FeeTierResponse)]
+    QueryFeeTier(QueryFeeTierMsg),
+    #[returns(FeeTiersResponse)]
+    QueryFeeTiers(QueryFeeTiersMsg),
+    #[returns(AddressFeeTierResponse)]
+    QueryAddressFeeTier(QueryAddressFeeTierMsg),
 }
 
 #[cw_serde]
@@ -161,4 +264,18 @@ pub struct StateResponse {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct QueryPendingConfigUpdatesMsg {
+    pub start_after: Option<Uint64>,
+    pub limit: Option<u32>,
+}
+
+#[cw_serde]
+pub struct PendingConfigUpdatesResponse {
+    pub updates: Vec<PendingConfigUpdate>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    // Version of the code being migrated to, must match the new contract version
+    pub version: String,
+}
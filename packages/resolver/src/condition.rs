@@ -1,14 +1,24 @@
 use std::str;
 
+use controller::account::AssetInfo;
 use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::schemars::JsonSchema;
+use cosmwasm_schema::serde::{Deserialize, Serialize};
 use cosmwasm_std::{Decimal256, Uint256, Uint64};
 
+use crate::int256::Int256;
+
 #[cw_serde]
 pub enum Condition {
     And(Vec<Box<Condition>>),
     Or(Vec<Box<Condition>>),
     Not(Box<Condition>),
     Expr(Box<Expr>),
+    // Resolves to on_error instead of failing when cond errors (e.g. a failing query)
+    Try {
+        cond: Box<Condition>,
+        on_error: bool,
+    },
 }
 
 #[cw_serde]
@@ -18,16 +28,41 @@ pub struct GenExpr<Type, Op> {
     pub right: Type,
 }
 
+// Compares the block time (seconds) against a timestamp, the legacy fixed form stays decodable
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(crate = "::cosmwasm_schema::serde", untagged)]
+#[schemars(crate = "::cosmwasm_schema::schemars")]
+pub enum TimeExpr {
+    Num(TimestampExpr),
+    Fixed(FixedTimeExpr),
+}
+
 #[cw_serde]
-pub struct TimeExpr {
+pub struct TimestampExpr {
+    pub op: NumOp<NumValue<Uint256, NumExprOp, IntFnOp>>,
+    pub value: NumValue<Uint256, NumExprOp, IntFnOp>,
+}
+
+#[cw_serde]
+pub struct FixedTimeExpr {
     pub comparator: Uint64,
-    pub op: TimeOp, //tmp: fix this post-comp
+    pub op: TimeOp,
 }
 
 #[cw_serde]
 pub struct BlockExpr {
     pub comparator: Uint64,
-    pub op: NumOp,
+    pub op: NumOp<Uint64>,
+}
+
+// Compares the balance address holds of asset, issuing the bank / cw20 / cw721 query itself.
+// Cw721 balances are 1 if address owns the token, 0 otherwise
+#[cw_serde]
+pub struct BalanceExpr {
+    pub address: StringValue<String>,
+    pub asset: AssetInfo,
+    pub op: NumOp<NumValue<Uint256, NumExprOp, IntFnOp>>,
+    pub value: NumValue<Uint256, NumExprOp, IntFnOp>,
 }
 
 #[cw_serde]
@@ -40,6 +75,10 @@ pub enum StringValue<T> {
 #[cw_serde]
 pub enum StringEnvValue {
     WarpAccountAddr,
+    JobOwner,
+    FundingAccountAddr,
+    ControllerAddr,
+    ChainId,
 }
 
 #[cw_serde]
@@ -49,12 +88,57 @@ pub enum NumValue<T, ExprOp, FnOp> {
     Ref(String),
     Fn(NumFnValue<T, ExprOp, FnOp>),
     Env(NumEnvValue),
+    Clamp(NumClampValue<T, ExprOp, FnOp>),
+    Convert(Box<NumConversion>),
+    History(NumHistoryValue),
+}
+
+// Converts between numeric kinds, resolving to the kind of the enclosing NumValue
+#[cw_serde]
+pub enum NumConversion {
+    // Uint amount to Decimal, divided by 10^scale (e.g. 1_500_000 with scale 6 is 1.5)
+    ToDecimal {
+        value: NumValue<Uint256, NumExprOp, IntFnOp>,
+        scale: u32,
+    },
+    // Decimal to Uint amount, multiplied by 10^scale and rounded to a whole number
+    ToUint {
+        value: NumValue<Decimal256, NumExprOp, DecimalFnOp>,
+        scale: u32,
+        rounding: RoundingMode,
+    },
+    ParseString(StringValue<String>),
+}
+
+// Function over the values recorded by a history variable, only resolvable as a Decimal
+#[cw_serde]
+pub struct NumHistoryValue {
+    // $warp.variable.{name} of the history variable
+    pub var: String,
+    pub op: HistoryFnOp,
+}
+
+#[cw_serde]
+pub enum HistoryFnOp {
+    Avg,
+    Min,
+    Max,
+    // n-th most recent value, Last(1) being the latest
+    Last(u32),
+    // exponential moving average from oldest to latest, alpha in (0, 1] weighting the newer value
+    Ema(Decimal256),
+    // population standard deviation
+    StdDev,
 }
 
 #[cw_serde]
 pub enum NumEnvValue {
     Time,
     BlockHeight,
+    JobId,
+    ExecutionCount,
+    JobCreatedAt,
+    LastExecutionTime,
 }
 
 #[cw_serde]
@@ -70,6 +154,14 @@ pub struct NumFnValue<T, ExprOp, FnOp> {
     pub right: Box<NumValue<T, ExprOp, FnOp>>,
 }
 
+// Bounds value to min <= value <= max
+#[cw_serde]
+pub struct NumClampValue<T, ExprOp, FnOp> {
+    pub value: Box<NumValue<T, ExprOp, FnOp>>,
+    pub min: Box<NumValue<T, ExprOp, FnOp>>,
+    pub max: Box<NumValue<T, ExprOp, FnOp>>,
+}
+
 #[cw_serde]
 pub enum NumExprOp {
     Add,
@@ -77,6 +169,9 @@ pub enum NumExprOp {
     Div,
     Mul,
     Mod,
+    Pow,
+    Min,
+    Max,
 }
 
 #[cw_serde]
@@ -86,23 +181,63 @@ pub enum DecimalFnOp {
     Floor,
     Sqrt,
     Ceil,
+    Log10,
+    Ln,
+    Exp,
+    // Rounds to precision decimal places
+    Round { precision: u32, mode: RoundingMode },
+}
+
+#[cw_serde]
+pub enum RoundingMode {
+    // towards zero
+    Down,
+    // away from zero
+    Up,
+    HalfUp,
+    HalfDown,
+    HalfEven,
 }
 
 #[cw_serde]
 pub enum IntFnOp {
     Abs,
     Neg,
+    // Durations converted to seconds
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    // UTC calendar fields of a timestamp (seconds), weekday being 1 (Monday) to 7 (Sunday)
+    Year,
+    Month,
+    Day,
+    Weekday,
+    Hour,
+    Minute,
+    // UTC timestamp (seconds) of the start of the day / week (Monday) / month of a timestamp
+    StartOfDay,
+    StartOfWeek,
+    StartOfMonth,
+    // Adds calendar months to the timestamp, clamping the day to the month's length
+    AddMonths { months: i64 },
 }
 
+// Numeric comparison, with both sides and the Between bound of the same value type
+pub type NumGenExpr<T, FnOp> =
+    GenExpr<NumValue<T, NumExprOp, FnOp>, NumOp<NumValue<T, NumExprOp, FnOp>>>;
+
 #[cw_serde]
 pub enum Expr {
     String(GenExpr<StringValue<String>, StringOp>),
-    Uint(GenExpr<NumValue<Uint256, NumExprOp, IntFnOp>, NumOp>),
-    Int(GenExpr<NumValue<i128, NumExprOp, IntFnOp>, NumOp>),
-    Decimal(GenExpr<NumValue<Decimal256, NumExprOp, DecimalFnOp>, NumOp>),
+    Uint(NumGenExpr<Uint256, IntFnOp>),
+    Int(NumGenExpr<Int256, IntFnOp>),
+    Decimal(NumGenExpr<Decimal256, DecimalFnOp>),
     Timestamp(TimeExpr),
     BlockHeight(BlockExpr),
     Bool(String), //ref
+    Balance(BalanceExpr),
 }
 
 // #[cw_serde]
@@ -110,14 +245,18 @@ pub enum Expr {
 //     Ref(String),
 // }
 
+// T is the type of the compared values, carried by the ops taking an extra operand
 #[cw_serde]
-pub enum NumOp {
+pub enum NumOp<T> {
     Eq,
     Neq,
     Lt,
     Gt,
     Gte,
     Lte,
+    // right <= left <= upper
+    Between { upper: T },
+    NotBetween { upper: T },
 }
 
 #[cw_serde]
@@ -133,4 +272,8 @@ pub enum StringOp {
     Contains,
     Eq,
     Neq,
+    // left is one of the given values, right is not used
+    In(Vec<String>),
+    // left matches the pattern in right, see util::regex in warp-resolver for the supported syntax
+    Matches,
 }
@@ -0,0 +1,223 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Neg;
+use std::str::FromStr;
+
+use cosmwasm_schema::schemars::gen::SchemaGenerator;
+use cosmwasm_schema::schemars::schema::Schema;
+use cosmwasm_schema::schemars::JsonSchema;
+use cosmwasm_schema::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use cosmwasm_std::{OverflowError, OverflowOperation, StdError, Uint256};
+
+// Signed 256-bit integer stored as sign and magnitude, covering -(2^256 - 1)..=2^256 - 1.
+// Serialized as a string like Uint256; integer JSON numbers are accepted too, so jobs written
+// when Int values were i128 numbers keep decoding.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Int256 {
+    negative: bool,
+    magnitude: Uint256,
+}
+
+impl Int256 {
+    pub const fn zero() -> Self {
+        Int256 {
+            negative: false,
+            magnitude: Uint256::zero(),
+        }
+    }
+
+    pub fn one() -> Self {
+        Int256::from(1i128)
+    }
+
+    pub fn new(negative: bool, magnitude: Uint256) -> Self {
+        Int256 {
+            // zero is never negative, keeping equality consistent
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn unsigned_abs(&self) -> Uint256 {
+        self.magnitude
+    }
+
+    pub fn abs(&self) -> Self {
+        Int256::new(false, self.magnitude)
+    }
+
+    pub fn checked_add(self, other: Self) -> Result<Self, OverflowError> {
+        if self.negative == other.negative {
+            let magnitude = self
+                .magnitude
+                .checked_add(other.magnitude)
+                .map_err(|_| overflow(OverflowOperation::Add, self, other))?;
+            return Ok(Int256::new(self.negative, magnitude));
+        }
+
+        Ok(if self.magnitude >= other.magnitude {
+            Int256::new(self.negative, self.magnitude - other.magnitude)
+        } else {
+            Int256::new(other.negative, other.magnitude - self.magnitude)
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self, OverflowError> {
+        self.checked_add(-other)
+            .map_err(|_| overflow(OverflowOperation::Sub, self, other))
+    }
+
+    pub fn checked_mul(self, other: Self) -> Result<Self, OverflowError> {
+        let magnitude = self
+            .magnitude
+            .checked_mul(other.magnitude)
+            .map_err(|_| overflow(OverflowOperation::Mul, self, other))?;
+        Ok(Int256::new(self.negative != other.negative, magnitude))
+    }
+
+    // Truncates towards zero like i128
+    pub fn checked_div(self, other: Self) -> Result<Self, StdError> {
+        let magnitude = self.magnitude.checked_div(other.magnitude)?;
+        Ok(Int256::new(self.negative != other.negative, magnitude))
+    }
+
+    // Remainder takes the sign of the dividend like i128
+    pub fn checked_rem(self, other: Self) -> Result<Self, StdError> {
+        let magnitude = self.magnitude.checked_rem(other.magnitude)?;
+        Ok(Int256::new(self.negative, magnitude))
+    }
+
+    pub fn checked_pow(self, exp: u32) -> Result<Self, OverflowError> {
+        let magnitude = self
+            .magnitude
+            .checked_pow(exp)
+            .map_err(|_| overflow(OverflowOperation::Pow, self, Int256::from(exp as i128)))?;
+        Ok(Int256::new(self.negative && exp % 2 == 1, magnitude))
+    }
+}
+
+fn overflow(operation: OverflowOperation, left: Int256, right: Int256) -> OverflowError {
+    OverflowError::new(operation, left, right)
+}
+
+impl Neg for Int256 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Int256::new(!self.negative, self.magnitude)
+    }
+}
+
+impl From<i128> for Int256 {
+    fn from(value: i128) -> Self {
+        Int256::new(value < 0, Uint256::from(value.unsigned_abs()))
+    }
+}
+
+impl From<Uint256> for Int256 {
+    fn from(value: Uint256) -> Self {
+        Int256::new(false, value)
+    }
+}
+
+impl FromStr for Int256 {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(StdError::generic_err(format!("Invalid Int256 {}", s)));
+        }
+
+        Ok(Int256::new(negative, Uint256::from_str(digits)?))
+    }
+}
+
+impl fmt::Display for Int256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.magnitude)
+    }
+}
+
+impl Ord for Int256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for Int256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Serialize for Int256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Int256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(Int256Visitor)
+    }
+}
+
+struct Int256Visitor;
+
+impl<'de> de::Visitor<'de> for Int256Visitor {
+    type Value = Int256;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string or integer encoded Int256")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Int256::from_str(v).map_err(|e| E::custom(e.to_string()))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Int256::from(v as i128))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Int256::from(v as i128))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        Ok(Int256::from(v))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        Ok(Int256::from(Uint256::from(v)))
+    }
+}
+
+impl JsonSchema for Int256 {
+    fn schema_name() -> String {
+        "Int256".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
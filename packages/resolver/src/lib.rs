@@ -1,4 +1,5 @@
 pub mod condition;
+pub mod int256;
 pub mod variable;
 
 use controller::{
@@ -6,7 +7,8 @@ use controller::{
     job::{Execution, ExternalInput, JobStatus},
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, QueryRequest};
+use cosmwasm_std::{Addr, QueryRequest, Uint64};
+
 #[cw_serde]
 pub struct InstantiateMsg {}
 
@@ -32,6 +34,8 @@ pub enum QueryMsg {
     QueryHydrateVars(QueryHydrateVarsMsg),
     #[returns(bool)]
     QueryResolveCondition(QueryResolveConditionMsg),
+    #[returns(ConditionTrace)]
+    QueryExplainCondition(QueryExplainConditionMsg),
     #[returns(String)]
     QueryApplyVarFn(QueryApplyVarFnMsg),
     #[returns(Vec<WarpMsg>)]
@@ -39,7 +43,10 @@ pub enum QueryMsg {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // Version of the code being migrated to, must match the new contract version
+    pub version: String,
+}
 
 #[cw_serde]
 pub struct WarpMsgsToCosmosMsgsMsg {
@@ -63,6 +70,8 @@ pub struct ExecuteHydrateVarsMsg {
     pub vars: String,
     pub external_inputs: Option<Vec<ExternalInput>>,
     pub warp_account_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_env: Option<JobEnv>,
 }
 
 #[cw_serde]
@@ -70,6 +79,8 @@ pub struct ExecuteResolveConditionMsg {
     pub condition: String,
     pub vars: String,
     pub warp_account_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_env: Option<JobEnv>,
 }
 
 #[cw_serde]
@@ -77,6 +88,8 @@ pub struct ExecuteApplyVarFnMsg {
     pub vars: String,
     pub status: JobStatus,
     pub warp_account_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_env: Option<JobEnv>,
 }
 
 #[cw_serde]
@@ -104,6 +117,8 @@ pub struct QueryHydrateVarsMsg {
     pub vars: String,
     pub external_inputs: Option<Vec<ExternalInput>>,
     pub warp_account_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_env: Option<JobEnv>,
 }
 
 #[cw_serde]
@@ -111,6 +126,47 @@ pub struct QueryResolveConditionMsg {
     pub condition: String,
     pub vars: String,
     pub warp_account_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_env: Option<JobEnv>,
+}
+
+#[cw_serde]
+pub struct QueryExplainConditionMsg {
+    pub condition: String,
+    pub vars: String,
+    pub warp_account_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_env: Option<JobEnv>,
+}
+
+// Condition tree with the outcome of every node, result being None when it resolved to error
+#[cw_serde]
+pub struct ConditionTrace {
+    pub node: ConditionTraceNode,
+    pub result: Option<bool>,
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub enum ConditionTraceNode {
+    And(Vec<ConditionTrace>),
+    Or(Vec<ConditionTrace>),
+    Not(Box<ConditionTrace>),
+    Try {
+        cond: Box<ConditionTrace>,
+        on_error: bool,
+    },
+    Expr(ExprTrace),
+}
+
+// Resolved operands of an Expr, None when the operand is missing or failed to resolve
+#[cw_serde]
+pub struct ExprTrace {
+    pub kind: String,
+    pub left: Option<String>,
+    pub op: String,
+    pub right: Option<String>,
+    pub upper: Option<String>,
 }
 
 #[cw_serde]
@@ -118,6 +174,22 @@ pub struct QueryApplyVarFnMsg {
     pub vars: String,
     pub status: JobStatus,
     pub warp_account_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_env: Option<JobEnv>,
+}
+
+// Job context resolved by StringEnvValue / NumEnvValue, provided by warp-controller
+#[cw_serde]
+#[derive(Default)]
+pub struct JobEnv {
+    pub warp_account_addr: Option<String>,
+    pub job_id: Option<Uint64>,
+    pub job_owner: Option<String>,
+    pub funding_account_addr: Option<String>,
+    pub controller_addr: Option<String>,
+    pub execution_count: Option<Uint64>,
+    pub job_created_at: Option<Uint64>,
+    pub last_execution_time: Option<Uint64>,
 }
 
 #[cw_serde]
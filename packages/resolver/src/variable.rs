@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal256, QueryRequest, Uint256};
+use cosmwasm_std::{Binary, Decimal256, QueryRequest, Uint256};
 
 use crate::condition::StringValue;
+use crate::int256::Int256;
 
 use super::condition::{DecimalFnOp, IntFnOp, NumExprOp, NumValue};
 
@@ -34,6 +35,16 @@ pub struct ExternalExpr {
     pub headers: Option<HashMap<String, String>>,
     pub body: Option<String>,
     pub selector: String,
+    // when set, inputs must be signed by one of the signers (see ExternalInputSignDoc)
+    pub trusted_signers: Option<Vec<TrustedSigner>>,
+    // inputs signed longer ago are rejected, requires trusted_signers
+    pub max_age_seconds: Option<u64>,
+}
+
+#[cw_serde]
+pub enum TrustedSigner {
+    Secp256k1(Binary),
+    Ed25519(Binary),
 }
 
 #[cw_serde]
@@ -51,6 +62,25 @@ pub struct QueryExpr {
     pub query: QueryRequest<String>,
 }
 
+// Reads contract storage directly with WasmQuery::Raw and decodes the stored JSON
+#[cw_serde]
+pub struct RawQueryExpr {
+    pub contract_addr: String,
+    // key of an Item, or namespace of a Map
+    pub namespace: String,
+    // Map key parts, empty for an Item
+    pub keys: Vec<RawKey>,
+    pub selector: String,
+}
+
+// cw-storage-plus Map key part, composite keys use several parts
+#[cw_serde]
+pub enum RawKey {
+    String(String),
+    Addr(String),
+    U64(String),
+}
+
 #[cw_serde]
 pub enum ExprOp {
     Add,
@@ -72,10 +102,10 @@ pub enum FnOp {
 #[cw_serde]
 pub enum FnValue {
     Uint(NumValue<Uint256, NumExprOp, IntFnOp>),
-    Int(NumValue<i128, NumExprOp, IntFnOp>),
+    Int(NumValue<Int256, NumExprOp, IntFnOp>),
     Decimal(NumValue<Decimal256, NumExprOp, DecimalFnOp>),
-    Timestamp(NumValue<i128, NumExprOp, IntFnOp>),
-    BlockHeight(NumValue<i128, NumExprOp, IntFnOp>),
+    Timestamp(NumValue<Int256, NumExprOp, IntFnOp>),
+    BlockHeight(NumValue<Int256, NumExprOp, IntFnOp>),
     Bool(String), //ref
     String(StringValue<String>),
 }
@@ -93,6 +123,8 @@ pub enum Variable {
     Static(StaticVariable),
     External(ExternalVariable),
     Query(QueryVariable),
+    History(HistoryVariable),
+    RawQuery(RawQueryVariable),
 }
 
 #[cw_serde]
@@ -104,6 +136,19 @@ pub struct StaticVariable {
     pub reinitialize: bool,
     pub value: Option<String>, //none if uninitialized
     pub update_fn: Option<UpdateFn>,
+    // value used by the on_error policy
+    pub default: Option<String>,
+    pub on_error: Option<OnErrorPolicy>, //fail if none
+}
+
+// What hydration does when a var's init_fn fails, e.g. a query erroring, a selector missing or
+// no external input being supplied
+#[cw_serde]
+pub enum OnErrorPolicy {
+    Fail,
+    UseDefault,
+    // falls back to default for uninitialized vars
+    UseLastValue,
 }
 
 #[cw_serde]
@@ -115,6 +160,9 @@ pub struct ExternalVariable {
     pub reinitialize: bool,
     pub value: Option<String>, //none if uninitialized
     pub update_fn: Option<UpdateFn>,
+    // value used by the on_error policy
+    pub default: Option<String>,
+    pub on_error: Option<OnErrorPolicy>, //fail if none
 }
 
 #[cw_serde]
@@ -126,4 +174,69 @@ pub struct QueryVariable {
     pub reinitialize: bool,
     pub value: Option<String>, //none if uninitialized
     pub update_fn: Option<UpdateFn>,
+    // value used by the on_error policy
+    pub default: Option<String>,
+    pub on_error: Option<OnErrorPolicy>, //fail if none
+}
+
+// Numeric variable of a recurring job hydrated like a static variable, which also records its
+// value after every apply_var_fn, keeping the latest capacity values for NumValue::History
+#[cw_serde]
+pub struct HistoryVariable {
+    pub kind: VariableKind,
+    pub name: String,
+    pub encode: bool,
+    pub init_fn: FnValue,
+    pub reinitialize: bool,
+    pub value: Option<String>, //none if uninitialized
+    pub update_fn: Option<UpdateFn>,
+    pub capacity: u32,
+    #[serde(default)]
+    pub history: Vec<String>, //oldest first
+}
+
+impl HistoryVariable {
+    pub fn to_static(&self) -> StaticVariable {
+        StaticVariable {
+            kind: self.kind.clone(),
+            name: self.name.clone(),
+            encode: self.encode,
+            init_fn: self.init_fn.clone(),
+            reinitialize: self.reinitialize,
+            value: self.value.clone(),
+            update_fn: self.update_fn.clone(),
+            default: None,
+            on_error: None,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RawQueryVariable {
+    pub kind: VariableKind,
+    pub name: String,
+    pub encode: bool,
+    pub init_fn: RawQueryExpr,
+    pub reinitialize: bool,
+    pub value: Option<String>, //none if uninitialized
+    pub update_fn: Option<UpdateFn>,
+    // value used by the on_error policy
+    pub default: Option<String>,
+    pub on_error: Option<OnErrorPolicy>, //fail if none
+}
+
+impl RawQueryVariable {
+    pub fn to_query(&self, init_fn: QueryExpr) -> QueryVariable {
+        QueryVariable {
+            kind: self.kind.clone(),
+            name: self.name.clone(),
+            encode: self.encode,
+            init_fn,
+            reinitialize: self.reinitialize,
+            value: self.value.clone(),
+            update_fn: self.update_fn.clone(),
+            default: self.default.clone(),
+            on_error: self.on_error.clone(),
+        }
+    }
 }
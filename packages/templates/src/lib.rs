@@ -5,6 +5,10 @@ use crate::template::{
     Template, TemplateResponse, TemplatesResponse,
 };
 
+pub use common::ownership::{
+    AcceptOwnershipMsg, CancelOwnershipProposalMsg, PendingOwner, PendingOwnerResponse,
+    ProposeOwnerMsg, QueryPendingOwnerMsg,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint64};
 
@@ -36,6 +40,10 @@ pub enum ExecuteMsg {
     DeleteTemplate(DeleteTemplateMsg),
 
     UpdateConfig(UpdateConfigMsg),
+
+    ProposeOwner(ProposeOwnerMsg),
+    AcceptOwnership(AcceptOwnershipMsg),
+    CancelOwnershipProposal(CancelOwnershipProposalMsg),
 }
 
 #[derive(QueryResponses)]
@@ -48,13 +56,19 @@ pub enum QueryMsg {
 
     #[returns(ConfigResponse)]
     QueryConfig(QueryConfigMsg),
+    #[returns(PendingOwnerResponse)]
+    QueryPendingOwner(QueryPendingOwnerMsg),
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // Version of the code being migrated to, must match the new contract version
+    pub version: String,
+}
 
 #[cw_serde]
 pub struct UpdateConfigMsg {
+    // Only proposes the new owner, who still has to send AcceptOwnership
     pub owner: Option<String>,
     pub fee_denom: Option<String>,
     pub template_fee: Option<Uint128>,
@@ -59,6 +59,7 @@ task(async ({ deployer, signer, refs }) => {
     queue_size_left: "5000",
     queue_size_right: "50000",
     burn_fee_rate: "25", // 25% of job reward
+    config_update_delay_seconds: "259200", // 3 days
   };
 
   await deployer.instantiate("warp-controller", instantiateControllerMsg, {